@@ -423,3 +423,52 @@ async fn allows_implementing_custom_node_messages() {
         )
     }
 }
+
+#[cfg(all(feature = "dac", feature = "mock_dac"))]
+#[tokio::test]
+async fn connects_with_indexes_in_audio_thread() {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use resonix::{AudioContext, ConstantNode, DACNode, MultiplyNode};
+
+    let data_written = Arc::new(Mutex::new(Vec::new()));
+
+    let mut audio_context = AudioContext::new()
+        .into_audio_init(Arc::clone(&data_written))
+        .unwrap();
+
+    // connect the output first, since the graph is processed between messages
+    let multiply_node_handle = audio_context.add_node(MultiplyNode::new(2)).await.unwrap();
+    let dac_node_handle = audio_context.add_node(DACNode::new(2)).await.unwrap();
+    audio_context
+        .connect(&multiply_node_handle, &dac_node_handle)
+        .await
+        .unwrap();
+
+    // multiply 0.5 by 1.5 by connecting each constant to a different input
+    let constant_node_a_handle = audio_context
+        .add_node(ConstantNode::new(2, 0.5))
+        .await
+        .unwrap();
+    let constant_node_b_handle = audio_context
+        .add_node(ConstantNode::new(2, 1.5))
+        .await
+        .unwrap();
+    audio_context
+        .connect_with_indexes(&constant_node_a_handle, &multiply_node_handle, 0, 0)
+        .await
+        .unwrap();
+    audio_context
+        .connect_with_indexes(&constant_node_b_handle, &multiply_node_handle, 0, 1)
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    {
+        let data_written = data_written.lock().unwrap();
+        assert_eq!(data_written[(data_written.len() - 100)..], [0.75; 100])
+    }
+}
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use crate::{NumChannels, SampleRate};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AudioBufferError {
+    #[error("All channels in an AudioBuffer must have the same length. Expected {expected} frames but channel {channel} has {found}")]
+    MismatchedChannelLengths {
+        channel: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error(
+        "Interleaved buffer of length {len} cannot be evenly divided into {num_channels} channels"
+    )]
+    InvalidInterleavedLength { len: usize, num_channels: usize },
+}
+
+/// A block of multichannel audio along with the sample rate it was recorded at.
+///
+/// Samples are stored planar (i.e. one `Vec` of samples per channel),
+/// and every channel is guaranteed to have the same number of frames.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AudioBuffer {
+    channels: Vec<Vec<f32>>,
+    sample_rate: SampleRate,
+}
+
+impl AudioBuffer {
+    /// Creates a silent buffer
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        num_frames: usize,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels: NumChannels = num_channels.into();
+        Self {
+            channels: vec![vec![0.0; num_frames]; *num_channels],
+            sample_rate: sample_rate.into(),
+        }
+    }
+
+    pub fn from_channels(
+        channels: Vec<Vec<f32>>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Result<Self, AudioBufferError> {
+        let expected = channels.first().map(Vec::len).unwrap_or_default();
+        if let Some((channel, samples)) = channels
+            .iter()
+            .enumerate()
+            .find(|(_, samples)| samples.len() != expected)
+        {
+            return Err(AudioBufferError::MismatchedChannelLengths {
+                channel,
+                expected,
+                found: samples.len(),
+            });
+        }
+
+        Ok(Self {
+            channels,
+            sample_rate: sample_rate.into(),
+        })
+    }
+
+    /// Splits a buffer of interleaved samples (i.e. `[L, R, L, R, ...]`) into separate channels
+    pub fn from_interleaved(
+        samples: &[f32],
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Result<Self, AudioBufferError> {
+        let num_channels = *num_channels.into();
+        if num_channels == 0 || !samples.chunks_exact(num_channels).remainder().is_empty() {
+            return Err(AudioBufferError::InvalidInterleavedLength {
+                len: samples.len(),
+                num_channels,
+            });
+        }
+
+        let num_frames = samples.len() / num_channels;
+        let mut buffer = Self::new(num_channels, num_frames, sample_rate);
+        samples
+            .chunks_exact(num_channels)
+            .enumerate()
+            .for_each(|(frame_index, frame)| {
+                frame
+                    .iter()
+                    .zip(buffer.channels.iter_mut())
+                    .for_each(|(sample, channel)| channel[frame_index] = *sample);
+            });
+
+        Ok(buffer)
+    }
+
+    /// Combines all channels into a single buffer of interleaved samples (i.e. `[L, R, L, R, ...]`)
+    pub fn to_interleaved(&self) -> Vec<f32> {
+        let num_channels = self.channels.len();
+        let mut samples = vec![0.0; num_channels * self.num_frames()];
+        self.channels
+            .iter()
+            .enumerate()
+            .for_each(|(channel_index, channel)| {
+                channel
+                    .iter()
+                    .enumerate()
+                    .for_each(|(frame_index, sample)| {
+                        samples[frame_index * num_channels + channel_index] = *sample;
+                    });
+            });
+        samples
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.channels.len())
+    }
+
+    pub fn num_frames(&self) -> usize {
+        self.channels.first().map(Vec::len).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_frames() == 0
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self
+    }
+
+    /// Length of the buffer when played back at its own sample rate
+    pub fn duration(&self) -> Duration {
+        if self.sample_rate.get() == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.num_frames() as f64 / self.sample_rate.get() as f64)
+    }
+
+    pub fn channel(&self, channel: usize) -> Option<&[f32]> {
+        self.channels.get(channel).map(Vec::as_slice)
+    }
+
+    pub fn channel_mut(&mut self, channel: usize) -> Option<&mut [f32]> {
+        self.channels.get_mut(channel).map(Vec::as_mut_slice)
+    }
+
+    pub fn channels(&self) -> &[Vec<f32>] {
+        &self.channels
+    }
+
+    pub fn into_channels(self) -> Vec<Vec<f32>> {
+        self.channels
+    }
+}
+
+#[cfg(test)]
+mod test_audio_buffer {
+    use std::time::Duration;
+
+    use crate::{AudioBuffer, AudioBufferError};
+
+    #[test]
+    fn it_should_convert_to_and_from_interleaved_samples() {
+        let interleaved = vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0];
+        let buffer = AudioBuffer::from_interleaved(&interleaved, 2, 44100).unwrap();
+
+        assert_eq!(*buffer.num_channels(), 2);
+        assert_eq!(buffer.num_frames(), 3);
+        assert_eq!(buffer.channel(0).unwrap(), &[1.0, 2.0, 3.0]);
+        assert_eq!(buffer.channel(1).unwrap(), &[-1.0, -2.0, -3.0]);
+        assert_eq!(buffer.to_interleaved(), interleaved);
+    }
+
+    #[test]
+    fn it_should_reject_mismatched_channels() {
+        let result = AudioBuffer::from_channels(vec![vec![0.0; 2], vec![0.0; 3]], 44100);
+        assert_eq!(
+            result.unwrap_err(),
+            AudioBufferError::MismatchedChannelLengths {
+                channel: 1,
+                expected: 2,
+                found: 3
+            }
+        );
+
+        assert!(AudioBuffer::from_interleaved(&[0.0; 3], 2, 44100).is_err());
+    }
+
+    #[test]
+    fn it_should_calculate_duration() {
+        let buffer = AudioBuffer::new(1, 22050, 44100);
+        assert_eq!(buffer.duration(), Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,5 @@
+mod convolver;
+mod impulse_response;
+
+pub use convolver::*;
+pub use impulse_response::*;
@@ -0,0 +1,398 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rustfft::num_complex::Complex;
+
+use crate::{ConvolverError, ImpulseResponse, ImpulseResponseLayout, NumChannels};
+
+/// Convolves a multichannel signal with an `ImpulseResponse` (e.g. to apply the reverb of a real room).
+///
+/// Uses uniformly partitioned FFT convolution: the signal is processed in blocks of the impulse
+/// response's `block_size`, so the output is delayed by exactly `block_size` samples, no matter
+/// how long the impulse response is.
+///
+/// Once created, convolving never allocates. Swapping in an impulse response doesn't allocate
+/// either: room is made up front for impulse responses up to `max_num_frames` long.
+#[derive(Clone)]
+pub struct Convolver {
+    impulse_response: Arc<ImpulseResponse>,
+    /// Position within the current block
+    block_index: usize,
+    /// The previous block and the current block of input, for each channel
+    input_blocks: Vec<Vec<f32>>,
+    /// The spectra of the most recent input blocks, for each channel (circular buffers)
+    input_spectra: Vec<Vec<Vec<Complex<f32>>>>,
+    /// Index of the most recent spectrum in `input_spectra`
+    spectrum_index: usize,
+    /// The output currently being played back, for each channel
+    output_blocks: Vec<Vec<f32>>,
+    fft_buffer: Vec<Complex<f32>>,
+    fft_scratch: Vec<Complex<f32>>,
+}
+
+impl Convolver {
+    /// Makes room for impulse responses as long as `impulse_response`
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+    ) -> Result<Self, ConvolverError> {
+        let max_num_frames = impulse_response.num_frames();
+        Self::new_with_max_len(num_channels, impulse_response, max_num_frames)
+    }
+
+    /// Makes room for impulse responses up to `max_num_frames` long (or as long as
+    /// `impulse_response`, if that's longer), so that they can be swapped in without allocating
+    pub fn new_with_max_len(
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+        max_num_frames: usize,
+    ) -> Result<Self, ConvolverError> {
+        let num_channels = *num_channels.into();
+        Self::validate_layout(num_channels, &impulse_response)?;
+
+        let block_size = impulse_response.block_size();
+        let max_num_partitions = impulse_response
+            .num_partitions()
+            .max(max_num_frames.div_ceil(block_size));
+        let fft_size = block_size * 2;
+        let scratch_len = impulse_response
+            .forward_fft()
+            .get_inplace_scratch_len()
+            .max(impulse_response.inverse_fft().get_inplace_scratch_len());
+
+        Ok(Self {
+            block_index: 0,
+            input_blocks: vec![vec![0.0; fft_size]; num_channels],
+            input_spectra: vec![
+                vec![vec![Complex::default(); fft_size]; max_num_partitions];
+                num_channels
+            ],
+            spectrum_index: 0,
+            output_blocks: vec![vec![0.0; block_size]; num_channels],
+            fft_buffer: vec![Complex::default(); fft_size],
+            fft_scratch: vec![Complex::default(); scratch_len],
+            impulse_response,
+        })
+    }
+
+    fn validate_layout(
+        num_channels: usize,
+        impulse_response: &ImpulseResponse,
+    ) -> Result<(), ConvolverError> {
+        if impulse_response.layout() == ImpulseResponseLayout::TrueStereo && num_channels != 2 {
+            return Err(ConvolverError::TrueStereoChannelMismatch(num_channels));
+        }
+        Ok(())
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.input_blocks.len())
+    }
+
+    pub fn impulse_response(&self) -> &Arc<ImpulseResponse> {
+        &self.impulse_response
+    }
+
+    /// The longest impulse response (in frames) that can be swapped in
+    pub fn max_num_frames(&self) -> usize {
+        self.input_spectra.first().map_or(0, Vec::len) * self.impulse_response.block_size()
+    }
+
+    /// Replaces the impulse response, while keeping the recent input history, so that
+    /// the signal continues smoothly. Never allocates: the new impulse response must have
+    /// the same block size and be no longer than `max_num_frames`.
+    ///
+    /// Returns the previous impulse response, so that it can be dropped
+    /// somewhere other than the audio thread.
+    pub fn set_impulse_response(
+        &mut self,
+        impulse_response: Arc<ImpulseResponse>,
+    ) -> Result<Arc<ImpulseResponse>, ConvolverError> {
+        Self::validate_layout(self.input_blocks.len(), &impulse_response)?;
+
+        let expected_block_size = self.impulse_response.block_size();
+        if impulse_response.block_size() != expected_block_size {
+            return Err(ConvolverError::BlockSizeMismatch {
+                block_size: impulse_response.block_size(),
+                expected_block_size,
+            });
+        }
+
+        let max_num_frames = self.max_num_frames();
+        if impulse_response.num_frames() > max_num_frames {
+            return Err(ConvolverError::ImpulseResponseTooLong {
+                num_frames: impulse_response.num_frames(),
+                max_num_frames,
+            });
+        }
+
+        Ok(std::mem::replace(
+            &mut self.impulse_response,
+            impulse_response,
+        ))
+    }
+
+    /// Silences the input history and any pending output
+    pub fn clear(&mut self) -> &mut Self {
+        self.block_index = 0;
+        self.input_blocks
+            .iter_mut()
+            .for_each(|block| block.fill(0.0));
+        self.input_spectra
+            .iter_mut()
+            .flatten()
+            .for_each(|spectrum| spectrum.fill(Complex::default()));
+        self.output_blocks
+            .iter_mut()
+            .for_each(|block| block.fill(0.0));
+        self
+    }
+
+    /// Convolves one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        let block_size = self.impulse_response.block_size();
+
+        frame
+            .iter_mut()
+            .zip(self.input_blocks.iter_mut())
+            .zip(self.output_blocks.iter())
+            .for_each(|((sample, input_block), output_block)| {
+                input_block[block_size + self.block_index] = *sample;
+                *sample = output_block[self.block_index];
+            });
+
+        self.block_index += 1;
+        if self.block_index == block_size {
+            self.block_index = 0;
+            self.process_block();
+        }
+    }
+
+    /// Transforms the most recent block of input and calculates the next block of output
+    fn process_block(&mut self) {
+        let impulse_response = &self.impulse_response;
+        let block_size = impulse_response.block_size();
+        let num_partitions = impulse_response.num_partitions();
+        let capacity = self.input_spectra.first().map_or(1, Vec::len);
+
+        // the oldest spectrum is overwritten with the newest
+        self.spectrum_index = (self.spectrum_index + capacity - 1) % capacity;
+
+        for (input_block, input_spectra) in self
+            .input_blocks
+            .iter_mut()
+            .zip(self.input_spectra.iter_mut())
+        {
+            let spectrum = &mut input_spectra[self.spectrum_index];
+            spectrum
+                .iter_mut()
+                .zip(input_block.iter())
+                .for_each(|(bin, sample)| *bin = Complex::new(*sample, 0.0));
+            impulse_response
+                .forward_fft()
+                .process_with_scratch(spectrum, &mut self.fft_scratch);
+
+            // the current block becomes the previous block
+            input_block.copy_within(block_size.., 0);
+        }
+
+        let normalization = 1.0 / (block_size * 2) as f32;
+        let layout = impulse_response.layout();
+
+        for (output_channel, output_block) in self.output_blocks.iter_mut().enumerate() {
+            self.fft_buffer.fill(Complex::default());
+
+            for (input_channel, impulse_response_channel) in layout.paths(output_channel) {
+                for partition in 0..num_partitions {
+                    let input_spectrum = &self.input_spectra[input_channel]
+                        [(self.spectrum_index + partition) % capacity];
+                    let partition_spectrum =
+                        impulse_response.partition(impulse_response_channel, partition);
+
+                    self.fft_buffer
+                        .iter_mut()
+                        .zip(input_spectrum.iter().zip(partition_spectrum.iter()))
+                        .for_each(|(bin, (x, h))| *bin += x * h);
+                }
+            }
+
+            impulse_response
+                .inverse_fft()
+                .process_with_scratch(&mut self.fft_buffer, &mut self.fft_scratch);
+
+            // the first half of the result is circular convolution garbage (overlap-save)
+            output_block
+                .iter_mut()
+                .zip(self.fft_buffer[block_size..].iter())
+                .for_each(|(sample, bin)| *sample = bin.re * normalization);
+        }
+    }
+}
+
+impl Debug for Convolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Convolver")
+            .field("num_channels", &self.input_blocks.len())
+            .field("impulse_response", &self.impulse_response)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test_convolver {
+    use std::sync::Arc;
+
+    use crate::{AudioBuffer, Convolver, ConvolverError, ImpulseResponse};
+
+    fn impulse_response(channels: Vec<Vec<f32>>, block_size: usize) -> Arc<ImpulseResponse> {
+        let buffer = AudioBuffer::from_channels(channels, 44100).unwrap();
+        Arc::new(ImpulseResponse::new(&buffer, block_size, false).unwrap())
+    }
+
+    fn render(convolver: &mut Convolver, input: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        input
+            .iter()
+            .map(|frame| {
+                let mut frame = frame.clone();
+                convolver.process_frame(&mut frame);
+                frame
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        actual
+            .iter()
+            .zip(expected.iter())
+            .enumerate()
+            .for_each(|(i, (actual, expected))| {
+                assert!(
+                    (actual - expected).abs() < 0.0001,
+                    "sample {i}: {actual} != {expected}"
+                )
+            });
+    }
+
+    #[test]
+    fn it_should_delay_by_one_block() {
+        let mut convolver = Convolver::new(1, impulse_response(vec![vec![1.0]], 16)).unwrap();
+
+        let mut input = vec![vec![0.0]; 40];
+        input[3] = vec![1.0];
+        let output: Vec<f32> = render(&mut convolver, &input).concat();
+
+        let mut expected = vec![0.0; 40];
+        expected[19] = 1.0;
+        assert_close(&output, &expected);
+    }
+
+    #[test]
+    fn it_should_match_direct_convolution_across_partitions() {
+        let ir: Vec<f32> = (0..50)
+            .map(|i| ((i * 7) % 11) as f32 / 11.0 - 0.5)
+            .collect();
+        let signal: Vec<f32> = (0..200)
+            .map(|i| ((i * 5) % 13) as f32 / 13.0 - 0.5)
+            .collect();
+        let mut convolver = Convolver::new(1, impulse_response(vec![ir.clone()], 16)).unwrap();
+
+        let input: Vec<Vec<f32>> = signal.iter().map(|sample| vec![*sample]).collect();
+        let output: Vec<f32> = render(&mut convolver, &input).concat();
+
+        let expected: Vec<f32> = (0..200)
+            .map(|n| {
+                // output is delayed by one block
+                let n = n as isize - 16;
+                (0..ir.len())
+                    .filter(|&k| n - k as isize >= 0)
+                    .map(|k| ir[k] * signal[(n - k as isize) as usize])
+                    .sum()
+            })
+            .collect();
+        assert_close(&output, &expected);
+    }
+
+    #[test]
+    fn it_should_route_true_stereo_impulse_responses() {
+        // left feeds left at a gain of 1.0 and right at 0.5; right only feeds right at 0.25
+        let mut convolver = Convolver::new(
+            2,
+            impulse_response(vec![vec![1.0], vec![0.5], vec![0.0], vec![0.25]], 16),
+        )
+        .unwrap();
+
+        let mut input = vec![vec![0.0, 0.0]; 32];
+        input[0] = vec![1.0, 1.0];
+        let output = render(&mut convolver, &input);
+
+        assert_close(&output[16], &[1.0, 0.75]);
+        assert!(Convolver::new(1, convolver.impulse_response().clone()).is_err());
+    }
+
+    #[test]
+    fn it_should_normalize_impulse_responses() {
+        let buffer = AudioBuffer::from_channels(vec![vec![3.0, 4.0]], 44100).unwrap();
+        let mut convolver = Convolver::new(
+            1,
+            Arc::new(ImpulseResponse::new(&buffer, 16, true).unwrap()),
+        )
+        .unwrap();
+
+        let mut input = vec![vec![0.0]; 18];
+        input[0] = vec![1.0];
+        let output: Vec<f32> = render(&mut convolver, &input).concat();
+        assert_close(&output[16..], &[0.6, 0.8]);
+    }
+
+    #[test]
+    fn it_should_swap_impulse_responses() {
+        let mut convolver = Convolver::new(2, impulse_response(vec![vec![1.0; 40]], 16)).unwrap();
+
+        convolver
+            .set_impulse_response(impulse_response(vec![vec![0.5], vec![-0.5]], 16))
+            .unwrap();
+        let mut input = vec![vec![0.0, 0.0]; 17];
+        input[0] = vec![1.0, 1.0];
+        assert_close(&render(&mut convolver, &input)[16], &[0.5, -0.5]);
+
+        let mut mono_convolver = Convolver::new(1, impulse_response(vec![vec![1.0]], 16)).unwrap();
+        assert_eq!(
+            mono_convolver
+                .set_impulse_response(impulse_response(vec![vec![1.0]; 4], 16))
+                .unwrap_err(),
+            ConvolverError::TrueStereoChannelMismatch(1)
+        );
+    }
+
+    #[test]
+    fn it_should_reject_impulse_responses_that_do_not_fit() {
+        let mut convolver =
+            Convolver::new_with_max_len(1, impulse_response(vec![vec![1.0]], 16), 40).unwrap();
+        assert_eq!(convolver.max_num_frames(), 48);
+
+        let previous_impulse_response = Arc::clone(convolver.impulse_response());
+        let replaced = convolver
+            .set_impulse_response(impulse_response(vec![vec![0.5; 48]], 16))
+            .unwrap();
+        assert!(Arc::ptr_eq(&replaced, &previous_impulse_response));
+
+        assert_eq!(
+            convolver
+                .set_impulse_response(impulse_response(vec![vec![0.5; 49]], 16))
+                .unwrap_err(),
+            ConvolverError::ImpulseResponseTooLong {
+                num_frames: 49,
+                max_num_frames: 48,
+            }
+        );
+        assert_eq!(
+            convolver
+                .set_impulse_response(impulse_response(vec![vec![0.5]], 32))
+                .unwrap_err(),
+            ConvolverError::BlockSizeMismatch {
+                block_size: 32,
+                expected_block_size: 16,
+            }
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+use crate::{AudioBuffer, SampleRate};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConvolverError {
+    #[error("Block size must be a power of 2 between {min} and {max}. Received {0}", min = ImpulseResponse::MIN_BLOCK_SIZE, max = ImpulseResponse::MAX_BLOCK_SIZE)]
+    InvalidBlockSize(usize),
+    #[error("Impulse response must contain at least one frame")]
+    EmptyImpulseResponse,
+    #[error(
+        "Impulse response must have 1 (mono), 2 (stereo) or 4 (true stereo) channels. Received {0}"
+    )]
+    UnsupportedChannelCount(usize),
+    #[error(
+        "True stereo impulse responses can only convolve 2 channels, but the convolver has {0}"
+    )]
+    TrueStereoChannelMismatch(usize),
+    #[error("Impulse response has a block size of {block_size}, but the convolver uses a block size of {expected_block_size}")]
+    BlockSizeMismatch {
+        block_size: usize,
+        expected_block_size: usize,
+    },
+    #[error("Impulse response is {num_frames} frames long, but the convolver only has room for {max_num_frames}")]
+    ImpulseResponseTooLong {
+        num_frames: usize,
+        max_num_frames: usize,
+    },
+}
+
+/// How the channels of an impulse response are applied to the channels of a signal
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImpulseResponseLayout {
+    /// Every channel of the signal is convolved with the same impulse response
+    Mono,
+    /// Even channels are convolved with the first channel of the impulse response,
+    /// and odd channels are convolved with the second
+    Stereo,
+    /// A stereo signal where both input channels feed both output channels.
+    /// The impulse response channels are (in order): left to left, left to right,
+    /// right to left, and right to right
+    TrueStereo,
+}
+
+impl ImpulseResponseLayout {
+    /// The (input channel, impulse response channel) pairs that are summed into `output_channel`
+    #[inline]
+    pub(crate) fn paths(&self, output_channel: usize) -> impl Iterator<Item = (usize, usize)> {
+        let paths = match self {
+            ImpulseResponseLayout::Mono => [Some((output_channel, 0)), None],
+            ImpulseResponseLayout::Stereo => [Some((output_channel, output_channel % 2)), None],
+            ImpulseResponseLayout::TrueStereo => {
+                [Some((0, output_channel)), Some((1, output_channel + 2))]
+            }
+        };
+        paths.into_iter().flatten()
+    }
+}
+
+/// An impulse response that has been split into equally sized partitions and
+/// transformed into the frequency domain, ready to be used by a `Convolver`.
+///
+/// Preparing an impulse response plans FFTs and allocates memory, so it should be
+/// done outside of the audio thread (and then sent to the audio thread in an `Arc`).
+#[derive(Clone)]
+pub struct ImpulseResponse {
+    block_size: usize,
+    sample_rate: SampleRate,
+    num_frames: usize,
+    layout: ImpulseResponseLayout,
+    forward_fft: Arc<dyn Fft<f32>>,
+    inverse_fft: Arc<dyn Fft<f32>>,
+    /// The spectrum of every partition, for every channel of the impulse response
+    partitions: Vec<Vec<Vec<Complex<f32>>>>,
+}
+
+impl ImpulseResponse {
+    pub const MIN_BLOCK_SIZE: usize = 16;
+    pub const MAX_BLOCK_SIZE: usize = 8192;
+    pub const DEFAULT_BLOCK_SIZE: usize = 256;
+
+    /// Prepares `buffer` to be convolved in blocks of `block_size` samples,
+    /// which is also how much latency the convolution adds.
+    ///
+    /// When `normalize` is true, the impulse response is scaled so that its loudest channel
+    /// has a total energy (sum of squares) of 1.0, which keeps different impulse responses
+    /// at a similar volume.
+    pub fn new(
+        buffer: &AudioBuffer,
+        block_size: usize,
+        normalize: bool,
+    ) -> Result<Self, ConvolverError> {
+        if !block_size.is_power_of_two()
+            || !(Self::MIN_BLOCK_SIZE..=Self::MAX_BLOCK_SIZE).contains(&block_size)
+        {
+            return Err(ConvolverError::InvalidBlockSize(block_size));
+        }
+
+        if buffer.is_empty() {
+            return Err(ConvolverError::EmptyImpulseResponse);
+        }
+
+        let layout = match *buffer.num_channels() {
+            1 => ImpulseResponseLayout::Mono,
+            2 => ImpulseResponseLayout::Stereo,
+            4 => ImpulseResponseLayout::TrueStereo,
+            num_channels => return Err(ConvolverError::UnsupportedChannelCount(num_channels)),
+        };
+
+        let gain = if normalize {
+            let max_energy = buffer
+                .channels()
+                .iter()
+                .map(|channel| channel.iter().map(|sample| sample * sample).sum::<f32>())
+                .fold(0.0, f32::max);
+            if max_energy > 0.0 {
+                1.0 / max_energy.sqrt()
+            } else {
+                1.0
+            }
+        } else {
+            1.0
+        };
+
+        let fft_size = block_size * 2;
+        let mut planner = FftPlanner::new();
+        let forward_fft = planner.plan_fft_forward(fft_size);
+        let inverse_fft = planner.plan_fft_inverse(fft_size);
+
+        let partitions = buffer
+            .channels()
+            .iter()
+            .map(|channel| {
+                channel
+                    .chunks(block_size)
+                    .map(|chunk| {
+                        // each partition is zero-padded to the FFT size
+                        let mut spectrum = vec![Complex::default(); fft_size];
+                        spectrum
+                            .iter_mut()
+                            .zip(chunk.iter())
+                            .for_each(|(bin, sample)| bin.re = sample * gain);
+                        forward_fft.process(&mut spectrum);
+                        spectrum
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            block_size,
+            sample_rate: buffer.sample_rate(),
+            num_frames: buffer.num_frames(),
+            layout,
+            forward_fft,
+            inverse_fft,
+            partitions,
+        })
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Sample rate of the buffer that the impulse response was prepared from
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    /// Length of the impulse response (before it was partitioned)
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    pub fn layout(&self) -> ImpulseResponseLayout {
+        self.layout
+    }
+
+    pub fn num_partitions(&self) -> usize {
+        self.partitions.first().map_or(0, Vec::len)
+    }
+
+    pub(crate) fn forward_fft(&self) -> &Arc<dyn Fft<f32>> {
+        &self.forward_fft
+    }
+
+    pub(crate) fn inverse_fft(&self) -> &Arc<dyn Fft<f32>> {
+        &self.inverse_fft
+    }
+
+    #[inline]
+    pub(crate) fn partition(&self, channel: usize, partition: usize) -> &[Complex<f32>] {
+        &self.partitions[channel][partition]
+    }
+}
+
+impl Debug for ImpulseResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImpulseResponse")
+            .field("block_size", &self.block_size)
+            .field("sample_rate", &self.sample_rate)
+            .field("num_frames", &self.num_frames)
+            .field("layout", &self.layout)
+            .field("num_partitions", &self.num_partitions())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test_impulse_response {
+    use crate::{AudioBuffer, ConvolverError, ImpulseResponse, ImpulseResponseLayout};
+
+    #[test]
+    fn it_should_partition_impulse_response() {
+        let buffer = AudioBuffer::new(2, 100, 44100);
+        let impulse_response = ImpulseResponse::new(&buffer, 32, false).unwrap();
+
+        assert_eq!(impulse_response.num_partitions(), 4);
+        assert_eq!(impulse_response.layout(), ImpulseResponseLayout::Stereo);
+    }
+
+    #[test]
+    fn it_should_reject_invalid_impulse_responses() {
+        let buffer = AudioBuffer::new(1, 100, 44100);
+        assert_eq!(
+            ImpulseResponse::new(&buffer, 100, false).unwrap_err(),
+            ConvolverError::InvalidBlockSize(100)
+        );
+        assert_eq!(
+            ImpulseResponse::new(&AudioBuffer::new(1, 0, 44100), 32, false).unwrap_err(),
+            ConvolverError::EmptyImpulseResponse
+        );
+        assert_eq!(
+            ImpulseResponse::new(&AudioBuffer::new(3, 100, 44100), 32, false).unwrap_err(),
+            ConvolverError::UnsupportedChannelCount(3)
+        );
+    }
+}
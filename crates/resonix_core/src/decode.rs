@@ -0,0 +1,188 @@
+use std::io::{Cursor, ErrorKind};
+
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::{DecoderOptions, CODEC_TYPE_NULL},
+    errors::Error as SymphoniaError,
+    formats::FormatOptions,
+    io::MediaSourceStream,
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use crate::{AudioBuffer, AudioBufferError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecodeError {
+    #[error("Audio data could not be decoded. Original error: {0:?}")]
+    Symphonia(#[from] SymphoniaError),
+    #[error("No decodable audio track was found")]
+    NoAudioTrack,
+    #[error("The sample rate of the audio could not be determined")]
+    UnknownSampleRate,
+    #[error("Decoded audio could not be converted into an AudioBuffer. Original error: {0:?}")]
+    AudioBuffer(#[from] AudioBufferError),
+}
+
+/// Decodes the bytes of an encoded audio file (WAV, FLAC, MP3, or OGG Vorbis)
+/// into an `AudioBuffer` with the file's own sample rate and number of channels.
+///
+/// The format is detected from the bytes themselves. If detection fails for
+/// a particular file, try `decode_audio_with_extension` instead.
+pub fn decode_audio(bytes: &[u8]) -> Result<AudioBuffer, DecodeError> {
+    decode(bytes, Hint::new())
+}
+
+/// Decodes the bytes of an encoded audio file, using the file's extension
+/// (e.g. `"mp3"`) as a hint for which format to decode.
+pub fn decode_audio_with_extension(
+    bytes: &[u8],
+    extension: &str,
+) -> Result<AudioBuffer, DecodeError> {
+    let mut hint = Hint::new();
+    hint.with_extension(extension);
+    decode(bytes, hint)
+}
+
+fn decode(bytes: &[u8], hint: Hint) -> Result<AudioBuffer, DecodeError> {
+    let media_source_stream =
+        MediaSourceStream::new(Box::new(Cursor::new(bytes.to_vec())), Default::default());
+
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            media_source_stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?
+        .format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or(DecodeError::NoAudioTrack)?;
+    let track_id = track.id;
+    let codec_sample_rate = track.codec_params.sample_rate;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    // the sample rate reported by the decoded audio is preferred over the
+    // container's metadata, since some formats (e.g. MP3) don't report it reliably
+    let mut sample_rate = None;
+    let mut channels: Vec<Vec<f32>> = Vec::new();
+    let mut sample_buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(error)) if error.kind() == ErrorKind::UnexpectedEof => {
+                break;
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // corrupted packets can be skipped without losing the rest of the audio
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        let spec = *decoded.spec();
+        let num_frames = decoded.frames();
+        sample_rate.get_or_insert(spec.rate);
+
+        if channels.is_empty() {
+            channels = vec![Vec::new(); spec.channels.count()];
+        }
+
+        let sample_buffer = match &mut sample_buffer {
+            Some(sample_buffer)
+                if sample_buffer.capacity() >= decoded.capacity() * channels.len() =>
+            {
+                sample_buffer
+            }
+            _ => sample_buffer.insert(SampleBuffer::new(decoded.capacity() as u64, spec)),
+        };
+        sample_buffer.copy_planar_ref(decoded);
+
+        // planar samples are stored one channel after another
+        channels
+            .iter_mut()
+            .zip(sample_buffer.samples().chunks_exact(num_frames.max(1)))
+            .for_each(|(channel, samples)| channel.extend_from_slice(samples));
+    }
+
+    let sample_rate = sample_rate
+        .or(codec_sample_rate)
+        .ok_or(DecodeError::UnknownSampleRate)?;
+
+    Ok(AudioBuffer::from_channels(channels, sample_rate)?)
+}
+
+#[cfg(test)]
+mod test_decode {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{decode_audio, decode_audio_with_extension};
+
+    /// Encodes interleaved samples as a 16-bit PCM WAV file
+    fn encode_wav(samples: &[i16], num_channels: u16, sample_rate: u32) -> Vec<u8> {
+        let data_len = (samples.len() * 2) as u32;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&num_channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * num_channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(num_channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        samples
+            .iter()
+            .for_each(|sample| bytes.extend_from_slice(&sample.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn it_should_decode_multichannel_wav() {
+        let bytes = encode_wav(&[16384, -16384, 0, 32767, -32768, 8192], 3, 22050);
+        let buffer = decode_audio(&bytes).unwrap();
+
+        assert_eq!(buffer.sample_rate().get(), 22050);
+        assert_eq!(*buffer.num_channels(), 3);
+        assert_eq!(buffer.num_frames(), 2);
+
+        let expected = [[0.5, 1.0], [-0.5, -1.0], [0.0, 0.25]];
+        for (channel, expected) in buffer.channels().iter().zip(expected.iter()) {
+            for (sample, expected) in channel.iter().zip(expected.iter()) {
+                assert_difference_is_within_tolerance(*sample, *expected, 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_decode_mp3_with_true_sample_rate_and_channels() {
+        let bytes = include_bytes!("../../../assets/resonances_4.mp3");
+        let buffer = decode_audio_with_extension(bytes, "mp3").unwrap();
+
+        assert_eq!(*buffer.num_channels(), 2);
+        assert_eq!(buffer.sample_rate().get(), 44100);
+        assert!(buffer.duration().as_secs() > 10);
+    }
+
+    #[test]
+    fn it_should_error_on_invalid_data() {
+        assert!(decode_audio(&[0, 1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+mod delay_line;
+
+pub use delay_line::*;
@@ -0,0 +1,124 @@
+use crate::{cubic_interpolate, linear_interpolate, Interpolation};
+
+/// A fixed-size circular buffer of the most recent samples of a signal,
+/// which can be read from at any (fractional) number of samples in the past.
+///
+/// All memory is allocated up front, so reading and writing never allocates.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct DelayLine {
+    buffer: Vec<f32>,
+    /// Index that the next sample will be written to
+    write_index: usize,
+    max_delay: usize,
+}
+
+impl DelayLine {
+    /// Extra samples kept beyond `max_delay`, so that interpolation
+    /// at the maximum delay can still read its neighbors
+    const INTERPOLATION_PADDING: usize = 3;
+
+    /// Creates a silent delay line that can delay by up to `max_delay` samples
+    pub fn new(max_delay: usize) -> Self {
+        Self {
+            buffer: vec![0.0; max_delay + Self::INTERPOLATION_PADDING],
+            write_index: 0,
+            max_delay,
+        }
+    }
+
+    /// The longest delay (in samples) that can be read
+    pub fn max_delay(&self) -> usize {
+        self.max_delay
+    }
+
+    /// Adds a new sample to the delay line (replacing the oldest sample)
+    #[inline]
+    pub fn write(&mut self, sample: f32) {
+        self.buffer[self.write_index] = sample;
+        self.write_index = (self.write_index + 1) % self.buffer.len();
+    }
+
+    /// Reads the sample from `delay` samples ago, where a `delay` of 1.0 is the
+    /// most recently written sample. To delay a signal by `delay` samples,
+    /// read from the delay line *before* writing the current sample.
+    ///
+    /// `delay` is clamped between 1.0 and `max_delay`.
+    #[inline]
+    pub fn read(&self, delay: f32, interpolation: Interpolation) -> f32 {
+        let delay = delay.clamp(1.0, self.max_delay.max(1) as f32);
+        let whole_delay = delay.ceil();
+        // distance from the older sample towards the newer sample
+        let fraction = whole_delay - delay;
+        let older = whole_delay as usize;
+
+        match interpolation {
+            Interpolation::Nearest => self.sample(delay.round() as usize),
+            Interpolation::Linear => {
+                linear_interpolate(self.sample(older), self.sample(older - 1), fraction)
+            }
+            Interpolation::Cubic => cubic_interpolate(
+                self.sample(older + 1),
+                self.sample(older),
+                self.sample(older - 1),
+                // the sample after the most recent sample has not been written yet
+                self.sample(older.saturating_sub(2).max(1)),
+                fraction,
+            ),
+        }
+    }
+
+    /// Reads the sample from exactly `delay` samples ago (1 is the most recently written sample)
+    #[inline]
+    pub fn sample(&self, delay: usize) -> f32 {
+        let len = self.buffer.len();
+        self.buffer[(self.write_index + len - delay.min(len)) % len]
+    }
+
+    /// Fills the delay line with silence
+    pub fn clear(&mut self) {
+        self.buffer.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod test_delay_line {
+    use crate::{DelayLine, Interpolation};
+
+    #[test]
+    fn it_should_delay_by_whole_samples() {
+        let mut delay_line = DelayLine::new(4);
+        let output: Vec<f32> = (1..=6)
+            .map(|i| {
+                let delayed = delay_line.read(3.0, Interpolation::Linear);
+                delay_line.write(i as f32);
+                delayed
+            })
+            .collect();
+
+        assert_eq!(output, vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn it_should_interpolate_fractional_delays() {
+        let mut delay_line = DelayLine::new(8);
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0].iter().for_each(|sample| {
+            delay_line.write(*sample);
+        });
+
+        assert_eq!(delay_line.read(1.0, Interpolation::Linear), 6.0);
+        assert_eq!(delay_line.read(2.5, Interpolation::Linear), 4.5);
+        assert_eq!(delay_line.read(2.5, Interpolation::Cubic), 4.5);
+        assert_eq!(delay_line.read(2.4, Interpolation::Nearest), 5.0);
+    }
+
+    #[test]
+    fn it_should_clamp_delay_to_max_delay() {
+        let mut delay_line = DelayLine::new(2);
+        [1.0, 2.0, 3.0].iter().for_each(|sample| {
+            delay_line.write(*sample);
+        });
+
+        assert_eq!(delay_line.read(10.0, Interpolation::Linear), 2.0);
+        assert_eq!(delay_line.read(0.0, Interpolation::Linear), 3.0);
+    }
+}
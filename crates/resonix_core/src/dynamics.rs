@@ -0,0 +1,6 @@
+mod compressor;
+mod gain_reduction_hold;
+mod limiter;
+
+pub use compressor::*;
+pub use limiter::*;
@@ -0,0 +1,334 @@
+use std::time::Duration;
+
+use crate::{
+    dynamics::gain_reduction_hold::GainReductionHold, Decibel, DelayLine, Interpolation,
+    NumChannels, SampleRate,
+};
+
+/// Reduces the dynamic range of a multichannel signal by turning down
+/// the signal whenever its level rises above `threshold`.
+///
+/// The level is detected from the loudest channel, and the same gain is applied to every
+/// channel (i.e. the channels are "linked"), which keeps the stereo image stable.
+/// The level can also be detected from a separate sidechain signal (e.g. for ducking).
+///
+/// With lookahead, the signal is delayed so that gain reduction can begin *before* a peak
+/// arrives. The largest gain reduction within the lookahead window is held until its peak
+/// has passed, so with an instant attack and an infinite ratio, no peak exceeds the threshold.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Compressor {
+    sample_rate: SampleRate,
+    threshold: f32,
+    ratio: f32,
+    knee: f32,
+    attack: Duration,
+    release: Duration,
+    makeup_gain: f32,
+    lookahead: Duration,
+    attack_coefficient: f32,
+    release_coefficient: f32,
+    /// Current (smoothed) gain reduction in dB (always <= 0.0)
+    gain_reduction: f32,
+    gain_reduction_hold: GainReductionHold,
+    /// One delay line for every channel
+    lookahead_lines: Vec<DelayLine>,
+}
+
+impl Compressor {
+    pub const DEFAULT_THRESHOLD: f32 = -20.0;
+    pub const DEFAULT_RATIO: f32 = 4.0;
+    pub const DEFAULT_KNEE: f32 = 6.0;
+    pub const DEFAULT_ATTACK: Duration = Duration::from_millis(10);
+    pub const DEFAULT_RELEASE: Duration = Duration::from_millis(100);
+    pub const DEFAULT_MAKEUP_GAIN: f32 = 0.0;
+    pub const DEFAULT_LOOKAHEAD: Duration = Duration::ZERO;
+
+    /// Memory for this much lookahead is allocated up front
+    pub const MAX_LOOKAHEAD: Duration = Duration::from_millis(50);
+
+    /// Lookahead memory is allocated for `MAX_LOOKAHEAD` at this sample rate.
+    /// At higher sample rates, the longest possible lookahead is shortened proportionally.
+    pub const MAX_SAMPLE_RATE: u32 = 96000;
+
+    pub fn new(num_channels: impl Into<NumChannels>, sample_rate: impl Into<SampleRate>) -> Self {
+        let max_lookahead =
+            (Self::MAX_LOOKAHEAD.as_secs_f64() * Self::MAX_SAMPLE_RATE as f64).ceil() as usize;
+        let mut compressor = Self {
+            sample_rate: sample_rate.into(),
+            threshold: Self::DEFAULT_THRESHOLD,
+            ratio: Self::DEFAULT_RATIO,
+            knee: Self::DEFAULT_KNEE,
+            attack: Self::DEFAULT_ATTACK,
+            release: Self::DEFAULT_RELEASE,
+            makeup_gain: Self::DEFAULT_MAKEUP_GAIN,
+            lookahead: Self::DEFAULT_LOOKAHEAD,
+            attack_coefficient: 0.0,
+            release_coefficient: 0.0,
+            gain_reduction: 0.0,
+            gain_reduction_hold: GainReductionHold::new(max_lookahead + 1),
+            lookahead_lines: vec![DelayLine::new(max_lookahead); *num_channels.into()],
+        };
+        compressor.update_timing();
+        compressor
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.lookahead_lines.len())
+    }
+
+    /// Level (in dBFS) above which the signal is compressed
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) -> &mut Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// How many dB the input must rise above the threshold for the output to rise by 1 dB
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Clamped to at least 1.0 (no compression). Use `f32::INFINITY` for limiting.
+    pub fn set_ratio(&mut self, ratio: f32) -> &mut Self {
+        self.ratio = ratio.max(1.0);
+        self
+    }
+
+    /// Width (in dB) of the region around the threshold where compression is gradually applied
+    pub fn knee(&self) -> f32 {
+        self.knee
+    }
+
+    pub fn set_knee(&mut self, knee: f32) -> &mut Self {
+        self.knee = knee.max(0.0);
+        self
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.attack
+    }
+
+    /// How quickly gain reduction is applied
+    pub fn set_attack(&mut self, attack: Duration) -> &mut Self {
+        self.attack = attack;
+        self.update_timing();
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.release
+    }
+
+    /// How quickly gain reduction is let go
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.release = release;
+        self.update_timing();
+        self
+    }
+
+    /// Gain (in dB) applied after compression
+    pub fn makeup_gain(&self) -> f32 {
+        self.makeup_gain
+    }
+
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) -> &mut Self {
+        self.makeup_gain = makeup_gain;
+        self
+    }
+
+    pub fn lookahead(&self) -> Duration {
+        self.lookahead
+    }
+
+    /// Delays the signal (clamped to `MAX_LOOKAHEAD`), so that gain reduction can begin before peaks arrive
+    pub fn set_lookahead(&mut self, lookahead: Duration) -> &mut Self {
+        self.lookahead = lookahead.min(Self::MAX_LOOKAHEAD);
+        self.update_timing();
+        self
+    }
+
+    /// The current gain reduction in dB (0.0 when the signal is not being compressed)
+    pub fn gain_reduction(&self) -> f32 {
+        self.gain_reduction
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self.update_timing();
+        self
+    }
+
+    /// Resets the gain reduction and silences the lookahead delay
+    pub fn clear(&mut self) -> &mut Self {
+        self.gain_reduction = 0.0;
+        self.gain_reduction_hold.clear();
+        self.lookahead_lines.iter_mut().for_each(DelayLine::clear);
+        self
+    }
+
+    /// The gain reduction (in dB) that the compressor aims for at a given input level (in dB),
+    /// before attack and release smoothing are applied
+    pub fn static_gain_reduction(&self, level: f32) -> f32 {
+        let overshoot = level - self.threshold;
+        let slope = 1.0 / self.ratio - 1.0;
+
+        if 2.0 * overshoot <= -self.knee {
+            0.0
+        } else if 2.0 * overshoot.abs() < self.knee {
+            // quadratic interpolation between no compression and full compression
+            let knee_position = overshoot + self.knee / 2.0;
+            slope * knee_position * knee_position / (2.0 * self.knee)
+        } else {
+            slope * overshoot
+        }
+    }
+
+    /// Compresses one frame of audio in place (with one sample per channel).
+    ///
+    /// If `sidechain` is provided, its level is used to decide how much to compress `frame`.
+    pub fn process_frame(&mut self, frame: &mut [f32], sidechain: Option<&[f32]>) {
+        let peak = |samples: &[f32]| samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        let level = Decibel::calculate_full_scale(peak(sidechain.unwrap_or(frame)));
+
+        let target = self
+            .gain_reduction_hold
+            .next(self.static_gain_reduction(level));
+        let coefficient = if target < self.gain_reduction {
+            self.attack_coefficient
+        } else {
+            self.release_coefficient
+        };
+        self.gain_reduction = target + coefficient * (self.gain_reduction - target);
+
+        let gain = Decibel::calculate_amplitude_full_scale(self.gain_reduction + self.makeup_gain);
+        let lookahead = self.lookahead_samples();
+
+        frame
+            .iter_mut()
+            .zip(self.lookahead_lines.iter_mut())
+            .for_each(|(sample, lookahead_line)| {
+                let delayed = if lookahead == 0 {
+                    *sample
+                } else {
+                    lookahead_line.read(lookahead as f32, Interpolation::Nearest)
+                };
+                lookahead_line.write(*sample);
+                *sample = delayed * gain;
+            });
+    }
+
+    fn lookahead_samples(&self) -> usize {
+        (self.lookahead.as_secs_f32() * self.sample_rate.get() as f32).round() as usize
+    }
+
+    /// Recalculates everything that depends on the sample rate
+    fn update_timing(&mut self) {
+        let coefficient = |time: Duration| {
+            let samples = time.as_secs_f32() * self.sample_rate.get() as f32;
+            if samples > 0.0 {
+                (-1.0 / samples).exp()
+            } else {
+                0.0
+            }
+        };
+        self.attack_coefficient = coefficient(self.attack);
+        self.release_coefficient = coefficient(self.release);
+        self.gain_reduction_hold
+            .set_window_len(self.lookahead_samples() + 1);
+    }
+}
+
+#[cfg(test)]
+mod test_compressor {
+    use std::time::Duration;
+
+    use crate::{Compressor, Decibel};
+
+    #[test]
+    fn it_should_calculate_static_gain_reduction() {
+        let mut compressor = Compressor::new(1, 44100);
+        compressor.set_threshold(-20.0).set_ratio(4.0).set_knee(0.0);
+
+        assert_eq!(compressor.static_gain_reduction(-30.0), 0.0);
+        assert_eq!(compressor.static_gain_reduction(-10.0), -7.5);
+
+        // in the middle of the knee, the gain reduction is partially applied
+        compressor.set_knee(10.0);
+        assert_eq!(compressor.static_gain_reduction(-25.0), 0.0);
+        assert_eq!(compressor.static_gain_reduction(-20.0), -0.9375);
+        assert_eq!(compressor.static_gain_reduction(-10.0), -7.5);
+    }
+
+    #[test]
+    fn it_should_pass_quiet_signals_unchanged() {
+        let mut compressor = Compressor::new(2, 44100);
+        compressor.set_knee(0.0);
+
+        let mut frame = [0.05, -0.05];
+        compressor.process_frame(&mut frame, None);
+        assert_eq!(frame, [0.05, -0.05]);
+        assert_eq!(compressor.gain_reduction(), 0.0);
+    }
+
+    #[test]
+    fn it_should_settle_on_static_gain_reduction() {
+        let mut compressor = Compressor::new(1, 1000);
+        compressor.set_knee(0.0);
+
+        let mut frame = [0.0];
+        (0..1000).for_each(|_| {
+            frame[0] = 1.0;
+            compressor.process_frame(&mut frame, None);
+        });
+
+        assert!((compressor.gain_reduction() - -15.0).abs() < 0.001);
+        assert!((Decibel::calculate_full_scale(frame[0]) - -15.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn it_should_duck_signal_from_sidechain() {
+        let mut compressor = Compressor::new(1, 1000);
+        compressor
+            .set_knee(0.0)
+            .set_attack(Duration::ZERO)
+            .set_ratio(f32::INFINITY);
+
+        let mut frame = [0.01];
+        compressor.process_frame(&mut frame, Some(&[1.0]));
+
+        // the sidechain is 20 dB over the threshold
+        assert!((frame[0] - 0.001).abs() < 0.000001);
+    }
+
+    #[test]
+    fn it_should_reduce_gain_before_peaks_with_lookahead() {
+        let mut compressor = Compressor::new(1, 1000);
+        compressor
+            .set_knee(0.0)
+            .set_attack(Duration::ZERO)
+            .set_ratio(f32::INFINITY)
+            .set_lookahead(Duration::from_millis(3));
+
+        let output: Vec<f32> = [0.01, 1.0, 0.01, 0.01, 0.01, 0.01]
+            .into_iter()
+            .map(|sample| {
+                let mut frame = [sample];
+                compressor.process_frame(&mut frame, None);
+                frame[0]
+            })
+            .collect();
+
+        // the signal is delayed by 3 samples, and gain reduction begins before the
+        // peak arrives, so that the peak is limited to the threshold
+        assert_eq!(output[..3], [0.0, 0.0, 0.0]);
+        assert!((output[3] - 0.001).abs() < 0.000001);
+        assert!((output[4] - 0.1).abs() < 0.000001);
+    }
+}
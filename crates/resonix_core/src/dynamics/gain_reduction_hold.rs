@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+
+/// Keeps track of the largest gain reduction (i.e. the minimum value, in dB)
+/// over the most recent `window_len` samples, so that the gain reduction needed
+/// for a peak can be held until that peak has made it through the lookahead delay.
+///
+/// Uses a monotonic queue, so each new value takes amortized constant time.
+/// All memory is allocated up front.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct GainReductionHold {
+    /// (sample index, gain reduction) pairs, with strictly increasing gain reductions
+    queue: VecDeque<(u64, f32)>,
+    window_len: u64,
+    max_window_len: u64,
+    sample_index: u64,
+}
+
+impl GainReductionHold {
+    pub(crate) fn new(max_window_len: usize) -> Self {
+        Self {
+            queue: VecDeque::with_capacity(max_window_len + 1),
+            window_len: 1,
+            max_window_len: max_window_len.max(1) as u64,
+            sample_index: 0,
+        }
+    }
+
+    /// `window_len` is clamped to the `max_window_len` the hold was created with.
+    ///
+    /// The gain reduction currently being held is kept, even if the window shrinks.
+    pub(crate) fn set_window_len(&mut self, window_len: usize) {
+        let window_len = (window_len as u64).clamp(1, self.max_window_len);
+        if window_len < self.window_len {
+            // hold the current gain reduction over the whole of the new window
+            // (everything else in the queue is smaller and would expire first anyway)
+            if let Some((_, held)) = self.queue.front().copied() {
+                self.queue.clear();
+                self.queue.push_back((self.sample_index - 1, held));
+            }
+        }
+        self.window_len = window_len;
+    }
+
+    /// Adds the gain reduction for the newest sample and returns
+    /// the largest gain reduction within the window
+    #[inline]
+    pub(crate) fn next(&mut self, gain_reduction: f32) -> f32 {
+        while self
+            .queue
+            .back()
+            .is_some_and(|(_, queued)| *queued >= gain_reduction)
+        {
+            self.queue.pop_back();
+        }
+        self.queue.push_back((self.sample_index, gain_reduction));
+
+        while self
+            .queue
+            .front()
+            .is_some_and(|(index, _)| index + self.window_len <= self.sample_index)
+        {
+            self.queue.pop_front();
+        }
+
+        self.sample_index += 1;
+
+        self.queue
+            .front()
+            .map(|(_, queued)| *queued)
+            .unwrap_or(gain_reduction)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.queue.clear();
+        self.sample_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod test_gain_reduction_hold {
+    use super::GainReductionHold;
+
+    #[test]
+    fn it_should_hold_minimum_over_window() {
+        let mut hold = GainReductionHold::new(8);
+        hold.set_window_len(3);
+
+        let output: Vec<f32> = [0.0, -6.0, 0.0, 0.0, 0.0, -2.0, -1.0, 0.0, 0.0]
+            .into_iter()
+            .map(|gain_reduction| hold.next(gain_reduction))
+            .collect();
+
+        assert_eq!(
+            output,
+            vec![0.0, -6.0, -6.0, -6.0, 0.0, -2.0, -2.0, -2.0, -1.0]
+        );
+    }
+
+    #[test]
+    fn it_should_keep_held_gain_reduction_when_window_changes() {
+        let mut hold = GainReductionHold::new(8);
+        hold.set_window_len(6);
+
+        [0.0, -6.0, 0.0, -3.0, 0.0]
+            .into_iter()
+            .for_each(|gain_reduction| {
+                hold.next(gain_reduction);
+            });
+
+        // setting the same window doesn't lose anything
+        hold.set_window_len(6);
+        assert_eq!(hold.next(0.0), -6.0);
+
+        // shrinking the window still holds the largest gain reduction for the new window
+        hold.set_window_len(3);
+        let output: Vec<f32> = [0.0, 0.0, 0.0]
+            .into_iter()
+            .map(|gain_reduction| hold.next(gain_reduction))
+            .collect();
+        assert_eq!(output, vec![-6.0, -6.0, 0.0]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use crate::{Compressor, NumChannels, SampleRate};
+
+/// A brickwall limiter, which keeps every peak of a multichannel signal at or below `threshold`
+/// when no sidechain is used (with a sidechain, gain reduction follows the sidechain's peaks instead).
+///
+/// Gain reduction is applied instantly, and lookahead lets it begin shortly before each
+/// peak arrives, so that peaks are turned down rather than clipped.
+/// Useful right before the `DACNode` to protect speakers (and ears) from sudden loud signals.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Limiter {
+    compressor: Compressor,
+}
+
+impl Limiter {
+    pub const DEFAULT_THRESHOLD: f32 = -1.0;
+    pub const DEFAULT_RELEASE: Duration = Duration::from_millis(50);
+    pub const DEFAULT_LOOKAHEAD: Duration = Duration::from_millis(5);
+
+    pub fn new(num_channels: impl Into<NumChannels>, sample_rate: impl Into<SampleRate>) -> Self {
+        let mut compressor = Compressor::new(num_channels, sample_rate);
+        compressor
+            .set_threshold(Self::DEFAULT_THRESHOLD)
+            .set_ratio(f32::INFINITY)
+            .set_knee(0.0)
+            .set_attack(Duration::ZERO)
+            .set_release(Self::DEFAULT_RELEASE)
+            .set_lookahead(Self::DEFAULT_LOOKAHEAD);
+        Self { compressor }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        self.compressor.num_channels()
+    }
+
+    /// Highest level (in dBFS) that the output can reach
+    pub fn threshold(&self) -> f32 {
+        self.compressor.threshold()
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) -> &mut Self {
+        self.compressor.set_threshold(threshold);
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.compressor.release()
+    }
+
+    /// How quickly gain reduction is let go
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.compressor.set_release(release);
+        self
+    }
+
+    pub fn lookahead(&self) -> Duration {
+        self.compressor.lookahead()
+    }
+
+    /// Delays the signal (clamped to `Compressor::MAX_LOOKAHEAD`), so that gain reduction can begin before peaks arrive
+    pub fn set_lookahead(&mut self, lookahead: Duration) -> &mut Self {
+        self.compressor.set_lookahead(lookahead);
+        self
+    }
+
+    /// The current gain reduction in dB (0.0 when the signal is not being limited)
+    pub fn gain_reduction(&self) -> f32 {
+        self.compressor.gain_reduction()
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.compressor.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.compressor.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Resets the gain reduction and silences the lookahead delay
+    pub fn clear(&mut self) -> &mut Self {
+        self.compressor.clear();
+        self
+    }
+
+    /// Limits one frame of audio in place (with one sample per channel)
+    ///
+    /// If `sidechain` is provided, its level is used to decide how much to limit `frame`.
+    pub fn process_frame(&mut self, frame: &mut [f32], sidechain: Option<&[f32]>) {
+        self.compressor.process_frame(frame, sidechain);
+    }
+}
+
+#[cfg(test)]
+mod test_limiter {
+    use crate::{Decibel, Limiter};
+
+    #[test]
+    fn it_should_never_exceed_threshold() {
+        let mut limiter = Limiter::new(2, 44100);
+        let ceiling = Decibel::calculate_amplitude_full_scale(limiter.threshold());
+
+        // a loud, wildly varying signal
+        (0..44100).for_each(|i| {
+            let t = i as f32 / 44100.0;
+            let loudness = 1.0 + 10.0 * (t * 3.0).sin().abs();
+            let mut frame = [
+                loudness * (t * 440.0 * std::f32::consts::TAU).sin(),
+                loudness * (t * 97.0 * std::f32::consts::TAU).cos(),
+            ];
+            limiter.process_frame(&mut frame, None);
+            frame
+                .iter()
+                .for_each(|sample| assert!(sample.abs() <= ceiling + 0.00001));
+        });
+    }
+
+    #[test]
+    fn it_should_limit_signal_from_sidechain() {
+        let mut limiter = Limiter::new(1, 1000);
+        limiter
+            .set_threshold(-20.0)
+            .set_lookahead(std::time::Duration::ZERO);
+
+        let mut frame = [0.05];
+        // the sidechain is 20 dB over the threshold
+        limiter.process_frame(&mut frame, Some(&[1.0]));
+
+        assert!((frame[0] - 0.005).abs() < 0.00001);
+    }
+
+    #[test]
+    fn it_should_keep_limiting_when_settings_change() {
+        let mut limiter = Limiter::new(1, 1000);
+        limiter.set_lookahead(std::time::Duration::from_millis(4));
+        let ceiling = Decibel::calculate_amplitude_full_scale(limiter.threshold());
+
+        let output: Vec<f32> = [0.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                if i == 2 {
+                    // the peak is still in the lookahead delay
+                    limiter.set_release(std::time::Duration::from_millis(10));
+                }
+                let mut frame = [sample];
+                limiter.process_frame(&mut frame, None);
+                frame[0]
+            })
+            .collect();
+
+        assert!(output
+            .iter()
+            .all(|sample| sample.abs() <= ceiling + 0.00001));
+    }
+}
@@ -1,5 +1,15 @@
+mod breakpoint_envelope;
 mod envelope;
+mod envelope_curve;
+mod envelope_follower;
+mod envelope_generator;
+mod envelope_segment;
 mod sine_envelope;
 
+pub use breakpoint_envelope::*;
 pub use envelope::*;
+pub use envelope_curve::*;
+pub use envelope_follower::*;
+pub use envelope_generator::*;
+pub use envelope_segment::*;
 pub use sine_envelope::*;
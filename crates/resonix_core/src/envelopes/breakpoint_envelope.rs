@@ -0,0 +1,132 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{EnvelopeCurve, EnvelopeSegment};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BreakpointEnvelopeError {
+    #[error("A breakpoint envelope must contain at least one segment")]
+    NoSegments,
+    #[error("Sustain index {sustain_index} is out of range for an envelope with {num_segments} segments")]
+    InvalidSustainIndex {
+        sustain_index: usize,
+        num_segments: usize,
+    },
+}
+
+/// The shape of an envelope, as a series of segments that each move to a new level.
+///
+/// If the envelope has a sustain point, then the level is held at the end of
+/// the segment at `sustain_index` for as long as the gate is on, and the
+/// remaining segments are played once the gate turns off (the release).
+/// Without a sustain point, every segment is played through in order.
+///
+/// Segments are shared between clones, so cloning an envelope never allocates.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct BreakpointEnvelope {
+    segments: Arc<[EnvelopeSegment]>,
+    sustain_index: Option<usize>,
+}
+
+impl BreakpointEnvelope {
+    /// Curvature used for the decay and release of `BreakpointEnvelope::adsr`
+    pub const ADSR_CURVATURE: f32 = 5.0;
+
+    pub fn new(
+        segments: Vec<EnvelopeSegment>,
+        sustain_index: Option<usize>,
+    ) -> Result<Self, BreakpointEnvelopeError> {
+        if segments.is_empty() {
+            return Err(BreakpointEnvelopeError::NoSegments);
+        }
+
+        if let Some(sustain_index) = sustain_index {
+            if sustain_index >= segments.len() {
+                return Err(BreakpointEnvelopeError::InvalidSustainIndex {
+                    sustain_index,
+                    num_segments: segments.len(),
+                });
+            }
+        }
+
+        Ok(Self {
+            segments: segments.into(),
+            sustain_index,
+        })
+    }
+
+    /// A classic attack-decay-sustain-release envelope, which rises linearly to 1.0,
+    /// falls to the `sustain` level, and then falls to 0.0 once the gate turns off.
+    ///
+    /// Decay and release follow an exponential curve, which sounds more natural.
+    pub fn adsr(attack: Duration, decay: Duration, sustain: f32, release: Duration) -> Self {
+        Self {
+            segments: Arc::new([
+                EnvelopeSegment::new(1.0, attack, EnvelopeCurve::Linear),
+                EnvelopeSegment::new(
+                    sustain,
+                    decay,
+                    EnvelopeCurve::Exponential(Self::ADSR_CURVATURE),
+                ),
+                EnvelopeSegment::new(
+                    0.0,
+                    release,
+                    EnvelopeCurve::Exponential(Self::ADSR_CURVATURE),
+                ),
+            ]),
+            sustain_index: Some(1),
+        }
+    }
+
+    pub fn segments(&self) -> &[EnvelopeSegment] {
+        &self.segments
+    }
+
+    pub fn sustain_index(&self) -> Option<usize> {
+        self.sustain_index
+    }
+}
+
+impl Default for BreakpointEnvelope {
+    fn default() -> Self {
+        Self::adsr(
+            Duration::from_millis(10),
+            Duration::from_millis(100),
+            0.7,
+            Duration::from_millis(300),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_breakpoint_envelope {
+    use std::time::Duration;
+
+    use crate::{BreakpointEnvelope, BreakpointEnvelopeError, EnvelopeCurve, EnvelopeSegment};
+
+    #[test]
+    fn it_should_validate_segments() {
+        assert_eq!(
+            BreakpointEnvelope::new(Vec::new(), None).unwrap_err(),
+            BreakpointEnvelopeError::NoSegments
+        );
+
+        let segment = EnvelopeSegment::new(1.0, Duration::ZERO, EnvelopeCurve::Linear);
+        assert_eq!(
+            BreakpointEnvelope::new(vec![segment], Some(1)).unwrap_err(),
+            BreakpointEnvelopeError::InvalidSustainIndex {
+                sustain_index: 1,
+                num_segments: 1
+            }
+        );
+        assert!(BreakpointEnvelope::new(vec![segment], Some(0)).is_ok());
+    }
+
+    #[test]
+    fn it_should_share_segments_between_clones() {
+        let envelope = BreakpointEnvelope::default();
+        let clone = envelope.clone();
+
+        assert_eq!(clone, envelope);
+        assert!(std::ptr::eq(clone.segments(), envelope.segments()));
+    }
+}
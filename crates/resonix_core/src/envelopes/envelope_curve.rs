@@ -0,0 +1,61 @@
+/// The shape of an `EnvelopeSegment` as it moves from its start level to its target level
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+pub enum EnvelopeCurve {
+    /// Moves towards the target level at a constant rate
+    #[default]
+    Linear,
+    /// Moves towards the target level along an exponential curve.
+    ///
+    /// Positive curvature moves quickly at first and then slows down as it
+    /// approaches the target (like a decaying note), while negative curvature
+    /// starts slowly and speeds up. A curvature of 0.0 is the same as `Linear`.
+    Exponential(f32),
+}
+
+impl EnvelopeCurve {
+    /// Converts `progress` through a segment (from 0.0 to 1.0) into
+    /// progress from the segment's start level to its target level
+    pub fn apply(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+
+        match *self {
+            EnvelopeCurve::Linear => progress,
+            EnvelopeCurve::Exponential(curvature) => {
+                if curvature.abs() < f32::EPSILON {
+                    return progress;
+                }
+
+                (1.0 - (-curvature * progress).exp()) / (1.0 - (-curvature).exp())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_envelope_curve {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::EnvelopeCurve;
+
+    #[test]
+    fn it_should_start_and_end_at_the_same_levels() {
+        [
+            EnvelopeCurve::Linear,
+            EnvelopeCurve::Exponential(5.0),
+            EnvelopeCurve::Exponential(-5.0),
+            EnvelopeCurve::Exponential(0.0),
+        ]
+        .iter()
+        .for_each(|curve| {
+            assert_difference_is_within_tolerance(curve.apply(0.0), 0.0, 0.0001);
+            assert_difference_is_within_tolerance(curve.apply(1.0), 1.0, 0.0001);
+        });
+    }
+
+    #[test]
+    fn it_should_bend_according_to_curvature() {
+        assert_eq!(EnvelopeCurve::Linear.apply(0.5), 0.5);
+        assert!(EnvelopeCurve::Exponential(5.0).apply(0.5) > 0.5);
+        assert!(EnvelopeCurve::Exponential(-5.0).apply(0.5) < 0.5);
+    }
+}
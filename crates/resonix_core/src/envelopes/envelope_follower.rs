@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use crate::SampleRate;
+
+/// Follows the level of an incoming (positive) signal, rising and falling
+/// at the rates determined by its `attack` and `release` times.
+///
+/// These "ballistics" are what make a meter rise quickly and fall back slowly,
+/// and they are also used to smooth gain changes in dynamics processors.
+///
+/// `attack` and `release` are time constants: after one `attack` time, the
+/// follower will have covered ~63% of the distance to a new, louder level.
+/// A time of `Duration::ZERO` makes the follower jump to the new level immediately.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct EnvelopeFollower {
+    sample_rate: SampleRate,
+    attack: Duration,
+    release: Duration,
+    attack_coefficient: f32,
+    release_coefficient: f32,
+    value: f32,
+}
+
+impl EnvelopeFollower {
+    pub fn new(sample_rate: impl Into<SampleRate>, attack: Duration, release: Duration) -> Self {
+        let sample_rate = sample_rate.into();
+        Self {
+            sample_rate,
+            attack,
+            release,
+            attack_coefficient: Self::calculate_coefficient(attack, sample_rate),
+            release_coefficient: Self::calculate_coefficient(release, sample_rate),
+            value: 0.0,
+        }
+    }
+
+    /// Moves the follower's level towards the `input` level and returns the new level
+    pub fn next_sample(&mut self, input: f32) -> f32 {
+        let coefficient = if input > self.value {
+            self.attack_coefficient
+        } else {
+            self.release_coefficient
+        };
+
+        self.value = input + coefficient * (self.value - input);
+
+        self.value
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn reset(&mut self) -> &mut Self {
+        self.value = 0.0;
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self.attack_coefficient = Self::calculate_coefficient(self.attack, self.sample_rate);
+        self.release_coefficient = Self::calculate_coefficient(self.release, self.sample_rate);
+        self
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.attack
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) -> &mut Self {
+        self.attack = attack;
+        self.attack_coefficient = Self::calculate_coefficient(attack, self.sample_rate);
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.release
+    }
+
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.release = release;
+        self.release_coefficient = Self::calculate_coefficient(release, self.sample_rate);
+        self
+    }
+
+    /// Calculates the one-pole filter coefficient for a given time constant
+    pub fn calculate_coefficient(time: Duration, sample_rate: SampleRate) -> f32 {
+        let time_in_samples = time.as_secs_f32() * sample_rate.get() as f32;
+
+        if time_in_samples <= 0.0 {
+            return 0.0;
+        }
+
+        (-1.0 / time_in_samples).exp()
+    }
+}
+
+#[cfg(test)]
+mod test_envelope_follower {
+    use std::time::Duration;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::EnvelopeFollower;
+
+    #[test]
+    fn it_should_jump_to_new_level_when_attack_is_zero() {
+        let mut follower = EnvelopeFollower::new(44100, Duration::ZERO, Duration::from_secs(1));
+
+        assert_eq!(follower.next_sample(0.8), 0.8);
+    }
+
+    #[test]
+    fn it_should_release_by_time_constant() {
+        const SAMPLE_RATE: u32 = 1000;
+        let mut follower =
+            EnvelopeFollower::new(SAMPLE_RATE, Duration::ZERO, Duration::from_millis(100));
+
+        follower.next_sample(1.0);
+
+        // after one release time, level should have fallen to ~1/e of the original level
+        for _ in 0..100 {
+            follower.next_sample(0.0);
+        }
+
+        assert_difference_is_within_tolerance(follower.value(), (-1.0f32).exp(), 0.001);
+    }
+
+    #[test]
+    fn it_should_attack_gradually() {
+        let mut follower =
+            EnvelopeFollower::new(1000, Duration::from_millis(10), Duration::from_millis(10));
+
+        let first = follower.next_sample(1.0);
+        let second = follower.next_sample(1.0);
+
+        assert!(first > 0.0 && first < 1.0);
+        assert!(second > first && second < 1.0);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{BreakpointEnvelope, EnvelopeSegment, SampleRate};
+
+/// Determines what happens when the gate turns on while it is already on
+/// (for example, when a new note starts before the previous note has ended)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum EnvelopeTriggerMode {
+    /// Restarts the envelope from its first segment
+    #[default]
+    Retrigger,
+    /// Keeps the envelope going without restarting it
+    Legato,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum EnvelopeStage {
+    /// Not playing: holds the level reached at the end of the last segment
+    Idle,
+    /// Moving through the segment at the given index
+    Segment(usize),
+    /// Holding the level at the end of the segment at the given index until the gate turns off
+    Sustain(usize),
+}
+
+/// Plays a `BreakpointEnvelope` in real time, in response to a gate turning on and off.
+///
+/// Every segment starts from whatever level the envelope is currently at,
+/// so retriggering or releasing the envelope early never causes a jump in level.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct EnvelopeGenerator {
+    sample_rate: SampleRate,
+    envelope: BreakpointEnvelope,
+    trigger_mode: EnvelopeTriggerMode,
+    is_gate_on: bool,
+    stage: EnvelopeStage,
+    value: f32,
+    /// The segment currently being played (kept separately so that
+    /// changing the envelope never affects a segment that is in progress)
+    segment: EnvelopeSegment,
+    segment_start_value: f32,
+    /// Number of samples that have been played in the current segment
+    segment_position: u32,
+    segment_len: u32,
+}
+
+impl EnvelopeGenerator {
+    pub fn new(sample_rate: impl Into<SampleRate>, envelope: BreakpointEnvelope) -> Self {
+        Self {
+            sample_rate: sample_rate.into(),
+            envelope,
+            trigger_mode: EnvelopeTriggerMode::default(),
+            is_gate_on: false,
+            stage: EnvelopeStage::Idle,
+            value: 0.0,
+            segment: EnvelopeSegment::default(),
+            segment_start_value: 0.0,
+            segment_position: 0,
+            segment_len: 0,
+        }
+    }
+
+    pub fn envelope(&self) -> &BreakpointEnvelope {
+        &self.envelope
+    }
+
+    /// Changes take effect once the current segment ends
+    pub fn set_envelope(&mut self, envelope: BreakpointEnvelope) -> &mut Self {
+        self.replace_envelope(envelope);
+        self
+    }
+
+    /// Like `set_envelope`, but returns the replaced envelope
+    pub fn replace_envelope(&mut self, envelope: BreakpointEnvelope) -> BreakpointEnvelope {
+        std::mem::replace(&mut self.envelope, envelope)
+    }
+
+    pub fn trigger_mode(&self) -> EnvelopeTriggerMode {
+        self.trigger_mode
+    }
+
+    pub fn set_trigger_mode(&mut self, trigger_mode: EnvelopeTriggerMode) -> &mut Self {
+        self.trigger_mode = trigger_mode;
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+
+        // keep the same relative progress through the current segment
+        if self.segment_len > 0 {
+            let progress = self.segment_position as f32 / self.segment_len as f32;
+            self.segment_len = self.duration_in_samples(&self.segment);
+            self.segment_position = (progress * self.segment_len as f32) as u32;
+        }
+
+        self
+    }
+
+    /// The current level of the envelope
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    pub fn is_gate_on(&self) -> bool {
+        self.is_gate_on
+    }
+
+    /// Whether the envelope is still moving or sustaining (as opposed to having finished)
+    pub fn is_active(&self) -> bool {
+        self.stage != EnvelopeStage::Idle
+    }
+
+    /// Index of the segment currently being played or sustained
+    pub fn current_segment(&self) -> Option<usize> {
+        match self.stage {
+            EnvelopeStage::Idle => None,
+            EnvelopeStage::Segment(index) | EnvelopeStage::Sustain(index) => Some(index),
+        }
+    }
+
+    /// Starts the envelope from its first segment and holds at the sustain point until `gate_off`
+    pub fn gate_on(&mut self) -> &mut Self {
+        if self.is_gate_on && self.trigger_mode == EnvelopeTriggerMode::Legato {
+            return self;
+        }
+
+        self.is_gate_on = true;
+        self.start_segment(0);
+        self
+    }
+
+    /// Moves on from the sustain point (or skips straight to the release,
+    /// if the sustain point has not yet been reached)
+    pub fn gate_off(&mut self) -> &mut Self {
+        if !self.is_gate_on {
+            return self;
+        }
+
+        self.is_gate_on = false;
+        match (self.stage, self.envelope.sustain_index()) {
+            (EnvelopeStage::Sustain(index), _) => self.start_segment(index + 1),
+            (EnvelopeStage::Segment(index), Some(sustain_index)) if index <= sustain_index => {
+                self.start_segment(sustain_index + 1)
+            }
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Plays every segment of the envelope once, without holding at the sustain point
+    pub fn trigger(&mut self) -> &mut Self {
+        self.is_gate_on = false;
+        self.start_segment(0);
+        self
+    }
+
+    /// Returns the envelope to its initial, silent state
+    pub fn reset(&mut self) -> &mut Self {
+        self.is_gate_on = false;
+        self.stage = EnvelopeStage::Idle;
+        self.value = 0.0;
+        self
+    }
+
+    /// Advances the envelope by one sample and returns its new level
+    pub fn next_sample(&mut self) -> f32 {
+        if let EnvelopeStage::Segment(index) = self.stage {
+            self.segment_position += 1;
+
+            if self.segment_position >= self.segment_len {
+                self.value = self.segment.target();
+                self.finish_segment(index);
+            } else {
+                let progress = self.segment_position as f32 / self.segment_len as f32;
+                self.value = self.segment_start_value
+                    + (self.segment.target() - self.segment_start_value)
+                        * self.segment.curve().apply(progress);
+            }
+        }
+
+        self.value
+    }
+
+    fn start_segment(&mut self, index: usize) {
+        match self.envelope.segments().get(index) {
+            Some(segment) => {
+                self.segment = *segment;
+                self.segment_start_value = self.value;
+                self.segment_position = 0;
+                self.segment_len = self.duration_in_samples(segment);
+                self.stage = EnvelopeStage::Segment(index);
+            }
+            None => {
+                self.stage = EnvelopeStage::Idle;
+            }
+        }
+    }
+
+    fn finish_segment(&mut self, index: usize) {
+        if self.is_gate_on && self.envelope.sustain_index() == Some(index) {
+            self.stage = EnvelopeStage::Sustain(index);
+        } else {
+            self.start_segment(index + 1);
+        }
+    }
+
+    fn duration_in_samples(&self, segment: &EnvelopeSegment) -> u32 {
+        (segment.duration().as_secs_f32() * self.sample_rate.get() as f32).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod test_envelope_generator {
+    use std::time::Duration;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{
+        BreakpointEnvelope, EnvelopeCurve, EnvelopeGenerator, EnvelopeSegment, EnvelopeTriggerMode,
+    };
+
+    const SAMPLE_RATE: u32 = 1000;
+
+    /// 4-sample attack, 2-sample decay to 0.5, and 2-sample release
+    fn linear_adsr() -> BreakpointEnvelope {
+        BreakpointEnvelope::new(
+            vec![
+                EnvelopeSegment::new(1.0, Duration::from_millis(4), EnvelopeCurve::Linear),
+                EnvelopeSegment::new(0.5, Duration::from_millis(2), EnvelopeCurve::Linear),
+                EnvelopeSegment::new(0.0, Duration::from_millis(2), EnvelopeCurve::Linear),
+            ],
+            Some(1),
+        )
+        .unwrap()
+    }
+
+    fn render(generator: &mut EnvelopeGenerator, num_samples: usize) -> Vec<f32> {
+        (0..num_samples).map(|_| generator.next_sample()).collect()
+    }
+
+    #[test]
+    fn it_should_sustain_until_gate_off() {
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, linear_adsr());
+        assert_eq!(render(&mut generator, 2), vec![0.0, 0.0]);
+
+        generator.gate_on();
+        assert_eq!(
+            render(&mut generator, 8),
+            vec![0.25, 0.5, 0.75, 1.0, 0.75, 0.5, 0.5, 0.5]
+        );
+        assert!(generator.is_active());
+
+        generator.gate_off();
+        assert_eq!(render(&mut generator, 3), vec![0.25, 0.0, 0.0]);
+        assert!(!generator.is_active());
+    }
+
+    #[test]
+    fn it_should_release_from_current_level_when_gate_turns_off_early() {
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, linear_adsr());
+
+        generator.gate_on();
+        render(&mut generator, 2);
+        generator.gate_off();
+
+        assert_eq!(render(&mut generator, 2), vec![0.25, 0.0]);
+    }
+
+    #[test]
+    fn it_should_play_through_every_segment_when_triggered() {
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, linear_adsr());
+
+        generator.trigger();
+
+        assert_eq!(
+            render(&mut generator, 9),
+            vec![0.25, 0.5, 0.75, 1.0, 0.75, 0.5, 0.25, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn it_should_retrigger_or_continue_in_legato() {
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, linear_adsr());
+        generator.gate_on();
+        render(&mut generator, 6);
+
+        // retriggering starts a new attack from the sustain level
+        generator.gate_on();
+        assert_eq!(render(&mut generator, 2), vec![0.625, 0.75]);
+
+        generator.set_trigger_mode(EnvelopeTriggerMode::Legato);
+        generator.gate_on();
+        assert_eq!(render(&mut generator, 2), vec![0.875, 1.0]);
+    }
+
+    #[test]
+    fn it_should_follow_segment_curve() {
+        let envelope = BreakpointEnvelope::new(
+            vec![EnvelopeSegment::new(
+                1.0,
+                Duration::from_millis(10),
+                EnvelopeCurve::Exponential(5.0),
+            )],
+            None,
+        )
+        .unwrap();
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, envelope);
+
+        generator.trigger();
+        let samples = render(&mut generator, 10);
+
+        assert_difference_is_within_tolerance(
+            samples[4],
+            EnvelopeCurve::Exponential(5.0).apply(0.5),
+            0.0001,
+        );
+        assert_eq!(samples[9], 1.0);
+    }
+
+    #[test]
+    fn it_should_jump_through_zero_length_segments() {
+        let envelope =
+            BreakpointEnvelope::adsr(Duration::ZERO, Duration::ZERO, 0.5, Duration::ZERO);
+        let mut generator = EnvelopeGenerator::new(SAMPLE_RATE, envelope);
+
+        generator.gate_on();
+        assert_eq!(render(&mut generator, 3), vec![1.0, 0.5, 0.5]);
+
+        generator.gate_off();
+        assert_eq!(render(&mut generator, 1), vec![0.0]);
+    }
+}
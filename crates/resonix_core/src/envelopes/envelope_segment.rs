@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use crate::EnvelopeCurve;
+
+/// One stage of a `BreakpointEnvelope`: a move from the current level to `target`
+/// over the course of `duration`, following the shape of `curve`
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct EnvelopeSegment {
+    target: f32,
+    duration: Duration,
+    curve: EnvelopeCurve,
+}
+
+impl EnvelopeSegment {
+    pub fn new(target: f32, duration: Duration, curve: EnvelopeCurve) -> Self {
+        Self {
+            target,
+            duration,
+            curve,
+        }
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn curve(&self) -> EnvelopeCurve {
+        self.curve
+    }
+}
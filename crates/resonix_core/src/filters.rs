@@ -0,0 +1,9 @@
+mod biquad_coefficients;
+mod biquad_filter;
+mod biquad_filter_type;
+mod frequency_response;
+
+pub use biquad_coefficients::*;
+pub use biquad_filter::*;
+pub use biquad_filter_type::*;
+pub use frequency_response::*;
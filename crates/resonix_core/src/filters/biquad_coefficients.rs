@@ -0,0 +1,256 @@
+use std::f64::consts::{PI, TAU};
+
+use crate::{BiquadFilterType, FrequencyResponse, SampleRate};
+
+/// The five coefficients of a second-order (biquad) filter,
+/// normalized so that `a0` is 1.0:
+///
+/// `y[n] = b0·x[n] + b1·x[n-1] + b2·x[n-2] - a1·y[n-1] - a2·y[n-2]`
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct BiquadCoefficients {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+impl BiquadCoefficients {
+    /// Lowest allowed Q, which keeps the filter from dividing by zero
+    pub const MIN_Q: f32 = 0.0001;
+
+    /// Calculates the coefficients for a filter, following the formulas
+    /// from Robert Bristow-Johnson's "Audio EQ Cookbook".
+    ///
+    /// `frequency` is clamped to just below the Nyquist frequency, and
+    /// `gain` (in dB) is ignored by filter types that do not use it.
+    /// At sample rates of 2 Hz or lower, the coefficients pass the signal through unaltered.
+    pub fn new(
+        filter_type: BiquadFilterType,
+        frequency: f32,
+        q: f32,
+        gain: f32,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let sample_rate = sample_rate.into().get() as f64;
+        let max_frequency = sample_rate / 2.0 * 0.999;
+        // there are no frequencies that can be filtered at very low sample rates
+        if max_frequency < 1.0 {
+            return Self::default();
+        }
+
+        let frequency = (frequency as f64).clamp(1.0, max_frequency);
+        let q = q.max(Self::MIN_Q) as f64;
+        let a = 10.0f64.powf(gain as f64 / 40.0);
+
+        let w0 = TAU * frequency / sample_rate;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * q);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let (b0, b1, b2, a0, a1, a2) = match filter_type {
+            BiquadFilterType::LowPass => (
+                (1.0 - cos_w0) / 2.0,
+                1.0 - cos_w0,
+                (1.0 - cos_w0) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            BiquadFilterType::HighPass => (
+                (1.0 + cos_w0) / 2.0,
+                -(1.0 + cos_w0),
+                (1.0 + cos_w0) / 2.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            BiquadFilterType::BandPass => {
+                (alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha)
+            }
+            BiquadFilterType::Notch => (
+                1.0,
+                -2.0 * cos_w0,
+                1.0,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            BiquadFilterType::AllPass => (
+                1.0 - alpha,
+                -2.0 * cos_w0,
+                1.0 + alpha,
+                1.0 + alpha,
+                -2.0 * cos_w0,
+                1.0 - alpha,
+            ),
+            BiquadFilterType::Peaking => (
+                1.0 + alpha * a,
+                -2.0 * cos_w0,
+                1.0 - alpha * a,
+                1.0 + alpha / a,
+                -2.0 * cos_w0,
+                1.0 - alpha / a,
+            ),
+            BiquadFilterType::LowShelf => (
+                a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+            ),
+            BiquadFilterType::HighShelf => (
+                a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+            ),
+        };
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// How the filter changes the level and phase of `frequency`
+    pub fn frequency_response(
+        &self,
+        frequency: f32,
+        sample_rate: impl Into<SampleRate>,
+    ) -> FrequencyResponse {
+        let sample_rate = sample_rate.into().get() as f64;
+        if sample_rate == 0.0 {
+            return FrequencyResponse {
+                magnitude: 1.0,
+                phase: 0.0,
+            };
+        }
+
+        // evaluate the transfer function at z = e^(jw)
+        let w = (TAU * frequency as f64 / sample_rate).clamp(0.0, PI);
+        let (sin_w, cos_w) = w.sin_cos();
+        let (sin_2w, cos_2w) = (2.0 * w).sin_cos();
+
+        let numerator_re = self.b0 + self.b1 * cos_w + self.b2 * cos_2w;
+        let numerator_im = -(self.b1 * sin_w + self.b2 * sin_2w);
+        let denominator_re = 1.0 + self.a1 * cos_w + self.a2 * cos_2w;
+        let denominator_im = -(self.a1 * sin_w + self.a2 * sin_2w);
+
+        let magnitude = numerator_re.hypot(numerator_im) / denominator_re.hypot(denominator_im);
+        let phase = numerator_im.atan2(numerator_re) - denominator_im.atan2(denominator_re);
+
+        FrequencyResponse {
+            magnitude: magnitude as f32,
+            // keep the phase between -π and π
+            phase: ((phase + PI).rem_euclid(TAU) - PI) as f32,
+        }
+    }
+}
+
+impl Default for BiquadCoefficients {
+    /// Passes the signal through unaltered
+    fn default() -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_biquad_coefficients {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{BiquadCoefficients, BiquadFilterType};
+
+    const SAMPLE_RATE: u32 = 48000;
+    const Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+    fn magnitude_db(filter_type: BiquadFilterType, gain: f32, frequency: f32) -> f32 {
+        BiquadCoefficients::new(filter_type, 1000.0, Q, gain, SAMPLE_RATE)
+            .frequency_response(frequency, SAMPLE_RATE)
+            .magnitude_db()
+    }
+
+    #[test]
+    fn pass_filters_should_be_3_db_down_at_cutoff() {
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::LowPass, 0.0, 1000.0),
+            -3.01,
+            0.01,
+        );
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::HighPass, 0.0, 1000.0),
+            -3.01,
+            0.01,
+        );
+        assert!(magnitude_db(BiquadFilterType::LowPass, 0.0, 10000.0) < -20.0);
+        assert!(magnitude_db(BiquadFilterType::HighPass, 0.0, 100.0) < -20.0);
+    }
+
+    #[test]
+    fn center_frequency_filters_should_shape_around_center() {
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::BandPass, 0.0, 1000.0),
+            0.0,
+            0.01,
+        );
+        assert!(magnitude_db(BiquadFilterType::Notch, 0.0, 1000.0) < -60.0);
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::Peaking, 6.0, 1000.0),
+            6.0,
+            0.01,
+        );
+        [100.0, 1000.0, 10000.0].iter().for_each(|frequency| {
+            assert_difference_is_within_tolerance(
+                magnitude_db(BiquadFilterType::AllPass, 0.0, *frequency),
+                0.0,
+                0.01,
+            );
+        });
+    }
+
+    #[test]
+    fn shelves_should_boost_one_side_only() {
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::LowShelf, 12.0, 20.0),
+            12.0,
+            0.1,
+        );
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::LowShelf, 12.0, 20000.0),
+            0.0,
+            0.1,
+        );
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::HighShelf, -12.0, 20000.0),
+            -12.0,
+            0.1,
+        );
+        assert_difference_is_within_tolerance(
+            magnitude_db(BiquadFilterType::HighShelf, -12.0, 20.0),
+            0.0,
+            0.1,
+        );
+    }
+
+    #[test]
+    fn should_pass_signal_through_at_very_low_sample_rates() {
+        [0, 1, 2].into_iter().for_each(|sample_rate| {
+            assert_eq!(
+                BiquadCoefficients::new(BiquadFilterType::LowPass, 1000.0, Q, 0.0, sample_rate),
+                BiquadCoefficients::default()
+            );
+        });
+    }
+}
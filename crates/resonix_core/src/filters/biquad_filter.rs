@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use crate::{
+    BiquadCoefficients, BiquadFilterType, FrequencyResponse, NumChannels, SampleRate, SmoothedValue,
+};
+
+/// Previous inputs and outputs for one channel of a biquad filter (transposed direct form II)
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+struct BiquadState {
+    z1: f64,
+    z2: f64,
+}
+
+impl BiquadState {
+    #[inline]
+    fn process(&mut self, input: f32, coefficients: &BiquadCoefficients) -> f32 {
+        let input = input as f64;
+        let output = coefficients.b0 * input + self.z1;
+        self.z1 = coefficients.b1 * input - coefficients.a1 * output + self.z2;
+        self.z2 = coefficients.b2 * input - coefficients.a2 * output;
+        output as f32
+    }
+}
+
+/// A multichannel second-order filter, which can be any of the `BiquadFilterType`s.
+///
+/// Every channel shares the same settings but keeps its own state.
+/// Changes to frequency, Q, and gain are smoothed over `smoothing_time`,
+/// so that they can be changed (or modulated) while audio is playing without clicking.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct BiquadFilter {
+    sample_rate: SampleRate,
+    filter_type: BiquadFilterType,
+    frequency: SmoothedValue,
+    q: SmoothedValue,
+    /// Gain in dB
+    gain: SmoothedValue,
+    coefficients: BiquadCoefficients,
+    states: Vec<BiquadState>,
+}
+
+impl BiquadFilter {
+    pub const DEFAULT_FREQUENCY: f32 = 350.0;
+    pub const DEFAULT_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+    pub const DEFAULT_GAIN: f32 = 0.0;
+    pub const DEFAULT_SMOOTHING_TIME: Duration = Duration::from_millis(20);
+
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        filter_type: BiquadFilterType,
+    ) -> Self {
+        let sample_rate = sample_rate.into();
+        let smoothed = |value| SmoothedValue::new(sample_rate, Self::DEFAULT_SMOOTHING_TIME, value);
+        let mut filter = Self {
+            sample_rate,
+            filter_type,
+            frequency: smoothed(Self::DEFAULT_FREQUENCY),
+            q: smoothed(Self::DEFAULT_Q),
+            gain: smoothed(Self::DEFAULT_GAIN),
+            coefficients: BiquadCoefficients::default(),
+            states: vec![BiquadState::default(); *num_channels.into()],
+        };
+        filter.update_coefficients();
+        filter
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.states.len())
+    }
+
+    pub fn filter_type(&self) -> BiquadFilterType {
+        self.filter_type
+    }
+
+    /// Takes effect immediately
+    pub fn set_filter_type(&mut self, filter_type: BiquadFilterType) -> &mut Self {
+        self.filter_type = filter_type;
+        self.update_coefficients();
+        self
+    }
+
+    /// The frequency that the filter is moving towards
+    pub fn frequency(&self) -> f32 {
+        self.frequency.target()
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.frequency.set_target(frequency);
+        self
+    }
+
+    /// The Q that the filter is moving towards
+    pub fn q(&self) -> f32 {
+        self.q.target()
+    }
+
+    pub fn set_q(&mut self, q: f32) -> &mut Self {
+        self.q.set_target(q);
+        self
+    }
+
+    /// The gain (in dB) that the filter is moving towards
+    pub fn gain(&self) -> f32 {
+        self.gain.target()
+    }
+
+    /// Only used by the peaking and shelf filter types
+    pub fn set_gain(&mut self, gain: f32) -> &mut Self {
+        self.gain.set_target(gain);
+        self
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.frequency.smoothing_time()
+    }
+
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.frequency.set_smoothing_time(smoothing_time);
+        self.q.set_smoothing_time(smoothing_time);
+        self.gain.set_smoothing_time(smoothing_time);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self.frequency.set_sample_rate(self.sample_rate);
+        self.q.set_sample_rate(self.sample_rate);
+        self.gain.set_sample_rate(self.sample_rate);
+        self.update_coefficients();
+        self
+    }
+
+    /// The coefficients currently being used (which may be partway through smoothing)
+    pub fn coefficients(&self) -> BiquadCoefficients {
+        self.coefficients
+    }
+
+    /// How the filter will change the level and phase of `frequency`
+    /// once it has finished moving to its current settings
+    pub fn frequency_response(&self, frequency: f32) -> FrequencyResponse {
+        BiquadCoefficients::new(
+            self.filter_type,
+            self.frequency.target(),
+            self.q.target(),
+            self.gain.target(),
+            self.sample_rate,
+        )
+        .frequency_response(frequency, self.sample_rate)
+    }
+
+    /// Clears the filter's memory of previous samples
+    pub fn reset(&mut self) -> &mut Self {
+        self.states.fill(BiquadState::default());
+        self
+    }
+
+    /// Filters one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        if self.frequency.is_smoothing() || self.q.is_smoothing() || self.gain.is_smoothing() {
+            self.frequency.next_value();
+            self.q.next_value();
+            self.gain.next_value();
+            self.update_coefficients();
+        }
+
+        frame
+            .iter_mut()
+            .zip(self.states.iter_mut())
+            .for_each(|(sample, state)| *sample = state.process(*sample, &self.coefficients));
+    }
+
+    fn update_coefficients(&mut self) {
+        self.coefficients = BiquadCoefficients::new(
+            self.filter_type,
+            self.frequency.value(),
+            self.q.value(),
+            self.gain.value(),
+            self.sample_rate,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_biquad_filter {
+    use std::f32::consts::TAU;
+
+    use crate::{BiquadFilter, BiquadFilterType};
+
+    const SAMPLE_RATE: u32 = 48000;
+
+    /// Filters a sine wave and returns the peak amplitude of the output after it settles
+    fn filtered_sine_amplitude(filter: &mut BiquadFilter, frequency: f32) -> f32 {
+        (0..SAMPLE_RATE / 10)
+            .map(|i| {
+                let mut frame = [(TAU * frequency * i as f32 / SAMPLE_RATE as f32).sin()];
+                filter.process_frame(&mut frame);
+                frame[0]
+            })
+            .skip(SAMPLE_RATE as usize / 20)
+            .fold(0.0, |max, sample| sample.abs().max(max))
+    }
+
+    #[test]
+    fn it_should_filter_each_channel_separately() {
+        let mut filter = BiquadFilter::new(2, SAMPLE_RATE, BiquadFilterType::LowPass);
+
+        let mut first_frame = [1.0, 0.0];
+        filter.process_frame(&mut first_frame);
+        assert!(first_frame[0] > 0.0);
+        assert_eq!(first_frame[1], 0.0);
+
+        let mut second_frame = [0.0, 0.0];
+        filter.process_frame(&mut second_frame);
+        assert!(second_frame[0] > 0.0);
+        assert_eq!(second_frame[1], 0.0);
+    }
+
+    #[test]
+    fn it_should_attenuate_according_to_frequency() {
+        let mut filter = BiquadFilter::new(1, SAMPLE_RATE, BiquadFilterType::LowPass);
+        filter.set_frequency(500.0);
+
+        assert!(filtered_sine_amplitude(&mut filter, 100.0) > 0.95);
+        filter.reset();
+        assert!(filtered_sine_amplitude(&mut filter, 5000.0) < 0.02);
+    }
+
+    #[test]
+    fn it_should_smooth_frequency_changes() {
+        let mut filter = BiquadFilter::new(1, SAMPLE_RATE, BiquadFilterType::LowPass);
+        let initial_coefficients = filter.coefficients();
+
+        filter.set_frequency(5000.0);
+        assert_eq!(filter.frequency(), 5000.0);
+        assert_eq!(filter.coefficients(), initial_coefficients);
+
+        filter.process_frame(&mut [0.0]);
+        let smoothing_coefficients = filter.coefficients();
+        assert_ne!(smoothing_coefficients, initial_coefficients);
+
+        (0..SAMPLE_RATE / 10).for_each(|_| filter.process_frame(&mut [0.0]));
+        assert_ne!(filter.coefficients(), smoothing_coefficients);
+        assert_eq!(
+            filter.frequency_response(5000.0),
+            filter
+                .coefficients()
+                .frequency_response(5000.0, SAMPLE_RATE)
+        );
+    }
+}
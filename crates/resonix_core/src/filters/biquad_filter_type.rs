@@ -0,0 +1,31 @@
+/// The filter shapes from Robert Bristow-Johnson's "Audio EQ Cookbook"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum BiquadFilterType {
+    /// Passes frequencies below the cutoff frequency
+    #[default]
+    LowPass,
+    /// Passes frequencies above the cutoff frequency
+    HighPass,
+    /// Passes frequencies around the center frequency (with a peak gain of 0 dB)
+    BandPass,
+    /// Removes frequencies around the center frequency
+    Notch,
+    /// Passes every frequency, but shifts the phase of frequencies around the center frequency
+    AllPass,
+    /// Boosts or cuts frequencies around the center frequency by `gain`
+    Peaking,
+    /// Boosts or cuts frequencies below the cutoff frequency by `gain`
+    LowShelf,
+    /// Boosts or cuts frequencies above the cutoff frequency by `gain`
+    HighShelf,
+}
+
+impl BiquadFilterType {
+    /// Whether the filter's `gain` has any effect on its shape
+    pub fn uses_gain(&self) -> bool {
+        matches!(
+            self,
+            BiquadFilterType::Peaking | BiquadFilterType::LowShelf | BiquadFilterType::HighShelf
+        )
+    }
+}
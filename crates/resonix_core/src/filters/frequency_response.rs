@@ -0,0 +1,64 @@
+use crate::{BiquadCoefficients, BiquadFilterType, Decibel, SampleRate};
+
+/// How a filter changes the level and phase of a single frequency
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Default)]
+pub struct FrequencyResponse {
+    /// Linear gain (1.0 leaves the level unchanged)
+    pub magnitude: f32,
+    /// Phase shift in radians
+    pub phase: f32,
+}
+
+impl FrequencyResponse {
+    /// Gain in decibels (0.0 dB leaves the level unchanged)
+    pub fn magnitude_db(&self) -> f32 {
+        Decibel::calculate_full_scale(self.magnitude)
+    }
+}
+
+/// Calculates how a biquad filter with the given settings changes the level
+/// and phase of each of the given `frequencies` (e.g. for plotting a filter's curve in a UI).
+///
+/// Since this only needs the filter's settings, it can be used for a filter
+/// that is already running in the audio thread.
+pub fn biquad_frequency_response(
+    filter_type: BiquadFilterType,
+    frequency: f32,
+    q: f32,
+    gain: f32,
+    sample_rate: impl Into<SampleRate>,
+    frequencies: &[f32],
+) -> Vec<FrequencyResponse> {
+    let sample_rate = sample_rate.into();
+    let coefficients = BiquadCoefficients::new(filter_type, frequency, q, gain, sample_rate);
+
+    frequencies
+        .iter()
+        .map(|frequency| coefficients.frequency_response(*frequency, sample_rate))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_frequency_response {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{biquad_frequency_response, BiquadFilterType};
+
+    #[test]
+    fn should_calculate_biquad_frequency_response_from_settings() {
+        let response = biquad_frequency_response(
+            BiquadFilterType::LowPass,
+            1000.0,
+            std::f32::consts::FRAC_1_SQRT_2,
+            0.0,
+            44100,
+            &[20.0, 1000.0, 15000.0],
+        );
+
+        assert_eq!(response.len(), 3);
+        assert_difference_is_within_tolerance(response[0].magnitude_db(), 0.0, 0.01);
+        // a Q of 1/√2 is 3 dB down at the cutoff
+        assert_difference_is_within_tolerance(response[1].magnitude_db(), -3.01, 0.01);
+        assert!(response[2].magnitude_db() < -40.0);
+    }
+}
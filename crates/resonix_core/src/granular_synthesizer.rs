@@ -1,7 +1,9 @@
 mod granular_synthesizer_action;
 mod granular_synthesizer_grain;
+mod granular_synthesizer_position_mode;
 mod granular_synthesizer_struct;
 
 pub use granular_synthesizer_action::*;
 pub use granular_synthesizer_grain::*;
+pub use granular_synthesizer_position_mode::*;
 pub use granular_synthesizer_struct::*;
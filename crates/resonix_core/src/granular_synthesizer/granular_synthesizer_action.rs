@@ -1,6 +1,8 @@
 use std::{sync::Arc, time::Duration};
 
-use crate::{EnvelopeType, GranularSynthesizerGrain, NumChannels, Percentage};
+use crate::{
+    EnvelopeType, GrainPositionMode, GranularSynthesizerGrain, NumChannels, PanMode, Percentage,
+};
 
 /// Public interface to the GranularSynthesizer.
 ///
@@ -29,6 +31,14 @@ pub trait GranularSynthesizerAction {
     /// Using a prime number leads to the least periodic overlap in grains.
     const DEFAULT_REFRESH_INTERVAL: u32 = 271;
 
+    const PITCH_SHIFT_MIN: f32 = -48.0;
+
+    const PITCH_SHIFT_MAX: f32 = 48.0;
+
+    /// Most grains that can play at once when `num_grains` is set
+    /// (unless another maximum is given to `GranularSynthesizer::new_with_max_num_grains`)
+    const DEFAULT_MAX_NUM_GRAINS: usize = 256;
+
     /// Creates a new GranularSynthesizer instance
     fn new() -> Self;
 
@@ -64,6 +74,9 @@ pub trait GranularSynthesizerAction {
     /// Any existing / current playing grains that are compatible with new buffer
     /// length will keep their internal state unchanged and will sample from the
     /// new buffer on the next frame.
+    ///
+    /// The buffer is assumed to already be at the synthesizer's sample rate.
+    /// Audio at any other sample rate should be converted first (see `resample_samples`).
     fn set_buffer(&mut self, buffer: Arc<Vec<f32>>) -> &mut Self;
 
     /// Returns a full audio frame (1 array element = 1 audio channel value),
@@ -92,6 +105,8 @@ pub trait GranularSynthesizerAction {
     fn new_grain(uid: u32) -> GranularSynthesizerGrain {
         GranularSynthesizerGrain {
             current_frame: 0,
+            position: 0.0,
+            playback_rate: 1.0,
             end_frame: 0,
             len: 0,
             start_frame: 0,
@@ -106,4 +121,60 @@ pub trait GranularSynthesizerAction {
     fn set_envelope(&mut self, envelope_type: EnvelopeType) -> &mut Self;
 
     fn grain_len(&self) -> Duration;
+
+    /// Pitch shift (in semitones) of new grains, before random spread is applied
+    fn pitch_shift(&self) -> f32;
+
+    /// Shifts the pitch of new grains by resampling them, where 12.0 semitones plays grains
+    /// an octave higher (and twice as fast). Use fractions of a semitone for cents
+    /// (e.g. 0.25 is 25 cents). Clamped between `PITCH_SHIFT_MIN` and `PITCH_SHIFT_MAX`.
+    fn set_pitch_shift(&mut self, semitones: f32) -> &mut Self;
+
+    fn pitch_spread(&self) -> f32;
+
+    /// Shifts the pitch of each new grain by a random amount of up to
+    /// `semitones` above or below `pitch_shift` (0.0 disables spread)
+    fn set_pitch_spread(&mut self, semitones: f32) -> &mut Self;
+
+    fn position_mode(&self) -> GrainPositionMode;
+
+    /// Choose between grains that start at random positions and grains that
+    /// follow a moving scan position (for time stretching)
+    fn set_position_mode(&mut self, position_mode: GrainPositionMode) -> &mut Self;
+
+    /// Current scan position as a percentage of the whole buffer
+    /// (only used with `GrainPositionMode::Scan`)
+    fn scan_position(&self) -> Percentage;
+
+    /// Moves the scan position to a percentage of the whole buffer.
+    /// If it falls outside the selection, it wraps back around into the selection.
+    fn set_scan_position(&mut self, position: impl Into<Percentage>) -> &mut Self;
+
+    /// How many grains play at once
+    fn num_grains(&self) -> usize;
+
+    /// By default (`None`), each output channel plays exactly one grain.
+    ///
+    /// Setting a number of grains decouples grain density from the number of channels:
+    /// every grain is placed at a random position among the output channels (see `set_pan_mode`
+    /// and `set_pan_spread`), so even a stereo output can contain hundreds of overlapping grains.
+    /// The mix of all grains is scaled by `1 / sqrt(num_grains)` to keep dense clouds from clipping.
+    ///
+    /// With all grains roughly the same length, about `num_grains / grain_len` grains start every second.
+    ///
+    /// `num_grains` is clamped to the maximum number of grains that were allocated up front,
+    /// so that changing it never allocates.
+    fn set_num_grains(&mut self, num_grains: impl Into<Option<usize>>) -> &mut Self;
+
+    fn pan_mode(&self) -> PanMode;
+
+    /// How grain positions are mapped onto the output channels (only used when `num_grains` is set)
+    fn set_pan_mode(&mut self, pan_mode: PanMode) -> &mut Self;
+
+    fn pan_spread(&self) -> f32;
+
+    /// How widely new grains are scattered among the output channels, from 0.0 (every grain
+    /// in the center) to 1.0 (anywhere from the first to the last channel, or anywhere
+    /// around the ring with `PanMode::Ring`). Only used when `num_grains` is set.
+    fn set_pan_spread(&mut self, pan_spread: f32) -> &mut Self;
 }
@@ -3,11 +3,17 @@ use std::hash::Hash;
 use nohash_hasher::IsEnabled;
 
 /// Contains information about where in a buffer the grain should sample from
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug)]
 pub struct GranularSynthesizerGrain {
     pub start_frame: usize,
     pub end_frame: usize,
+    /// The whole frame of the buffer that `position` is currently in
     pub current_frame: usize,
+    /// Exact (fractional) read position within the buffer
+    pub position: f64,
+    /// How many frames of the buffer the grain moves through for every frame of output
+    /// (e.g. 2.0 plays the grain an octave higher and twice as fast)
+    pub playback_rate: f64,
     /// the number of frames between `start_frame` and `end_frame` in samples
     pub len: usize,
     /// allows O(1) look-ups when finding grains that are finished
@@ -29,11 +35,34 @@ impl Hash for GranularSynthesizerGrain {
     }
 }
 
+/// Grains are compared (and ordered) by uid, since the rest of their state is always changing
+impl PartialEq for GranularSynthesizerGrain {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for GranularSynthesizerGrain {}
+
+impl PartialOrd for GranularSynthesizerGrain {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GranularSynthesizerGrain {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
 impl Default for GranularSynthesizerGrain {
     fn default() -> Self {
         Self {
             start_frame: 0,
             current_frame: 0,
+            position: 0.0,
+            playback_rate: 1.0,
             end_frame: 0,
             is_finished: true,
             len: 0,
@@ -46,10 +75,23 @@ impl Default for GranularSynthesizerGrain {
 
 impl GranularSynthesizerGrain {
     pub fn new(start_frame: usize, end_frame: usize, uid: u32, init: bool) -> Self {
+        Self::new_with_playback_rate(start_frame, end_frame, uid, init, 1.0)
+    }
+
+    pub fn new_with_playback_rate(
+        start_frame: usize,
+        end_frame: usize,
+        uid: u32,
+        init: bool,
+        playback_rate: f64,
+    ) -> Self {
         debug_assert!(start_frame < end_frame);
+        debug_assert!(playback_rate > 0.0);
         GranularSynthesizerGrain {
             start_frame,
             current_frame: start_frame,
+            position: start_frame as f64,
+            playback_rate,
             end_frame,
             is_finished: false,
             len: end_frame - start_frame,
@@ -68,7 +110,8 @@ impl GranularSynthesizerGrain {
             || (self.end_frame as u32) > selection_end_in_samples
     }
 
-    /// Increments the current_frame and returns it.
+    /// Moves the position forward by `playback_rate` and returns the frame
+    /// that was current before moving.
     ///
     /// If the grain is already finished, this is a no-op and `None` is returned.
     pub fn next_frame(&mut self) -> Option<usize> {
@@ -79,8 +122,9 @@ impl GranularSynthesizerGrain {
         // return the frame that was valid before incrementing the count
         let frame_to_return = self.current_frame;
 
-        self.current_frame += 1;
-        if self.current_frame == self.end_frame {
+        self.position += self.playback_rate;
+        self.current_frame = self.position as usize;
+        if self.current_frame >= self.end_frame {
             self.is_finished = true;
         }
 
@@ -88,6 +132,35 @@ impl GranularSynthesizerGrain {
     }
 
     pub fn remaining_samples(&self) -> usize {
-        self.end_frame - self.current_frame
+        self.end_frame.saturating_sub(self.current_frame)
+    }
+}
+
+#[cfg(test)]
+mod test_granular_synthesizer_grain {
+    use crate::GranularSynthesizerGrain;
+
+    #[test]
+    fn it_should_move_through_buffer_at_playback_rate() {
+        let mut grain = GranularSynthesizerGrain::new_with_playback_rate(10, 13, 0, true, 0.75);
+
+        let frames: Vec<_> = std::iter::from_fn(|| grain.next_frame()).collect();
+
+        assert_eq!(frames, vec![10, 10, 11, 12]);
+        assert!(grain.is_finished);
+    }
+
+    #[test]
+    fn it_should_be_ordered_by_uid() {
+        let mut grains = [
+            GranularSynthesizerGrain::new_with_playback_rate(0, 10, 2, true, 0.5),
+            GranularSynthesizerGrain::new(20, 30, 0, true),
+            GranularSynthesizerGrain::new(5, 6, 1, false),
+        ];
+        grains.sort();
+
+        let uids: Vec<_> = grains.iter().map(|grain| grain.uid).collect();
+        assert_eq!(uids, vec![0, 1, 2]);
+        assert_eq!(grains[0], GranularSynthesizerGrain::new(0, 1, 0, false));
     }
 }
@@ -0,0 +1,14 @@
+/// Determines where in the buffer selection new grains start playing from
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub enum GrainPositionMode {
+    /// Every grain starts at a random position within the selection
+    #[default]
+    Random,
+    /// Every grain starts at a scan position, which moves through the selection
+    /// (wrapping around at the ends) at `speed` times real time.
+    ///
+    /// Since the pitch of each grain is set by its own playback rate, this stretches
+    /// (or compresses) time without changing pitch: e.g. a `speed` of 0.5 plays the
+    /// selection back at half speed, and a `speed` of 0.0 freezes it in place.
+    Scan { speed: f32 },
+}
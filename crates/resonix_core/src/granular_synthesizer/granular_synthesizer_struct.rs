@@ -1,8 +1,10 @@
+use crate::linear_interpolate;
+use crate::GrainPositionMode;
 use crate::GranularSynthesizerAction;
 use crate::GranularSynthesizerGrain as Grain;
 use crate::LazyCached;
 use crate::Percentage;
-use crate::{Envelope, EnvelopeType, NumChannels};
+use crate::{Envelope, EnvelopeType, NumChannels, PanMode};
 use nohash_hasher::IntMap;
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
@@ -66,6 +68,37 @@ pub struct GranularSynthesizer {
 
     /// cached for more efficient processing
     selection_start_in_samples: LazyCached<u32>,
+
+    /// Pitch shift (in semitones) applied to every new grain
+    pitch_shift: f32,
+
+    /// Largest random deviation (in semitones) from `pitch_shift` for each new grain
+    pitch_spread: f32,
+
+    /// Where new grains start reading from in the buffer
+    position_mode: GrainPositionMode,
+
+    /// Frame of the buffer that new grains start from in `GrainPositionMode::Scan`
+    scan_position: f64,
+
+    /// How many grains play at once (`None` plays one grain in each channel)
+    num_grains: Option<usize>,
+
+    /// Grains (and their positions and gains) are allocated up front for this many grains,
+    /// so that `num_grains` can be changed from the audio thread without allocating
+    max_num_grains: usize,
+
+    /// How each grain's position is mapped onto the output channels (when `num_grains` is set)
+    pan_mode: PanMode,
+
+    /// How widely new grains are scattered among the output channels (from 0.0 to 1.0)
+    pan_spread: f32,
+
+    /// Spatial position of each grain (indexed by uid, with room for `max_num_grains`)
+    grain_positions: Vec<f32>,
+
+    /// Gain of every output channel for each grain (indexed by `uid * num_channels + channel`)
+    grain_gains: Vec<f32>,
 }
 
 impl GranularSynthesizerAction for GranularSynthesizer {
@@ -132,6 +165,9 @@ impl GranularSynthesizerAction for GranularSynthesizer {
 
     fn set_num_channels(&mut self, num_channels: impl Into<NumChannels>) -> &mut Self {
         self.num_channels = num_channels.into();
+        self.grain_gains
+            .resize(self.max_num_grains * *self.num_channels, 0.0);
+        self.update_all_grain_gains();
         self
     }
 
@@ -172,23 +208,111 @@ impl GranularSynthesizerAction for GranularSynthesizer {
         self.envelope = envelope_type.into();
         self
     }
+
+    fn pitch_shift(&self) -> f32 {
+        self.pitch_shift
+    }
+
+    fn set_pitch_shift(&mut self, semitones: f32) -> &mut Self {
+        self.pitch_shift = semitones.clamp(Self::PITCH_SHIFT_MIN, Self::PITCH_SHIFT_MAX);
+        self
+    }
+
+    fn pitch_spread(&self) -> f32 {
+        self.pitch_spread
+    }
+
+    fn set_pitch_spread(&mut self, semitones: f32) -> &mut Self {
+        self.pitch_spread = semitones.clamp(0.0, Self::PITCH_SHIFT_MAX);
+        self
+    }
+
+    fn position_mode(&self) -> GrainPositionMode {
+        self.position_mode
+    }
+
+    fn set_position_mode(&mut self, position_mode: GrainPositionMode) -> &mut Self {
+        self.position_mode = position_mode;
+        self
+    }
+
+    fn scan_position(&self) -> Percentage {
+        if self.buffer.is_empty() {
+            return Percentage::from(0.0);
+        }
+        Percentage::from((self.scan_position / self.buffer.len() as f64) as f32)
+    }
+
+    fn set_scan_position(&mut self, position: impl Into<Percentage>) -> &mut Self {
+        self.scan_position = (self.buffer.len() as f32 * position.into()) as f64;
+        self.move_scan_position(0.0);
+        self
+    }
+
+    fn num_grains(&self) -> usize {
+        self.num_grains.unwrap_or(*self.num_channels)
+    }
+
+    fn set_num_grains(&mut self, num_grains: impl Into<Option<usize>>) -> &mut Self {
+        self.num_grains = num_grains
+            .into()
+            .map(|num_grains| num_grains.min(self.max_num_grains));
+        self
+    }
+
+    fn pan_mode(&self) -> PanMode {
+        self.pan_mode
+    }
+
+    fn set_pan_mode(&mut self, pan_mode: PanMode) -> &mut Self {
+        self.pan_mode = pan_mode;
+        self.update_all_grain_gains();
+        self
+    }
+
+    fn pan_spread(&self) -> f32 {
+        self.pan_spread
+    }
+
+    fn set_pan_spread(&mut self, pan_spread: f32) -> &mut Self {
+        self.pan_spread = pan_spread.clamp(0.0, 1.0);
+        self
+    }
 }
 
 // internal logic to support public GranularSynthesizer interface
 impl GranularSynthesizer {
+    /// Leaves room for up to `max_num_grains` grains to play at once (see `set_num_grains`)
+    pub fn new_with_max_num_grains(max_num_grains: usize) -> Self {
+        let seed: <SmallRng as SeedableRng>::Seed = Default::default();
+        GranularSynthesizer::from_seed_with_max_num_grains(seed, max_num_grains)
+    }
+
     /// Allows seeding random number generator manually for consistent snapshot testing
     pub fn from_seed(seed: <SmallRng as SeedableRng>::Seed) -> Self {
+        Self::from_seed_with_max_num_grains(seed, Self::DEFAULT_MAX_NUM_GRAINS)
+    }
+
+    pub fn from_seed_with_max_num_grains(
+        seed: <SmallRng as SeedableRng>::Seed,
+        max_num_grains: usize,
+    ) -> Self {
         let default_buffer = Arc::new(Vec::new());
-        let mut uninitialized_grains = IntMap::default();
+
+        // every grain can be in any of the grain maps at once, and maps that are at most
+        // half full can clean up after removed grains without reallocating
+        let grains_capacity = 2 * max_num_grains.max(Self::DEFAULT_NUM_CHANNELS);
+        let grains_map = || IntMap::with_capacity_and_hasher(grains_capacity, Default::default());
+        let mut uninitialized_grains = grains_map();
 
         for grain in (0..Self::DEFAULT_NUM_CHANNELS).map(|i| Self::new_grain(i as u32)) {
             uninitialized_grains.insert(grain.uid, grain);
         }
 
-        let fresh_grains = IntMap::default();
-        let finished_grains = IntMap::default();
+        let fresh_grains = grains_map();
+        let finished_grains = grains_map();
 
-        Self {
+        let mut granular_synthesizer = Self {
             sample_rate: Self::DEFAULT_SAMPLE_RATE,
             buffer: default_buffer,
             rng: SmallRng::from_seed(seed),
@@ -204,7 +328,23 @@ impl GranularSynthesizer {
             grain_initialization_delay: Self::DEFAULT_GRAIN_INITIALIZATION_DELAY,
             selection_end_in_samples: LazyCached::new_uncached(),
             selection_start_in_samples: LazyCached::new_uncached(),
-        }
+            pitch_shift: 0.0,
+            pitch_spread: 0.0,
+            position_mode: GrainPositionMode::default(),
+            scan_position: 0.0,
+            num_grains: None,
+            max_num_grains,
+            pan_mode: PanMode::default(),
+            pan_spread: 1.0,
+            grain_positions: vec![0.0; max_num_grains],
+            grain_gains: vec![0.0; max_num_grains * Self::DEFAULT_NUM_CHANNELS],
+        };
+        granular_synthesizer.update_all_grain_gains();
+        granular_synthesizer
+    }
+
+    pub fn max_num_grains(&self) -> usize {
+        self.max_num_grains
     }
 
     /// This is the pipeline for generating a frame of audio--it can be shared
@@ -215,21 +355,23 @@ impl GranularSynthesizer {
         frame_data_buffer: &'a mut [f32],
         is_new_buffer: bool,
     ) -> &'a mut [f32] {
-        self.synchronize_num_grains_with_channels();
+        self.synchronize_num_grains();
         self.initialize_an_uninitialized_grain();
         self.refresh_finished_grains();
+        self.advance_scan_position();
         self.increment_frame_count();
         self.write_frame_data_into_buffer(frame_data_buffer, is_new_buffer)
     }
 
-    /// Make sure num_channels is equal to the total number of grains
-    fn synchronize_num_grains_with_channels(&mut self) -> &mut Self {
-        let num_channels = *self.num_channels();
+    /// Make sure the total number of grains is equal to `num_grains`
+    /// (which is the number of channels by default)
+    fn synchronize_num_grains(&mut self) -> &mut Self {
+        let num_grains = self.num_grains();
         let total_num_grains = self.total_num_grains();
 
-        match num_channels.cmp(&total_num_grains) {
+        match num_grains.cmp(&total_num_grains) {
             Ordering::Greater => {
-                let new_grains_to_add = num_channels - total_num_grains;
+                let new_grains_to_add = num_grains - total_num_grains;
                 for grain in (total_num_grains..(total_num_grains + new_grains_to_add))
                     .map(|i| Self::new_grain(i as u32))
                 {
@@ -237,9 +379,9 @@ impl GranularSynthesizer {
                 }
             }
             Ordering::Less => {
-                // get rid of all grains that exceed the total number of channels
-                let num_channels = *self.num_channels as u32;
-                let filter_grain = |_: &u32, grain: &mut Grain| grain.uid < num_channels;
+                // get rid of all grains that exceed the total number of grains
+                let num_grains = num_grains as u32;
+                let filter_grain = |_: &u32, grain: &mut Grain| grain.uid < num_grains;
 
                 self.fresh_grains.retain(filter_grain);
                 self.finished_grains.retain(filter_grain);
@@ -251,6 +393,43 @@ impl GranularSynthesizer {
         self
     }
 
+    /// Gives a freshly started grain a new random position among the output channels
+    /// (random numbers are only used when `num_grains` is set)
+    fn assign_grain_position(&mut self, uid: u32) {
+        if self.num_grains.is_none() || uid as usize >= self.grain_positions.len() {
+            return;
+        }
+
+        let position = if self.pan_spread > 0.0 {
+            self.rng.gen_range(-self.pan_spread..=self.pan_spread)
+        } else {
+            0.0
+        };
+
+        self.grain_positions[uid as usize] = match self.pan_mode {
+            PanMode::EqualPower => position,
+            // a full spread covers the whole way around the ring
+            PanMode::Ring => position / 2.0,
+        };
+        self.update_grain_gains(uid as usize);
+    }
+
+    fn update_grain_gains(&mut self, uid: usize) {
+        let num_channels = *self.num_channels;
+        let Some(gains) = self
+            .grain_gains
+            .get_mut(uid * num_channels..(uid + 1) * num_channels)
+        else {
+            return;
+        };
+
+        self.pan_mode.as_panner_to_buffer()(self.grain_positions[uid], gains);
+    }
+
+    fn update_all_grain_gains(&mut self) {
+        (0..self.grain_positions.len()).for_each(|uid| self.update_grain_gains(uid));
+    }
+
     fn total_num_grains(&self) -> usize {
         self.uninitialized_grains.len() + self.finished_grains.len() + self.fresh_grains.len()
     }
@@ -285,11 +464,11 @@ impl GranularSynthesizer {
         // decreases runtime performance by ~25%, I'm leaving it out for now
         // grains.sort_by_key(|grain| grain.uid);
 
-        let half_way = self.uninitialized_grains.len().min(*self.num_channels) / 2;
+        let half_way = self.uninitialized_grains.len().min(self.num_grains()) / 2;
 
         // uninitialized grain should be moved into the fresh_grains list--
         // the new, refreshed grain should use the same uid as the uninitialized one
-        let Some(Grain {  uid, .. }) = grains.get(half_way) else {
+        let Some(Grain { uid, .. }) = grains.get(half_way) else {
             return self;
         };
 
@@ -297,9 +476,17 @@ impl GranularSynthesizer {
         self.uninitialized_grains.remove(&uid);
 
         const INIT: bool = true;
-        let (grain_start_index, grain_end_index) = self.get_grain_random_start_and_end();
-        let fresh_grain = Grain::new(grain_start_index, grain_end_index, uid, INIT);
+        let playback_rate = self.next_grain_playback_rate();
+        let (grain_start_index, grain_end_index) = self.get_grain_start_and_end(playback_rate);
+        let fresh_grain = Grain::new_with_playback_rate(
+            grain_start_index,
+            grain_end_index,
+            uid,
+            INIT,
+            playback_rate,
+        );
         self.fresh_grains.insert(fresh_grain.uid, fresh_grain);
+        self.assign_grain_position(uid);
 
         self
     }
@@ -318,18 +505,57 @@ impl GranularSynthesizer {
             .max(Self::GRAIN_LEN_MIN)
     }
 
-    fn grain_len_in_samples(&mut self) -> u32 {
+    /// The number of frames of the buffer that a grain plays through
+    /// (grains that play back faster read through more of the buffer)
+    fn grain_len_in_samples(&mut self, playback_rate: f64) -> u32 {
         let selection_start_index = self.selection_start_in_samples();
         let selection_end_index = self.selection_end_in_samples();
         let selection_len_in_samples = selection_end_index - selection_start_index;
 
         let samples_per_second = self.sample_rate as f32;
         let grain_len_in_seconds = self.grain_len.as_secs_f32();
-        let grain_len_in_samples = (samples_per_second * grain_len_in_seconds) as u32;
+        let grain_len_in_samples =
+            ((samples_per_second * grain_len_in_seconds * playback_rate as f32) as u32).max(1);
 
         selection_len_in_samples.min(grain_len_in_samples)
     }
 
+    /// Random spread is only calculated when enabled, so that grain positions
+    /// come out the same as before for any given seed when there is no spread
+    fn next_grain_playback_rate(&mut self) -> f64 {
+        let spread = if self.pitch_spread > 0.0 {
+            self.rng.gen_range(-self.pitch_spread..=self.pitch_spread)
+        } else {
+            0.0
+        };
+
+        2.0f64.powf((self.pitch_shift + spread) as f64 / 12.0)
+    }
+
+    /// Moves the scan position forward (when scanning)
+    fn advance_scan_position(&mut self) {
+        let GrainPositionMode::Scan { speed } = self.position_mode else {
+            return;
+        };
+
+        self.move_scan_position(speed as f64);
+    }
+
+    /// Moves the scan position by `num_frames`, wrapping it around within the selection
+    fn move_scan_position(&mut self, num_frames: f64) {
+        if self.buffer_selection_is_empty() {
+            return;
+        }
+
+        let selection_start_index = self.selection_start_in_samples() as f64;
+        let selection_len_in_samples =
+            self.selection_end_in_samples() as f64 - selection_start_index;
+
+        self.scan_position = selection_start_index
+            + (self.scan_position + num_frames - selection_start_index)
+                .rem_euclid(selection_len_in_samples);
+    }
+
     /// Iterates through array of grains (1 grain for each channel), and refreshes 1
     /// grain that was previously finished with a new range of buffer indexes.
     fn refresh_finished_grains(&mut self) {
@@ -344,15 +570,18 @@ impl GranularSynthesizer {
             .collect();
 
         for uid in uids {
-            let (grain_start_index, grain_end_index) = self.get_grain_random_start_and_end();
-            let fresh_grain = Grain::new(
+            let playback_rate = self.next_grain_playback_rate();
+            let (grain_start_index, grain_end_index) = self.get_grain_start_and_end(playback_rate);
+            let fresh_grain = Grain::new_with_playback_rate(
                 grain_start_index,
                 grain_end_index,
                 // keep the same uid as previous grain
                 uid,
                 true,
+                playback_rate,
             );
             self.fresh_grains.insert(uid, fresh_grain);
+            self.assign_grain_position(uid);
         }
     }
 
@@ -364,7 +593,7 @@ impl GranularSynthesizer {
         selection_start_index >= selection_end_index
     }
 
-    fn get_grain_random_start_and_end(&mut self) -> (usize, usize) {
+    fn get_grain_start_and_end(&mut self, playback_rate: f64) -> (usize, usize) {
         // get start and end of selection
         let selection_start_index = self.selection_start_in_samples();
         let selection_end_index = self.selection_end_in_samples();
@@ -373,7 +602,7 @@ impl GranularSynthesizer {
             return (selection_start_index as usize, selection_end_index as usize);
         }
 
-        let grain_len_in_samples = self.grain_len_in_samples();
+        let grain_len_in_samples = self.grain_len_in_samples(playback_rate);
 
         let smallest_start_index = selection_start_index;
         let range_would_be_empty = (selection_end_index < grain_len_in_samples)
@@ -385,12 +614,16 @@ impl GranularSynthesizer {
             selection_end_index - grain_len_in_samples
         };
 
-        // get random starting index inside selection
-        let grain_start_index = if smallest_start_index < largest_start_index {
-            self.rng
-                .gen_range(smallest_start_index..=largest_start_index)
-        } else {
-            smallest_start_index
+        let grain_start_index = match self.position_mode {
+            // get random starting index inside selection
+            GrainPositionMode::Random if smallest_start_index < largest_start_index => self
+                .rng
+                .gen_range(smallest_start_index..=largest_start_index),
+            GrainPositionMode::Random => smallest_start_index,
+            // start at the scan position, but keep the whole grain inside the selection
+            GrainPositionMode::Scan { .. } => {
+                (self.scan_position as u32).clamp(smallest_start_index, largest_start_index)
+            }
         };
 
         // all grains have the same length (for now)
@@ -430,6 +663,23 @@ impl GranularSynthesizer {
             .get(|| calculate_selection_end_in_samples(buffer_len, selection_end))
     }
 
+    /// Current sample value of a grain (before it is advanced to its next frame),
+    /// interpolating between frames when the position is fractional
+    fn grain_sample(buffer: &[f32], envelope: &Envelope, grain: &Grain) -> f32 {
+        let sample_value = buffer[grain.current_frame];
+        // never interpolate toward audio past the end of the grain (or its selection)
+        let next_frame = (grain.current_frame + 1).min(grain.end_frame.saturating_sub(1));
+        let next_sample_value = buffer.get(next_frame).copied().unwrap_or(sample_value);
+        let fraction = (grain.position - grain.current_frame as f64) as f32;
+        let sample_value = linear_interpolate(sample_value, next_sample_value, fraction);
+        let grain_len = grain.len.max(1) as f32;
+        let envelope_percent = ((grain.position - grain.start_frame as f64) as f32) / grain_len;
+        let envelope_i = (envelope_percent * envelope.len() as f32) as usize;
+        let envelope_value = envelope[envelope_i];
+
+        sample_value * envelope_value
+    }
+
     /// Combines current buffer and envelope sample values to calculate a full audio frame
     /// (where each channel gets a single audio output value).
     fn write_frame_data_into_buffer<'a>(
@@ -448,31 +698,12 @@ impl GranularSynthesizer {
             frame_data_buffer.fill(0.0);
         }
 
-        // spread out the grains into a vec with the same number of slots as there are channels
-        let mut grains_as_channels = vec![None; *self.num_channels + 1];
-        for grain in self.fresh_grains.values_mut() {
-            let uid = grain.uid as usize;
-
-            // if the uid exceeds the number of channels, we don't need its output
-            if uid >= grains_as_channels.len() {
-                continue;
-            }
-
-            // safe to store/deref these pointers since they are temporary and unique pointers
-            grains_as_channels[uid] = Some(grain as *mut Grain);
-        }
-
-        frame_data_buffer
-            .iter_mut()
-            .zip(grains_as_channels.into_iter())
-            .for_each(|(channel, grain)| {
-                let Some(grain) = grain else {
-                    *channel = 0.0;
-                    return;
-                };
-
-                let grain = unsafe { &mut *grain };
+        if self.num_grains.is_some() {
+            // every grain is panned across all channels
+            let num_channels = *self.num_channels;
+            let gain = 1.0 / (self.num_grains().max(1) as f32).sqrt();
 
+            for grain in self.fresh_grains.values_mut() {
                 if grain.calculate_exceeds_buffer_selection(
                     selection_start_in_samples,
                     selection_end_in_samples,
@@ -482,28 +713,91 @@ impl GranularSynthesizer {
                 }
 
                 if grain.is_finished {
-                    // mark for moving into the finished or uninitialized hash map later
-                    if finished_grain_uid.is_none() {
-                        finished_grain_uid.replace(grain.uid);
-                    }
-
-                    // output for this grain should be 0
-                    *channel = 0.0;
-                    return;
+                    // moved into the finished or uninitialized hash map below
+                    continue;
                 }
 
-                // get final sample value for the current grain/channel
-                let sample_value = self.buffer[grain.current_frame];
-                let grain_len = grain.len.max(1) as f32;
-                let envelope_percent =
-                    ((grain.current_frame - grain.start_frame) as f32) / grain_len;
-                let envelope_i = (envelope_percent * self.envelope.len() as f32) as usize;
-                let envelope_value = self.envelope[envelope_i];
-
-                *channel = sample_value * envelope_value;
+                let uid = grain.uid as usize;
+                if let Some(grain_gains) = self
+                    .grain_gains
+                    .get(uid * num_channels..(uid + 1) * num_channels)
+                {
+                    let sample_value =
+                        Self::grain_sample(&self.buffer, &self.envelope, grain) * gain;
+                    frame_data_buffer.iter_mut().zip(grain_gains).for_each(
+                        |(channel, channel_gain)| *channel += sample_value * channel_gain,
+                    );
+                }
 
                 grain.next_frame();
+            }
+
+            // move every finished grain into the finished_grains list,
+            // so that grains keep starting at the same rate they finish
+            let finished_grains = &mut self.finished_grains;
+            let uninitialized_grains = &mut self.uninitialized_grains;
+            self.fresh_grains.retain(|&uid, grain| {
+                if !grain.is_finished {
+                    return true;
+                }
+                if grain.exceeds_buffer_selection {
+                    uninitialized_grains.insert(uid, *grain);
+                } else {
+                    finished_grains.insert(uid, *grain);
+                }
+                false
             });
+        } else {
+            // spread out the grains into a vec with the same number of slots as there are channels
+            let mut grains_as_channels = vec![None; *self.num_channels + 1];
+            for grain in self.fresh_grains.values_mut() {
+                let uid = grain.uid as usize;
+
+                // if the uid exceeds the number of channels, we don't need its output
+                if uid >= grains_as_channels.len() {
+                    continue;
+                }
+
+                // safe to store/deref these pointers since they are temporary and unique pointers
+                grains_as_channels[uid] = Some(grain as *mut Grain);
+            }
+
+            frame_data_buffer
+                .iter_mut()
+                .zip(grains_as_channels)
+                .for_each(|(channel, grain)| {
+                    let Some(grain) = grain else {
+                        *channel = 0.0;
+                        return;
+                    };
+
+                    let grain = unsafe { &mut *grain };
+
+                    if grain.calculate_exceeds_buffer_selection(
+                        selection_start_in_samples,
+                        selection_end_in_samples,
+                    ) {
+                        // mark for moving into appropriate hash map later
+                        grain.exceeds_buffer_selection = true;
+                    }
+
+                    if grain.is_finished {
+                        // mark for moving into the finished or uninitialized hash map later
+                        if finished_grain_uid.is_none() {
+                            finished_grain_uid.replace(grain.uid);
+                        }
+
+                        // output for this grain should be 0
+                        *channel = 0.0;
+                        return;
+                    }
+
+                    // get final sample value for the current grain/channel
+                    *channel = Self::grain_sample(&self.buffer, &self.envelope, grain);
+
+                    grain.next_frame();
+                });
+        }
 
         // move a finished grain into the finished_grains list
         // this list gets refreshed more frequently than the
@@ -894,6 +1188,267 @@ mod test_granular_synthesizer {
         }
     }
 
+    #[cfg(test)]
+    mod pitch_shift {
+        use std::{sync::Arc, time::Duration};
+
+        use crate::{
+            granular_synthesizer::GranularSynthesizer,
+            granular_synthesizer::GranularSynthesizerAction, Envelope, GranularSynthesizerGrain,
+        };
+
+        fn ramp_synth() -> GranularSynthesizer {
+            let mut synth = GranularSynthesizer::new();
+            let buffer: Vec<_> = (0..5000).map(|i| i as f32).collect();
+            synth
+                .set_buffer(Arc::new(buffer))
+                .set_envelope(crate::EnvelopeType::All1)
+                .set_grain_initialization_delay(Duration::ZERO);
+            synth
+        }
+
+        #[test]
+        fn it_should_read_grains_faster_when_shifted_up() {
+            let mut synth = ramp_synth();
+            synth.set_pitch_shift(12.0);
+
+            synth.next_frame();
+            let frame_1 = synth.next_frame();
+            let frame_2 = synth.next_frame();
+
+            assert_eq!(frame_1[0] + 2.0, frame_2[0]);
+            assert_eq!(frame_1[1] + 2.0, frame_2[1]);
+        }
+
+        #[test]
+        fn it_should_interpolate_fractional_positions() {
+            let mut synth = ramp_synth();
+            synth.set_pitch_shift(-12.0);
+
+            synth.next_frame();
+            let frame_1 = synth.next_frame();
+            let frame_2 = synth.next_frame();
+
+            assert_eq!(frame_1[0] + 0.5, frame_2[0]);
+        }
+
+        #[test]
+        fn it_should_not_interpolate_past_the_end_of_a_grain() {
+            let buffer = [0.0, 1.0, 2.0, 100.0];
+            let mut grain = GranularSynthesizerGrain::new_with_playback_rate(0, 3, 0, true, 0.5);
+            grain.position = 2.5;
+            grain.current_frame = 2;
+
+            let sample = GranularSynthesizer::grain_sample(&buffer, &Envelope::new_all_1(), &grain);
+            assert_eq!(sample, 2.0);
+        }
+
+        #[test]
+        fn it_should_spread_pitch_randomly_between_grains() {
+            let mut synth = ramp_synth();
+            synth.set_pitch_spread(12.0);
+
+            synth.next_frame();
+            let frame_1 = synth.next_frame();
+            let frame_2 = synth.next_frame();
+
+            let rates: Vec<f32> = frame_1
+                .iter()
+                .zip(frame_2.iter())
+                .map(|(a, b)| b - a)
+                .collect();
+            assert!(rates.iter().all(|rate| (0.5..=2.0).contains(rate)));
+            assert_ne!(rates[0], rates[1]);
+        }
+    }
+
+    #[cfg(test)]
+    mod position_mode {
+        use std::{sync::Arc, time::Duration};
+
+        use crate::{
+            granular_synthesizer::GranularSynthesizer,
+            granular_synthesizer::GranularSynthesizerAction, GrainPositionMode,
+        };
+
+        #[test]
+        fn it_should_start_grains_at_frozen_scan_position() {
+            let mut synth = GranularSynthesizer::new();
+            let buffer: Vec<_> = (0..5000).map(|i| i as f32).collect();
+            synth
+                .set_buffer(Arc::new(buffer))
+                .set_envelope(crate::EnvelopeType::All1)
+                .set_grain_initialization_delay(Duration::ZERO)
+                .set_position_mode(GrainPositionMode::Scan { speed: 0.0 })
+                .set_scan_position(0.5);
+
+            synth.next_frame();
+            let mut frame = synth.next_frame();
+            frame.sort_by(f32::total_cmp);
+
+            // one grain has played 1 frame and the other has played 2 frames
+            assert_eq!(frame, vec![2500.0, 2501.0]);
+        }
+
+        #[test]
+        fn it_should_wrap_scan_position_into_selection_when_set() {
+            let mut synth = GranularSynthesizer::new();
+            synth
+                .set_buffer(Arc::new(vec![0.0; 1000]))
+                .set_selection_start(0.5)
+                .set_selection_end(0.75);
+
+            // 100 frames past the end of the selection
+            synth.set_scan_position(0.85);
+            assert!((synth.scan_position().get() - 0.6).abs() < 0.00001);
+
+            // 100 frames before the start of the selection
+            synth.set_scan_position(0.4);
+            assert!((synth.scan_position().get() - 0.65).abs() < 0.00001);
+        }
+
+        #[test]
+        fn it_should_advance_and_wrap_scan_position_within_selection() {
+            let mut synth = GranularSynthesizer::new();
+            synth
+                .set_buffer(Arc::new(vec![0.0; 1000]))
+                .set_selection_start(0.5)
+                .set_position_mode(GrainPositionMode::Scan { speed: 0.5 })
+                .set_scan_position(0.5);
+
+            for _ in 0..200 {
+                synth.next_frame();
+            }
+            assert!((synth.scan_position().get() - 0.6).abs() < 0.00001);
+
+            // 1000 frames at half speed moves through the whole selection (and back to the start)
+            for _ in 0..1000 {
+                synth.next_frame();
+            }
+            assert!((synth.scan_position().get() - 0.6).abs() < 0.00001);
+        }
+    }
+
+    #[cfg(test)]
+    mod num_grains {
+        use std::{sync::Arc, time::Duration};
+
+        use crate::{
+            granular_synthesizer::GranularSynthesizer,
+            granular_synthesizer::GranularSynthesizerAction,
+        };
+
+        fn constant_synth(num_grains: usize) -> GranularSynthesizer {
+            let mut synth = GranularSynthesizer::new();
+            synth
+                .set_buffer(Arc::new(vec![1.0; 5000]))
+                .set_envelope(crate::EnvelopeType::All1)
+                .set_grain_initialization_delay(Duration::ZERO)
+                .set_num_grains(num_grains);
+            synth
+        }
+
+        #[test]
+        fn it_should_play_one_grain_per_channel_by_default() {
+            let mut synth = GranularSynthesizer::new();
+            synth.set_buffer(Arc::new(vec![0.0; 1024]));
+            assert_eq!(synth.num_grains(), 2);
+
+            synth.set_num_channels(5);
+            synth.next_frame();
+            assert_eq!(synth.num_grains(), 5);
+            assert_eq!(synth.total_num_grains(), 5);
+        }
+
+        #[test]
+        fn it_should_keep_num_grains_independent_of_num_channels() {
+            let mut synth = constant_synth(200);
+
+            assert_eq!(synth.next_frame().len(), 2);
+            assert_eq!(synth.total_num_grains(), 200);
+
+            synth.set_num_channels(4);
+            assert_eq!(synth.next_frame().len(), 4);
+            assert_eq!(synth.total_num_grains(), 200);
+
+            synth.set_num_grains(None);
+            synth.next_frame();
+            assert_eq!(synth.total_num_grains(), 4);
+        }
+
+        #[test]
+        fn it_should_allocate_grains_up_front() {
+            let mut synth = GranularSynthesizer::new_with_max_num_grains(300);
+            synth
+                .set_buffer(Arc::new(vec![1.0; 5000]))
+                .set_grain_initialization_delay(Duration::ZERO);
+            let grain_positions = synth.grain_positions.as_ptr();
+            let grain_gains = synth.grain_gains.as_ptr();
+
+            synth.set_num_grains(200);
+            for _ in 0..1000 {
+                synth.next_frame();
+            }
+            synth.set_num_grains(300);
+            for _ in 0..1000 {
+                synth.next_frame();
+            }
+
+            assert_eq!(synth.total_num_grains(), 300);
+            assert_eq!(synth.grain_positions.as_ptr(), grain_positions);
+            assert_eq!(synth.grain_gains.as_ptr(), grain_gains);
+        }
+
+        #[test]
+        fn it_should_clamp_num_grains_to_max_num_grains() {
+            let mut synth = GranularSynthesizer::new_with_max_num_grains(100);
+            assert_eq!(synth.max_num_grains(), 100);
+
+            synth.set_num_grains(200);
+            assert_eq!(synth.num_grains(), 100);
+        }
+
+        #[test]
+        fn it_should_move_every_finished_grain_out_of_fresh_grains() {
+            let mut synth = constant_synth(200);
+
+            for _ in 0..5000 {
+                synth.next_frame();
+                assert!(synth.fresh_grains.values().all(|grain| !grain.is_finished));
+            }
+        }
+
+        #[test]
+        fn it_should_mix_many_grains_into_few_channels() {
+            let mut synth = constant_synth(200);
+            synth.set_pan_spread(0.0);
+
+            for _ in 0..500 {
+                synth.next_frame();
+            }
+            let frame = synth.next_frame();
+
+            // centered grains are equally loud in both channels,
+            // and the mix is scaled down by 1 / sqrt(num_grains)
+            assert_eq!(frame[0], frame[1]);
+            assert!(frame[0] > 0.0);
+            assert!(frame[0] <= 200.0_f32.sqrt());
+        }
+
+        #[test]
+        fn it_should_spread_grains_between_channels() {
+            let mut synth = constant_synth(200);
+
+            for _ in 0..500 {
+                synth.next_frame();
+            }
+            let frame = synth.next_frame();
+
+            assert!(frame.iter().all(|sample| *sample > 0.0));
+            assert_ne!(frame[0], frame[1]);
+        }
+    }
+
     #[cfg(test)]
     mod grain_initialization_delay {
         use std::{sync::Arc, time::Duration};
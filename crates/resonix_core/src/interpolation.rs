@@ -0,0 +1,147 @@
+/// Determines how samples are read from fractional positions in a buffer
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Interpolation {
+    /// Uses the sample at the closest preceding index
+    Nearest,
+    /// Draws a straight line between the two closest samples
+    #[default]
+    Linear,
+    /// Fits a smooth (Catmull-Rom) curve through the four closest samples
+    Cubic,
+}
+
+impl Interpolation {
+    /// Reads a sample from a fractional `position` in `samples`.
+    ///
+    /// Neighboring samples beyond the edges of the buffer are clamped to
+    /// the first / last sample. Returns 0.0 for an empty buffer.
+    pub fn sample_at(&self, samples: &[f32], position: f64) -> f32 {
+        let last_index = samples.len() as isize - 1;
+        self.sample_with_neighbors(samples, position, |index| index.clamp(0, last_index))
+    }
+
+    /// Reads a sample from a fractional `position` in `samples`, like `sample_at`,
+    /// except that while `position` is inside the loop region `loop_start..loop_end`,
+    /// neighboring samples wrap around within that region (so that looped audio
+    /// interpolates from the end of the loop back to its start).
+    pub fn sample_at_looped(
+        &self,
+        samples: &[f32],
+        position: f64,
+        loop_start: usize,
+        loop_end: usize,
+    ) -> f32 {
+        let loop_start = loop_start as isize;
+        let loop_end = loop_end.min(samples.len()) as isize;
+        let index = position.floor() as isize;
+        if index < loop_start || index >= loop_end {
+            return self.sample_at(samples, position);
+        }
+
+        let loop_len = loop_end - loop_start;
+        self.sample_with_neighbors(samples, position, |index| {
+            loop_start + (index - loop_start).rem_euclid(loop_len)
+        })
+    }
+
+    /// `neighbor_index` maps the indexes of neighboring samples
+    /// (which may be out of bounds) to indexes in `samples`
+    fn sample_with_neighbors(
+        &self,
+        samples: &[f32],
+        position: f64,
+        neighbor_index: impl Fn(isize) -> isize,
+    ) -> f32 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+
+        let index = position.floor() as isize;
+        let fraction = (position - position.floor()) as f32;
+        let sample = |offset: isize| samples[neighbor_index(index + offset) as usize];
+
+        match self {
+            Interpolation::Nearest => sample(0),
+            Interpolation::Linear => linear_interpolate(sample(0), sample(1), fraction),
+            Interpolation::Cubic => {
+                cubic_interpolate(sample(-1), sample(0), sample(1), sample(2), fraction)
+            }
+        }
+    }
+}
+
+/// Interpolates between `a` and `b`, where `t` is between 0.0 and 1.0
+#[inline]
+pub fn linear_interpolate(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Interpolates between `y1` and `y2` using a Catmull-Rom spline,
+/// where `t` is between 0.0 and 1.0, and `y0` and `y3` are the outer neighbors
+#[inline]
+pub fn cubic_interpolate(y0: f32, y1: f32, y2: f32, y3: f32, t: f32) -> f32 {
+    let a = -0.5 * y0 + 1.5 * y1 - 1.5 * y2 + 0.5 * y3;
+    let b = y0 - 2.5 * y1 + 2.0 * y2 - 0.5 * y3;
+    let c = -0.5 * y0 + 0.5 * y2;
+    let d = y1;
+
+    ((a * t + b) * t + c) * t + d
+}
+
+#[cfg(test)]
+mod test_interpolation {
+    use crate::{cubic_interpolate, Interpolation};
+
+    #[test]
+    fn it_should_return_exact_samples_at_integer_positions() {
+        let samples = [0.0, 1.0, 4.0, 9.0];
+        for interpolation in [
+            Interpolation::Nearest,
+            Interpolation::Linear,
+            Interpolation::Cubic,
+        ] {
+            for (i, sample) in samples.iter().enumerate() {
+                assert_eq!(interpolation.sample_at(&samples, i as f64), *sample);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_interpolate_between_samples() {
+        let samples = [0.0, 1.0, 2.0, 3.0];
+
+        assert_eq!(Interpolation::Nearest.sample_at(&samples, 1.5), 1.0);
+        assert_eq!(Interpolation::Linear.sample_at(&samples, 1.5), 1.5);
+        // cubic interpolation reproduces straight lines exactly
+        assert_eq!(Interpolation::Cubic.sample_at(&samples, 1.5), 1.5);
+    }
+
+    #[test]
+    fn it_should_clamp_at_edges() {
+        let samples = [1.0, 2.0];
+        assert_eq!(Interpolation::Linear.sample_at(&samples, 1.5), 2.0);
+        assert_eq!(Interpolation::Linear.sample_at(&[], 0.0), 0.0);
+    }
+
+    #[test]
+    fn it_should_wrap_neighbors_within_loop_region() {
+        let samples = [0.0, 1.0, 2.0, 3.0];
+
+        // the sample after the end of the loop is the start of the loop
+        assert_eq!(
+            Interpolation::Linear.sample_at_looped(&samples, 2.5, 1, 3),
+            1.5
+        );
+        // cubic interpolation wraps both outer neighbors
+        assert_eq!(
+            Interpolation::Cubic.sample_at_looped(&[0.0, 1.0, 4.0, 9.0], 2.5, 1, 3),
+            cubic_interpolate(1.0, 4.0, 1.0, 4.0, 0.5)
+        );
+        // positions outside of the loop region are read like `sample_at`
+        assert_eq!(
+            Interpolation::Linear.sample_at_looped(&samples, 0.5, 1, 3),
+            0.5
+        );
+        assert_eq!(Interpolation::Linear.sample_at_looped(&[], 0.0, 0, 0), 0.0);
+    }
+}
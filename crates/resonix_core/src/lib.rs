@@ -1,16 +1,48 @@
 pub mod amplitude;
+pub mod audio_buffer;
+pub mod convolution;
+#[cfg(feature = "decode")]
+pub mod decode;
+pub mod delays;
 pub mod downmixers;
+pub mod dynamics;
 pub mod envelopes;
+pub mod filters;
 pub mod granular_synthesizer;
+pub mod interpolation;
+pub mod modulation_effects;
+pub mod noise;
+pub mod oscillators;
+pub mod panners;
+pub mod resampling;
+pub mod reverbs;
+pub mod shapers;
 pub mod sine;
+pub mod spectrum;
 pub mod units;
 pub mod utils;
 
 pub use amplitude::*;
+pub use audio_buffer::*;
+pub use convolution::*;
 pub use decibel::*;
+#[cfg(feature = "decode")]
+pub use decode::*;
+pub use delays::*;
 pub use downmixers::*;
+pub use dynamics::*;
 pub use envelopes::*;
+pub use filters::*;
 pub use granular_synthesizer::*;
+pub use interpolation::*;
+pub use modulation_effects::*;
+pub use noise::*;
+pub use oscillators::*;
+pub use panners::*;
+pub use resampling::*;
+pub use reverbs::*;
+pub use shapers::*;
 pub use sine::*;
+pub use spectrum::*;
 pub use units::*;
 pub use utils::*;
@@ -0,0 +1,9 @@
+mod chorus;
+mod flanger;
+mod lfo;
+mod modulated_delay;
+mod phaser;
+
+pub use chorus::*;
+pub use flanger::*;
+pub use phaser::*;
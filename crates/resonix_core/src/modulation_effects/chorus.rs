@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use crate::{modulation_effects::modulated_delay::ModulatedDelay, NumChannels, SampleRate};
+
+/// Thickens a multichannel signal by mixing it with copies of itself that are
+/// delayed by a slowly moving amount, which sounds like several slightly detuned voices.
+///
+/// Each channel's LFO is offset by `channel_phase_offset`, which makes the effect sound wide.
+/// The chorus contains no randomness, so the same input always produces the same output.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Chorus {
+    modulated_delay: ModulatedDelay,
+}
+
+impl Chorus {
+    pub const DEFAULT_RATE: f32 = 0.8;
+    pub const DEFAULT_DEPTH: f32 = 0.5;
+    pub const DEFAULT_FEEDBACK: f32 = 0.0;
+    pub const DEFAULT_MIX: f32 = 0.5;
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(15);
+    pub const DEFAULT_CHANNEL_PHASE_OFFSET: f32 = 0.25;
+
+    /// Memory for this much delay (plus `SWEEP`) is allocated up front
+    pub const MAX_DELAY: Duration = Duration::from_millis(40);
+
+    /// How far the delay is swept above `delay` when `depth` is 1.0
+    pub const SWEEP: Duration = Duration::from_millis(10);
+
+    pub fn new(num_channels: impl Into<NumChannels>, sample_rate: impl Into<SampleRate>) -> Self {
+        let mut modulated_delay = ModulatedDelay::new(
+            num_channels.into(),
+            sample_rate.into(),
+            Self::MAX_DELAY,
+            Self::SWEEP,
+        );
+        modulated_delay.set_rate(Self::DEFAULT_RATE);
+        modulated_delay.set_depth(Self::DEFAULT_DEPTH);
+        modulated_delay.set_feedback(Self::DEFAULT_FEEDBACK);
+        modulated_delay.set_mix(Self::DEFAULT_MIX);
+        modulated_delay.set_delay(Self::DEFAULT_DELAY);
+        modulated_delay.set_channel_phase_offset(Self::DEFAULT_CHANNEL_PHASE_OFFSET);
+        Self { modulated_delay }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        self.modulated_delay.num_channels()
+    }
+
+    /// Frequency of the LFO (in Hz)
+    pub fn rate(&self) -> f32 {
+        self.modulated_delay.rate()
+    }
+
+    pub fn set_rate(&mut self, rate: f32) -> &mut Self {
+        self.modulated_delay.set_rate(rate);
+        self
+    }
+
+    /// How much of `SWEEP` the LFO moves the delay through (from 0.0 to 1.0)
+    pub fn depth(&self) -> f32 {
+        self.modulated_delay.depth()
+    }
+
+    pub fn set_depth(&mut self, depth: f32) -> &mut Self {
+        self.modulated_delay.set_depth(depth);
+        self
+    }
+
+    /// How much of the delayed signal is fed back into the delay
+    pub fn feedback(&self) -> f32 {
+        self.modulated_delay.feedback()
+    }
+
+    /// Clamped between -0.95 and 0.95
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.modulated_delay.set_feedback(feedback);
+        self
+    }
+
+    /// Balance between the original signal (0.0) and the delayed signal (1.0)
+    pub fn mix(&self) -> f32 {
+        self.modulated_delay.mix()
+    }
+
+    pub fn set_mix(&mut self, mix: f32) -> &mut Self {
+        self.modulated_delay.set_mix(mix);
+        self
+    }
+
+    /// Shortest delay, which the LFO sweeps upwards from
+    pub fn delay(&self) -> Duration {
+        self.modulated_delay.delay()
+    }
+
+    /// Clamped to `MAX_DELAY`
+    pub fn set_delay(&mut self, delay: Duration) -> &mut Self {
+        self.modulated_delay.set_delay(delay);
+        self
+    }
+
+    /// How far (in turns) each channel's LFO is ahead of the previous channel's LFO
+    pub fn channel_phase_offset(&self) -> f32 {
+        self.modulated_delay.channel_phase_offset()
+    }
+
+    pub fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) -> &mut Self {
+        self.modulated_delay
+            .set_channel_phase_offset(channel_phase_offset);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.modulated_delay.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.modulated_delay.set_sample_rate(sample_rate.into());
+        self
+    }
+
+    /// Silences the delay and restarts the LFO
+    pub fn clear(&mut self) -> &mut Self {
+        self.modulated_delay.clear();
+        self
+    }
+
+    /// Processes one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        self.modulated_delay.process_frame(frame);
+    }
+}
+
+#[cfg(test)]
+mod test_chorus {
+    use std::time::Duration;
+
+    use crate::Chorus;
+
+    #[test]
+    fn it_should_delay_signal_without_depth() {
+        let mut chorus = Chorus::new(1, 1000);
+        chorus
+            .set_depth(0.0)
+            .set_mix(1.0)
+            .set_delay(Duration::from_millis(3));
+
+        let output: Vec<f32> = [1.0, 0.0, 0.0, 0.0, 0.0]
+            .into_iter()
+            .map(|sample| {
+                let mut frame = [sample];
+                chorus.process_frame(&mut frame);
+                frame[0]
+            })
+            .collect();
+        assert_eq!(output, vec![0.0, 0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn it_should_pass_dry_signal_without_mix() {
+        let mut chorus = Chorus::new(2, 44100);
+        chorus.set_mix(0.0);
+
+        let mut frame = [0.5, -0.25];
+        chorus.process_frame(&mut frame);
+        assert_eq!(frame, [0.5, -0.25]);
+    }
+
+    #[test]
+    fn it_should_offset_lfo_for_each_channel() {
+        let mut chorus = Chorus::new(2, 1000);
+        chorus.set_mix(1.0).set_depth(1.0).set_rate(10.0);
+
+        let mut channels_differ = false;
+        for i in 0..200 {
+            let sample = (i as f32 * 0.3).sin();
+            let mut frame = [sample, sample];
+            chorus.process_frame(&mut frame);
+            channels_differ |= (frame[0] - frame[1]).abs() > 0.01;
+        }
+        assert!(channels_differ);
+
+        // with no offset, every channel is identical
+        chorus.clear().set_channel_phase_offset(0.0);
+        for i in 0..200 {
+            let sample = (i as f32 * 0.3).sin();
+            let mut frame = [sample, sample];
+            chorus.process_frame(&mut frame);
+            assert_eq!(frame[0], frame[1]);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::f32::consts::TAU;
+
+use crate::{PhaseAccumulator, SampleRate};
+
+/// A sine low frequency oscillator shared by every channel of a modulation effect,
+/// where each successive channel is shifted by `channel_phase_offset` (in turns)
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct Lfo {
+    phase_accumulator: PhaseAccumulator,
+    channel_phase_offset: f32,
+}
+
+impl Lfo {
+    pub(crate) fn new(sample_rate: SampleRate, rate: f32, channel_phase_offset: f32) -> Self {
+        Self {
+            phase_accumulator: PhaseAccumulator::new(sample_rate, rate),
+            channel_phase_offset,
+        }
+    }
+
+    pub(crate) fn rate(&self) -> f32 {
+        self.phase_accumulator.frequency()
+    }
+
+    pub(crate) fn set_rate(&mut self, rate: f32) {
+        self.phase_accumulator.set_frequency(rate.max(0.0));
+    }
+
+    pub(crate) fn channel_phase_offset(&self) -> f32 {
+        self.channel_phase_offset
+    }
+
+    pub(crate) fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) {
+        self.channel_phase_offset = channel_phase_offset;
+    }
+
+    pub(crate) fn sample_rate(&self) -> SampleRate {
+        self.phase_accumulator.sample_rate()
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: SampleRate) {
+        self.phase_accumulator.set_sample_rate(sample_rate);
+    }
+
+    /// Restarts the LFO at the beginning of its cycle
+    pub(crate) fn reset(&mut self) {
+        self.phase_accumulator.set_phase(0.0);
+    }
+
+    /// The current value (from 0.0 to 1.0) for the given channel.
+    /// The first channel starts its cycle at 0.0.
+    #[inline]
+    pub(crate) fn value(&self, channel: usize) -> f32 {
+        let phase = self.phase_accumulator.phase() + channel as f32 * self.channel_phase_offset;
+        0.5 - 0.5 * (TAU * phase).cos()
+    }
+
+    #[inline]
+    pub(crate) fn advance(&mut self) {
+        self.phase_accumulator.advance();
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use crate::{modulation_effects::lfo::Lfo, DelayLine, Interpolation, NumChannels, SampleRate};
+
+/// Memory for modulated delays is allocated up front for this sample rate.
+/// At higher sample rates, the longest possible delay is shortened proportionally.
+pub(crate) const MAX_SAMPLE_RATE: u32 = 96000;
+
+/// Feedback is clamped to this (positive or negative) amount to keep the effect stable
+pub(crate) const MAX_FEEDBACK: f32 = 0.95;
+
+/// A delay whose length is swept by an `Lfo`, which is the basis of both `Chorus` and `Flanger`
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct ModulatedDelay {
+    lfo: Lfo,
+    delay: Duration,
+    max_delay: Duration,
+    /// How far the delay moves when `depth` is 1.0
+    sweep: Duration,
+    depth: f32,
+    feedback: f32,
+    mix: f32,
+    /// One delay line for every channel
+    delay_lines: Vec<DelayLine>,
+}
+
+impl ModulatedDelay {
+    pub(crate) fn new(
+        num_channels: NumChannels,
+        sample_rate: SampleRate,
+        max_delay: Duration,
+        sweep: Duration,
+    ) -> Self {
+        let max_delay_samples =
+            ((max_delay + sweep).as_secs_f64() * MAX_SAMPLE_RATE as f64).ceil() as usize;
+        Self {
+            lfo: Lfo::new(sample_rate, 0.0, 0.0),
+            delay: Duration::ZERO,
+            max_delay,
+            sweep,
+            depth: 0.0,
+            feedback: 0.0,
+            mix: 0.0,
+            delay_lines: vec![DelayLine::new(max_delay_samples); *num_channels],
+        }
+    }
+
+    pub(crate) fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.delay_lines.len())
+    }
+
+    pub(crate) fn rate(&self) -> f32 {
+        self.lfo.rate()
+    }
+
+    pub(crate) fn set_rate(&mut self, rate: f32) {
+        self.lfo.set_rate(rate);
+    }
+
+    pub(crate) fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    pub(crate) fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.clamp(0.0, 1.0);
+    }
+
+    pub(crate) fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    pub(crate) fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+    }
+
+    pub(crate) fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    pub(crate) fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    pub(crate) fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub(crate) fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay.min(self.max_delay);
+    }
+
+    pub(crate) fn channel_phase_offset(&self) -> f32 {
+        self.lfo.channel_phase_offset()
+    }
+
+    pub(crate) fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) {
+        self.lfo.set_channel_phase_offset(channel_phase_offset);
+    }
+
+    pub(crate) fn sample_rate(&self) -> SampleRate {
+        self.lfo.sample_rate()
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: SampleRate) {
+        self.lfo.set_sample_rate(sample_rate);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.lfo.reset();
+        self.delay_lines.iter_mut().for_each(DelayLine::clear);
+    }
+
+    #[inline]
+    pub(crate) fn process_frame(&mut self, frame: &mut [f32]) {
+        let sample_rate = self.lfo.sample_rate().get() as f32;
+        let delay = self.delay.as_secs_f32() * sample_rate;
+        let sweep = self.sweep.as_secs_f32() * sample_rate * self.depth;
+
+        frame
+            .iter_mut()
+            .zip(self.delay_lines.iter_mut())
+            .enumerate()
+            .for_each(|(channel, (sample, delay_line))| {
+                let delay = delay + sweep * self.lfo.value(channel);
+                let delayed = delay_line.read(delay, Interpolation::Cubic);
+                delay_line.write(*sample + delayed * self.feedback);
+                *sample = *sample * (1.0 - self.mix) + delayed * self.mix;
+            });
+
+        self.lfo.advance();
+    }
+}
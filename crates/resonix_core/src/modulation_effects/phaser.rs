@@ -0,0 +1,276 @@
+use std::f32::consts::PI;
+
+use crate::{
+    modulation_effects::{lfo::Lfo, modulated_delay::MAX_FEEDBACK},
+    NumChannels, SampleRate,
+};
+
+/// A first-order all-pass filter, which shifts the phase of frequencies around
+/// its break frequency without changing their amplitude
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+struct AllPassStage {
+    state: f32,
+}
+
+impl AllPassStage {
+    #[inline]
+    fn process(&mut self, input: f32, coefficient: f32) -> f32 {
+        let output = coefficient * input + self.state;
+        self.state = input - coefficient * output;
+        output
+    }
+}
+
+/// Sweeps a series of notches up and down the spectrum of a multichannel signal by mixing
+/// it with a copy of itself that has passed through a chain of swept all-pass filters.
+///
+/// Every 2 stages add one notch. Feedback makes the notches (and the peaks between them) more pronounced.
+///
+/// Each channel's LFO is offset by `channel_phase_offset`, which makes the effect sound wide.
+/// The phaser contains no randomness, so the same input always produces the same output.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Phaser {
+    lfo: Lfo,
+    depth: f32,
+    feedback: f32,
+    mix: f32,
+    min_frequency: f32,
+    max_frequency: f32,
+    num_stages: usize,
+    /// `MAX_STAGES` all-pass stages for every channel (only the first `num_stages` are used)
+    stages: Vec<[AllPassStage; Phaser::MAX_STAGES]>,
+    /// The most recent output of each channel's all-pass chain
+    feedback_samples: Vec<f32>,
+}
+
+impl Phaser {
+    pub const DEFAULT_RATE: f32 = 0.5;
+    pub const DEFAULT_DEPTH: f32 = 1.0;
+    pub const DEFAULT_FEEDBACK: f32 = 0.5;
+    pub const DEFAULT_MIX: f32 = 0.5;
+    pub const DEFAULT_MIN_FREQUENCY: f32 = 200.0;
+    pub const DEFAULT_MAX_FREQUENCY: f32 = 2000.0;
+    pub const DEFAULT_NUM_STAGES: usize = 4;
+    pub const DEFAULT_CHANNEL_PHASE_OFFSET: f32 = 0.25;
+
+    pub const MAX_STAGES: usize = 12;
+
+    pub fn new(num_channels: impl Into<NumChannels>, sample_rate: impl Into<SampleRate>) -> Self {
+        let num_channels = *num_channels.into();
+        Self {
+            lfo: Lfo::new(
+                sample_rate.into(),
+                Self::DEFAULT_RATE,
+                Self::DEFAULT_CHANNEL_PHASE_OFFSET,
+            ),
+            depth: Self::DEFAULT_DEPTH,
+            feedback: Self::DEFAULT_FEEDBACK,
+            mix: Self::DEFAULT_MIX,
+            min_frequency: Self::DEFAULT_MIN_FREQUENCY,
+            max_frequency: Self::DEFAULT_MAX_FREQUENCY,
+            num_stages: Self::DEFAULT_NUM_STAGES,
+            stages: vec![[AllPassStage::default(); Self::MAX_STAGES]; num_channels],
+            feedback_samples: vec![0.0; num_channels],
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.stages.len())
+    }
+
+    /// Frequency of the LFO (in Hz)
+    pub fn rate(&self) -> f32 {
+        self.lfo.rate()
+    }
+
+    pub fn set_rate(&mut self, rate: f32) -> &mut Self {
+        self.lfo.set_rate(rate);
+        self
+    }
+
+    /// How much of the range between `min_frequency` and `max_frequency`
+    /// the LFO sweeps through (from 0.0 to 1.0)
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    pub fn set_depth(&mut self, depth: f32) -> &mut Self {
+        self.depth = depth.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How much of the all-pass output is fed back into the all-pass chain
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Clamped between -0.95 and 0.95
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+        self
+    }
+
+    /// Balance between the original signal (0.0) and the phase-shifted signal (1.0).
+    /// The notches are deepest at 0.5.
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    pub fn set_mix(&mut self, mix: f32) -> &mut Self {
+        self.mix = mix.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Lowest break frequency (in Hz) of the all-pass filters
+    pub fn min_frequency(&self) -> f32 {
+        self.min_frequency
+    }
+
+    pub fn set_min_frequency(&mut self, min_frequency: f32) -> &mut Self {
+        self.min_frequency = min_frequency.max(1.0);
+        self
+    }
+
+    /// Highest break frequency (in Hz) of the all-pass filters
+    pub fn max_frequency(&self) -> f32 {
+        self.max_frequency
+    }
+
+    pub fn set_max_frequency(&mut self, max_frequency: f32) -> &mut Self {
+        self.max_frequency = max_frequency.max(1.0);
+        self
+    }
+
+    pub fn num_stages(&self) -> usize {
+        self.num_stages
+    }
+
+    /// Clamped between 1 and `MAX_STAGES`
+    pub fn set_num_stages(&mut self, num_stages: usize) -> &mut Self {
+        self.num_stages = num_stages.clamp(1, Self::MAX_STAGES);
+        self
+    }
+
+    /// How far (in turns) each channel's LFO is ahead of the previous channel's LFO
+    pub fn channel_phase_offset(&self) -> f32 {
+        self.lfo.channel_phase_offset()
+    }
+
+    pub fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) -> &mut Self {
+        self.lfo.set_channel_phase_offset(channel_phase_offset);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.lfo.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.lfo.set_sample_rate(sample_rate.into());
+        self
+    }
+
+    /// Silences the all-pass filters and restarts the LFO
+    pub fn clear(&mut self) -> &mut Self {
+        self.lfo.reset();
+        self.stages
+            .iter_mut()
+            .for_each(|stages| stages.fill(AllPassStage::default()));
+        self.feedback_samples.fill(0.0);
+        self
+    }
+
+    /// Processes one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        let sample_rate = self.lfo.sample_rate().get() as f32;
+        // keep break frequencies safely below the Nyquist frequency
+        let nyquist_limit = sample_rate * 0.49;
+        let frequency_ratio = self.max_frequency / self.min_frequency;
+
+        frame
+            .iter_mut()
+            .zip(self.stages.iter_mut())
+            .zip(self.feedback_samples.iter_mut())
+            .enumerate()
+            .for_each(|(channel, ((sample, stages), feedback_sample))| {
+                // sweep exponentially, so that the notches move evenly in pitch
+                let frequency = (self.min_frequency
+                    * frequency_ratio.powf(self.depth * self.lfo.value(channel)))
+                .min(nyquist_limit);
+                let tan = (PI * frequency / sample_rate).tan();
+                let coefficient = (tan - 1.0) / (tan + 1.0);
+
+                let wet = stages[..self.num_stages].iter_mut().fold(
+                    *sample + *feedback_sample * self.feedback,
+                    |stage_sample, stage| stage.process(stage_sample, coefficient),
+                );
+                *feedback_sample = wet;
+                *sample = *sample * (1.0 - self.mix) + wet * self.mix;
+            });
+
+        self.lfo.advance();
+    }
+}
+
+#[cfg(test)]
+mod test_phaser {
+    use std::f32::consts::{PI, TAU};
+
+    use crate::Phaser;
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    /// Peak amplitude of a phased sine after the phaser has settled
+    fn sine_amplitude(phaser: &mut Phaser, frequency: f32) -> f32 {
+        (0..4410)
+            .map(|i| {
+                let mut frame = [(TAU * frequency * i as f32 / SAMPLE_RATE).sin()];
+                phaser.process_frame(&mut frame);
+                frame[0]
+            })
+            .skip(2205)
+            .fold(0.0, |peak, sample| sample.abs().max(peak))
+    }
+
+    #[test]
+    fn it_should_pass_all_frequencies_through_all_pass_chain() {
+        let mut phaser = Phaser::new(1, SAMPLE_RATE as u32);
+        phaser.set_mix(1.0).set_feedback(0.0);
+
+        for frequency in [100.0, 1000.0, 5000.0] {
+            let amplitude = sine_amplitude(&mut phaser, frequency);
+            assert!((amplitude - 1.0).abs() < 0.01, "{frequency}: {amplitude}");
+        }
+    }
+
+    #[test]
+    fn it_should_notch_where_phase_is_inverted() {
+        let mut phaser = Phaser::new(1, SAMPLE_RATE as u32);
+        phaser
+            .set_depth(0.0)
+            .set_feedback(0.0)
+            .set_min_frequency(1000.0);
+
+        // each of the 4 stages shifts this frequency by 45 degrees, for a total of 180 degrees
+        let notch_frequency = 1000.0 * (PI / 8.0).tan();
+        assert!(sine_amplitude(&mut phaser, notch_frequency) < 0.01);
+        assert!(sine_amplitude(&mut phaser, 5000.0) > 0.5);
+    }
+
+    #[test]
+    fn it_should_produce_the_same_output_every_time() {
+        let render = || {
+            let mut phaser = Phaser::new(2, SAMPLE_RATE as u32);
+            phaser.set_rate(5.0);
+            (0..1000)
+                .flat_map(|i| {
+                    let mut frame = [(i as f32 * 0.1).sin(), (i as f32 * 0.2).sin()];
+                    phaser.process_frame(&mut frame);
+                    frame
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        assert_eq!(render(), render());
+    }
+}
@@ -0,0 +1,5 @@
+mod noise_color;
+mod noise_struct;
+
+pub use noise_color::*;
+pub use noise_struct::*;
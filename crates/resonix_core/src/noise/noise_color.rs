@@ -0,0 +1,11 @@
+/// The spectral balance of generated noise
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum NoiseColor {
+    /// Equal energy at every frequency
+    #[default]
+    White,
+    /// Energy falls by 3dB per octave (equal energy in every octave)
+    Pink,
+    /// Energy falls by 6dB per octave (a random walk)
+    Brown,
+}
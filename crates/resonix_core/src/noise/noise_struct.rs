@@ -0,0 +1,169 @@
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+use crate::NoiseColor;
+
+/// Seed accepted by `Noise::from_seed`
+pub type NoiseSeed = <SmallRng as SeedableRng>::Seed;
+
+/// Generates white, pink, or brown noise between -1.0 and 1.0.
+///
+/// Noise created with `from_seed` always produces the same sequence of samples,
+/// which is useful for snapshot testing.
+#[derive(Debug, Clone)]
+pub struct Noise {
+    color: NoiseColor,
+    rng: SmallRng,
+    /// Number of samples generated so far (used to pick which pink noise row to update)
+    counter: u32,
+    /// Random values that are each updated half as often as the row before (Voss-McCartney)
+    pink_rows: [f32; Noise::NUM_PINK_ROWS],
+    pink_sum: f32,
+    /// Current position of the random walk
+    brown_value: f32,
+}
+
+impl Noise {
+    const NUM_PINK_ROWS: usize = 16;
+
+    /// How far each brown noise step can move
+    const BROWN_STEP_SIZE: f32 = 0.02;
+
+    /// Gently pulls brown noise back towards 0.0, so that it never drifts out of range
+    const BROWN_LEAK: f32 = 0.998;
+
+    /// Brings brown noise back up to a similar loudness as the other colors
+    const BROWN_GAIN: f32 = 3.5;
+
+    /// Creates a noise generator with a random seed
+    pub fn new(color: NoiseColor) -> Self {
+        Self::from_rng(color, SmallRng::from_entropy())
+    }
+
+    /// Creates a noise generator that always produces the same samples for the same seed
+    pub fn from_seed(color: NoiseColor, seed: NoiseSeed) -> Self {
+        Self::from_rng(color, SmallRng::from_seed(seed))
+    }
+
+    pub fn from_rng(color: NoiseColor, mut rng: SmallRng) -> Self {
+        let pink_rows = [(); Self::NUM_PINK_ROWS].map(|_| rng.gen_range(-1.0..1.0));
+        Self {
+            color,
+            counter: 0,
+            pink_sum: pink_rows.iter().sum(),
+            pink_rows,
+            brown_value: 0.0,
+            rng,
+        }
+    }
+
+    /// Creates a new generator of the same color, seeded from this generator.
+    ///
+    /// The new generator's samples are unrelated to this generator's samples,
+    /// but are still reproducible when this generator was seeded.
+    pub fn split(&mut self) -> Self {
+        let rng = SmallRng::from_rng(&mut self.rng).expect("SmallRng should never fail to seed");
+        Self::from_rng(self.color, rng)
+    }
+
+    pub fn color(&self) -> NoiseColor {
+        self.color
+    }
+
+    pub fn set_color(&mut self, color: NoiseColor) -> &mut Self {
+        self.color = color;
+        self
+    }
+
+    pub fn next_sample(&mut self) -> f32 {
+        match self.color {
+            NoiseColor::White => self.next_white_sample(),
+            NoiseColor::Pink => self.next_pink_sample(),
+            NoiseColor::Brown => self.next_brown_sample(),
+        }
+    }
+
+    fn next_white_sample(&mut self) -> f32 {
+        self.rng.gen_range(-1.0..1.0)
+    }
+
+    /// Voss-McCartney algorithm: sums several random values that are each held
+    /// for twice as long as the one before, plus a fresh white noise sample
+    fn next_pink_sample(&mut self) -> f32 {
+        self.counter = self.counter.wrapping_add(1);
+        let row = (self.counter.trailing_zeros() as usize).min(Self::NUM_PINK_ROWS - 1);
+
+        let new_value = self.next_white_sample();
+        self.pink_sum += new_value - self.pink_rows[row];
+        self.pink_rows[row] = new_value;
+
+        (self.pink_sum + self.next_white_sample()) / (Self::NUM_PINK_ROWS + 1) as f32
+    }
+
+    fn next_brown_sample(&mut self) -> f32 {
+        let step = self.next_white_sample() * Self::BROWN_STEP_SIZE;
+        self.brown_value = (self.brown_value + step) * Self::BROWN_LEAK;
+
+        (self.brown_value * Self::BROWN_GAIN).clamp(-1.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test_noise {
+    use crate::{Noise, NoiseColor, SpectrumAnalyser};
+
+    /// Ratio of low-frequency energy to high-frequency energy
+    fn spectral_tilt(color: NoiseColor) -> f32 {
+        let mut noise = Noise::from_seed(color, [1; 32]);
+        let mut analyser = SpectrumAnalyser::new(1024).unwrap();
+        analyser.set_smoothing_time_constant(0.9).unwrap();
+
+        for _ in 0..200 {
+            let samples: Vec<f32> = (0..1024).map(|_| noise.next_sample()).collect();
+            analyser.analyse(&samples);
+        }
+
+        let magnitudes = analyser.magnitudes();
+        let low: f32 = magnitudes[4..32].iter().sum();
+        let high: f32 = magnitudes[256..284].iter().sum();
+        low / high
+    }
+
+    #[test]
+    fn it_should_be_deterministic_when_seeded() {
+        for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+            let mut a = Noise::from_seed(color, [7; 32]);
+            let mut b = Noise::from_seed(color, [7; 32]);
+            let mut c = Noise::from_seed(color, [8; 32]);
+
+            let a: Vec<f32> = (0..100).map(|_| a.next_sample()).collect();
+            let b: Vec<f32> = (0..100).map(|_| b.next_sample()).collect();
+            let c: Vec<f32> = (0..100).map(|_| c.next_sample()).collect();
+            assert_eq!(a, b);
+            assert_ne!(a, c);
+        }
+    }
+
+    #[test]
+    fn it_should_stay_within_range() {
+        for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+            let mut noise = Noise::from_seed(color, [0; 32]);
+            for _ in 0..100_000 {
+                let sample = noise.next_sample();
+                assert!((-1.0..=1.0).contains(&sample));
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_have_expected_spectral_tilt() {
+        let white = spectral_tilt(NoiseColor::White);
+        let pink = spectral_tilt(NoiseColor::Pink);
+        let brown = spectral_tilt(NoiseColor::Brown);
+
+        // white noise is flat
+        assert!((0.8..1.25).contains(&white));
+        // pink and brown noise get progressively darker
+        assert!(pink > 2.0 * white);
+        assert!(brown > 2.0 * pink);
+    }
+}
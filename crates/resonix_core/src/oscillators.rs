@@ -0,0 +1,17 @@
+mod oscillator_interface;
+mod phase_accumulator;
+mod poly_blep;
+mod pulse;
+mod sawtooth;
+mod triangle;
+mod wavetable;
+mod wavetable_oscillator;
+
+pub use oscillator_interface::*;
+pub(crate) use phase_accumulator::*;
+pub use poly_blep::*;
+pub use pulse::*;
+pub use sawtooth::*;
+pub use triangle::*;
+pub use wavetable::*;
+pub use wavetable_oscillator::*;
@@ -0,0 +1,16 @@
+use crate::SineInterface;
+
+/// Shared interface for all periodic oscillators (sine, sawtooth, pulse, etc.)
+pub trait OscillatorInterface: SineInterface {
+    /// Current position within the waveform's cycle, between 0.0 (inclusive) and 1.0 (exclusive)
+    fn phase(&self) -> f32;
+
+    /// Jumps to a position within the waveform's cycle, where 0.0 is the
+    /// beginning of the cycle and 1.0 is the end. Values outside this range wrap around.
+    fn set_phase(&mut self, phase: f32) -> &mut Self;
+
+    /// Restarts the waveform from the beginning of its cycle
+    fn reset_phase(&mut self) -> &mut Self {
+        self.set_phase(0.0)
+    }
+}
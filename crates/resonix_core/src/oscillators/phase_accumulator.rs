@@ -0,0 +1,66 @@
+use crate::SampleRate;
+
+/// Tracks an oscillator's position within its cycle (from 0.0 to 1.0)
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub(crate) struct PhaseAccumulator {
+    sample_rate: SampleRate,
+    frequency: f32,
+    phase: f32,
+    /// How far the phase moves every sample
+    phase_increment: f32,
+}
+
+impl PhaseAccumulator {
+    pub(crate) fn new(sample_rate: impl Into<SampleRate>, frequency: f32) -> Self {
+        let sample_rate = sample_rate.into();
+        Self {
+            sample_rate,
+            frequency,
+            phase: 0.0,
+            phase_increment: Self::calculate_phase_increment(frequency, sample_rate),
+        }
+    }
+
+    pub(crate) fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) {
+        self.sample_rate = sample_rate.into();
+        self.phase_increment = Self::calculate_phase_increment(self.frequency, self.sample_rate);
+    }
+
+    pub(crate) fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    pub(crate) fn set_frequency(&mut self, frequency: f32) {
+        self.frequency = frequency;
+        self.phase_increment = Self::calculate_phase_increment(self.frequency, self.sample_rate);
+    }
+
+    pub(crate) fn phase(&self) -> f32 {
+        self.phase
+    }
+
+    pub(crate) fn set_phase(&mut self, phase: f32) {
+        self.phase = phase.rem_euclid(1.0);
+    }
+
+    pub(crate) fn phase_increment(&self) -> f32 {
+        self.phase_increment
+    }
+
+    /// Moves the phase forward by one sample
+    pub(crate) fn advance(&mut self) {
+        self.phase = (self.phase + self.phase_increment).rem_euclid(1.0);
+    }
+
+    fn calculate_phase_increment(frequency: f32, sample_rate: SampleRate) -> f32 {
+        if sample_rate.get() == 0 {
+            return 0.0;
+        }
+
+        frequency / sample_rate.get() as f32
+    }
+}
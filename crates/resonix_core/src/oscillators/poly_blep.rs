@@ -0,0 +1,72 @@
+/// Polynomial band-limited step (PolyBLEP): the residual for an upward step of height 1.0 at phase 0.0.
+///
+/// Naive waveforms that jump suddenly (e.g. sawtooth, pulse) contain harmonics far
+/// above the Nyquist frequency, which alias back down as inharmonic tones. Adding this
+/// residual to the samples on either side of each jump removes most of that aliasing.
+///
+/// `phase` is the oscillator's current phase (0.0 to 1.0), and `phase_increment`
+/// is how far the phase moves every sample.
+#[inline]
+pub fn poly_blep(phase: f32, phase_increment: f32) -> f32 {
+    let phase_increment = phase_increment.abs();
+    if phase_increment == 0.0 {
+        return 0.0;
+    }
+
+    if phase < phase_increment {
+        // just after the step
+        let t = phase / phase_increment;
+        -(1.0 - t) * (1.0 - t) * 0.5
+    } else if phase > 1.0 - phase_increment {
+        // just before the step
+        let t = (1.0 - phase) / phase_increment;
+        (1.0 - t) * (1.0 - t) * 0.5
+    } else {
+        0.0
+    }
+}
+
+/// Polynomial band-limited ramp (PolyBLAMP): the residual for a change in slope
+/// of 1.0 per sample at phase 0.0, used to smooth sharp corners (e.g. in a triangle wave).
+///
+/// `phase` is the oscillator's current phase (0.0 to 1.0), and `phase_increment`
+/// is how far the phase moves every sample.
+#[inline]
+pub fn poly_blamp(phase: f32, phase_increment: f32) -> f32 {
+    let phase_increment = phase_increment.abs();
+    if phase_increment == 0.0 {
+        return 0.0;
+    }
+
+    let distance = if phase < 0.5 { phase } else { 1.0 - phase } / phase_increment;
+    if distance < 1.0 {
+        let t = 1.0 - distance;
+        t * t * t / 6.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test_poly_blep {
+    use crate::{poly_blamp, poly_blep};
+
+    #[test]
+    fn residuals_should_only_affect_samples_near_discontinuity() {
+        assert_eq!(poly_blep(0.5, 0.01), 0.0);
+        assert_eq!(poly_blamp(0.5, 0.01), 0.0);
+        assert_ne!(poly_blep(0.005, 0.01), 0.0);
+        assert_ne!(poly_blep(0.995, 0.01), 0.0);
+        assert_ne!(poly_blamp(0.995, 0.01), 0.0);
+    }
+
+    #[test]
+    fn step_residual_should_be_continuous_across_discontinuity() {
+        // the naive step jumps by 1.0, so the corrected signal shouldn't jump at all
+        let before = 1.0 - 0.000_001;
+        let after = 0.000_001;
+        let corrected_before = 0.0 + poly_blep(before, 0.1);
+        let corrected_after = 1.0 + poly_blep(after, 0.1);
+        assert!((corrected_before - corrected_after).abs() < 0.001);
+    }
+}
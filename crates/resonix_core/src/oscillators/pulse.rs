@@ -0,0 +1,161 @@
+use crate::{poly_blep, OscillatorInterface, PhaseAccumulator, SampleRate, SineInterface};
+
+/// Produces a band-limited pulse wave at the given frequency and sample rate
+///
+/// The wave is 1.0 for the first `pulse_width` of each cycle and -1.0 for the rest,
+/// so a `pulse_width` of 0.5 (the default) produces a square wave.
+/// Each jump is smoothed with PolyBLEP to prevent aliasing.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub struct Pulse {
+    phase_accumulator: PhaseAccumulator,
+    pulse_width: f32,
+}
+
+impl Pulse {
+    pub const DEFAULT_PULSE_WIDTH: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self::new_with_config(1u32, 0.0)
+    }
+
+    pub fn new_with_config(sample_rate: impl Into<SampleRate>, frequency: impl Into<f32>) -> Self {
+        Self {
+            phase_accumulator: PhaseAccumulator::new(sample_rate, frequency.into()),
+            pulse_width: Self::DEFAULT_PULSE_WIDTH,
+        }
+    }
+
+    pub fn pulse_width(&self) -> f32 {
+        self.pulse_width
+    }
+
+    /// Portion of each cycle (from 0.0 to 1.0) spent at 1.0 rather than -1.0
+    pub fn set_pulse_width(&mut self, pulse_width: f32) -> &mut Self {
+        self.pulse_width = pulse_width.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Default for Pulse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SineInterface for Pulse {
+    fn next_sample(&mut self) -> f32 {
+        if self.phase_accumulator.sample_rate().get() == 0 {
+            return 0.0;
+        }
+
+        let phase = self.phase_accumulator.phase();
+        let phase_increment = self.phase_accumulator.phase_increment();
+        let naive_sample = if phase < self.pulse_width { 1.0 } else { -1.0 };
+        // jumps up by 2.0 at the start of the cycle and down by 2.0 at the pulse width
+        let sample = naive_sample + 2.0 * poly_blep(phase, phase_increment)
+            - 2.0 * poly_blep((phase - self.pulse_width).rem_euclid(1.0), phase_increment);
+
+        self.phase_accumulator.advance();
+
+        sample
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.phase_accumulator.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.phase_accumulator.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.phase_accumulator.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.phase_accumulator.frequency()
+    }
+}
+
+impl OscillatorInterface for Pulse {
+    fn phase(&self) -> f32 {
+        self.phase_accumulator.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.phase_accumulator.set_phase(phase);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_pulse {
+    use std::f32::consts::TAU;
+
+    use crate::{Pulse, SineInterface, SpectrumAnalyser, WindowFunction};
+
+    /// Sums the spectrum's magnitude everywhere except near the harmonics of `frequency`
+    fn aliased_magnitude(samples: &[f32], frequency: f32, sample_rate: f32) -> f32 {
+        let mut analyser = SpectrumAnalyser::new(samples.len()).unwrap();
+        analyser
+            .set_window_function(WindowFunction::Blackman)
+            .set_smoothing_time_constant(0.0)
+            .unwrap();
+        let bin_width = sample_rate / samples.len() as f32;
+
+        analyser
+            .analyse(samples)
+            .iter()
+            .enumerate()
+            .filter(|(bin, _)| {
+                let harmonic = *bin as f32 * bin_width / frequency;
+                (harmonic - harmonic.round()).abs() * frequency > 4.0 * bin_width
+            })
+            .map(|(_, magnitude)| magnitude)
+            .sum()
+    }
+
+    #[test]
+    fn it_should_produce_square_wave_by_default() {
+        let mut pulse = Pulse::new_with_config(100u32, 1.0);
+
+        let samples: Vec<f32> = (0..100).map(|_| pulse.next_sample()).collect();
+        assert_eq!(samples[10], 1.0);
+        assert_eq!(samples[40], 1.0);
+        assert_eq!(samples[60], -1.0);
+        assert_eq!(samples[90], -1.0);
+        assert_eq!(samples.iter().sum::<f32>().round(), 0.0);
+    }
+
+    #[test]
+    fn it_should_respect_pulse_width() {
+        let mut pulse = Pulse::new_with_config(100u32, 1.0);
+        pulse.set_pulse_width(0.25);
+
+        let samples: Vec<f32> = (0..100).map(|_| pulse.next_sample()).collect();
+        assert_eq!(samples[20], 1.0);
+        assert_eq!(samples[30], -1.0);
+
+        pulse.set_pulse_width(2.0);
+        assert_eq!(pulse.pulse_width(), 1.0);
+    }
+
+    #[test]
+    fn it_should_alias_less_than_naive_pulse_wave() {
+        const SAMPLE_RATE: f32 = 44100.0;
+        const FREQUENCY: f32 = 4321.0;
+        let mut pulse = Pulse::new_with_config(SAMPLE_RATE as u32, FREQUENCY);
+
+        let band_limited: Vec<f32> = (0..4096).map(|_| pulse.next_sample()).collect();
+        let naive: Vec<f32> = (0..4096)
+            .map(|i| (TAU * FREQUENCY * i as f32 / SAMPLE_RATE).sin().signum())
+            .collect();
+
+        assert!(
+            aliased_magnitude(&band_limited, FREQUENCY, SAMPLE_RATE)
+                < aliased_magnitude(&naive, FREQUENCY, SAMPLE_RATE) * 0.5
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{poly_blep, OscillatorInterface, PhaseAccumulator, SampleRate, SineInterface};
+
+/// Produces a band-limited sawtooth wave at the given frequency and sample rate
+///
+/// Like `Sine`, the wave starts at 0.0 and rises to 1.0 before jumping down to -1.0
+/// halfway through each cycle. The jump is smoothed with PolyBLEP to prevent aliasing.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct Sawtooth {
+    phase_accumulator: PhaseAccumulator,
+}
+
+impl Sawtooth {
+    pub fn new() -> Self {
+        Self::new_with_config(1u32, 0.0)
+    }
+
+    pub fn new_with_config(sample_rate: impl Into<SampleRate>, frequency: impl Into<f32>) -> Self {
+        Self {
+            phase_accumulator: PhaseAccumulator::new(sample_rate, frequency.into()),
+        }
+    }
+}
+
+impl SineInterface for Sawtooth {
+    fn next_sample(&mut self) -> f32 {
+        if self.phase_accumulator.sample_rate().get() == 0 {
+            return 0.0;
+        }
+
+        // offset by half a cycle, so that the wave starts at 0.0
+        let phase = (self.phase_accumulator.phase() + 0.5) % 1.0;
+        let phase_increment = self.phase_accumulator.phase_increment();
+        let sample = 2.0 * phase - 1.0 - 2.0 * poly_blep(phase, phase_increment);
+
+        self.phase_accumulator.advance();
+
+        sample
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.phase_accumulator.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.phase_accumulator.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.phase_accumulator.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.phase_accumulator.frequency()
+    }
+}
+
+impl OscillatorInterface for Sawtooth {
+    fn phase(&self) -> f32 {
+        self.phase_accumulator.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.phase_accumulator.set_phase(phase);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_sawtooth {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{OscillatorInterface, Sawtooth, SineInterface};
+
+    #[test]
+    fn it_should_ramp_up_between_discontinuities() {
+        let mut sawtooth = Sawtooth::new_with_config(100u32, 1.0);
+
+        let samples: Vec<f32> = (0..100).map(|_| sawtooth.next_sample()).collect();
+        assert_difference_is_within_tolerance(samples[0], 0.0, 0.0001);
+        assert_difference_is_within_tolerance(samples[25], 0.5, 0.0001);
+        assert_difference_is_within_tolerance(samples[75], -0.5, 0.0001);
+        // the jump itself is smoothed over
+        assert!(samples[49] < 0.98);
+        assert!(samples[50] > -0.98);
+    }
+
+    #[test]
+    fn it_should_reset_phase() {
+        let mut sawtooth = Sawtooth::new_with_config(100u32, 1.0);
+        sawtooth.next_sample();
+        sawtooth.next_sample();
+        assert_difference_is_within_tolerance(sawtooth.phase(), 0.02, 0.0001);
+
+        sawtooth.reset_phase();
+        assert_eq!(sawtooth.phase(), 0.0);
+        assert_difference_is_within_tolerance(sawtooth.next_sample(), 0.0, 0.0001);
+    }
+
+    #[test]
+    fn it_should_work_when_sample_rate_is_0() {
+        let mut sawtooth = Sawtooth::new_with_config(0u32, 1.0);
+        assert_eq!(sawtooth.next_sample(), 0.0);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{poly_blamp, OscillatorInterface, PhaseAccumulator, SampleRate, SineInterface};
+
+/// Produces a band-limited triangle wave at the given frequency and sample rate
+///
+/// Like `Sine`, the wave starts at 0.0, peaks at 1.0 a quarter of the way
+/// through each cycle, and bottoms out at -1.0 three quarters of the way through.
+/// The corners are smoothed with PolyBLAMP to prevent aliasing.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
+pub struct Triangle {
+    phase_accumulator: PhaseAccumulator,
+}
+
+impl Triangle {
+    pub fn new() -> Self {
+        Self::new_with_config(1u32, 0.0)
+    }
+
+    pub fn new_with_config(sample_rate: impl Into<SampleRate>, frequency: impl Into<f32>) -> Self {
+        Self {
+            phase_accumulator: PhaseAccumulator::new(sample_rate, frequency.into()),
+        }
+    }
+}
+
+impl SineInterface for Triangle {
+    fn next_sample(&mut self) -> f32 {
+        if self.phase_accumulator.sample_rate().get() == 0 {
+            return 0.0;
+        }
+
+        // offset by a quarter cycle, so that the wave starts at 0.0 (rather than at its trough)
+        let phase = (self.phase_accumulator.phase() + 0.25) % 1.0;
+        let phase_increment = self.phase_accumulator.phase_increment();
+        let naive_sample = 1.0 - 4.0 * (phase - 0.5).abs();
+        // the slope changes by 8.0 per cycle at each corner: upward at the trough, downward at the peak
+        let slope_change = 8.0 * phase_increment.abs();
+        let sample = naive_sample
+            + slope_change
+                * (poly_blamp(phase, phase_increment)
+                    - poly_blamp((phase + 0.5) % 1.0, phase_increment));
+
+        self.phase_accumulator.advance();
+
+        sample
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.phase_accumulator.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.phase_accumulator.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.phase_accumulator.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.phase_accumulator.frequency()
+    }
+}
+
+impl OscillatorInterface for Triangle {
+    fn phase(&self) -> f32 {
+        self.phase_accumulator.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.phase_accumulator.set_phase(phase);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_triangle {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{OscillatorInterface, SineInterface, Triangle};
+
+    #[test]
+    fn it_should_produce_triangle_values_at_given_frequency() {
+        let mut triangle = Triangle::new_with_config(16u32, 1.0);
+
+        let samples: Vec<f32> = (0..16).map(|_| triangle.next_sample()).collect();
+        assert_difference_is_within_tolerance(samples[0], 0.0, 0.0001);
+        assert_difference_is_within_tolerance(samples[2], 0.5, 0.0001);
+        assert_difference_is_within_tolerance(samples[8], 0.0, 0.0001);
+        assert_difference_is_within_tolerance(samples[10], -0.5, 0.0001);
+        // corners are rounded off
+        assert!(samples[4] < 1.0 && samples[4] > 0.9);
+        assert!(samples[12] > -1.0 && samples[12] < -0.9);
+    }
+
+    #[test]
+    fn it_should_start_from_phase() {
+        let mut triangle = Triangle::new_with_config(1000u32, 1.0);
+        triangle.set_phase(1.25);
+
+        assert_eq!(triangle.phase(), 0.25);
+        assert_difference_is_within_tolerance(triangle.next_sample(), 1.0, 0.01);
+    }
+}
@@ -0,0 +1,263 @@
+use std::f32::consts::TAU;
+
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use crate::{linear_interpolate, SampleRate};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum WavetableError {
+    #[error("A wavetable cannot be created from an empty cycle")]
+    EmptyCycle,
+    #[error("Wavetable length must be a power of 2 of at least {min}. Received {0}", min = Wavetable::MIN_TABLE_LEN)]
+    InvalidTableLen(usize),
+    #[error("Cannot split samples into cycles of length {0}")]
+    InvalidCycleLen(usize),
+}
+
+/// A single cycle of a waveform, stored at several levels of detail (mip-maps).
+///
+/// Level 0 contains every harmonic of the original cycle that fits in the table,
+/// and each level after that contains half as many harmonics as the level before.
+/// Playing a level whose highest harmonic stays below the Nyquist frequency
+/// prevents the aliasing that a single, full-bandwidth table produces at high pitches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wavetable {
+    table_len: usize,
+    levels: Vec<Vec<f32>>,
+    /// The highest harmonic contained in each level
+    level_harmonics: Vec<usize>,
+}
+
+impl Wavetable {
+    pub const MIN_TABLE_LEN: usize = 4;
+    pub const DEFAULT_TABLE_LEN: usize = 2048;
+
+    /// Creates a wavetable from one cycle of a waveform (of any length)
+    pub fn new(cycle: &[f32]) -> Result<Self, WavetableError> {
+        Self::new_with_table_len(cycle, Self::DEFAULT_TABLE_LEN)
+    }
+
+    /// Creates a wavetable from one cycle of a waveform (of any length),
+    /// resampling it to `table_len` samples per level
+    pub fn new_with_table_len(cycle: &[f32], table_len: usize) -> Result<Self, WavetableError> {
+        if cycle.is_empty() {
+            return Err(WavetableError::EmptyCycle);
+        }
+        if !table_len.is_power_of_two() || table_len < Self::MIN_TABLE_LEN {
+            return Err(WavetableError::InvalidTableLen(table_len));
+        }
+
+        let mut planner = FftPlanner::new();
+        let mut spectrum: Vec<Complex<f32>> = cycle
+            .iter()
+            .map(|sample| Complex::new(*sample, 0.0))
+            .collect();
+        planner.plan_fft_forward(cycle.len()).process(&mut spectrum);
+        let inverse_fft = planner.plan_fft_inverse(table_len);
+
+        // the Nyquist bins are skipped, since their phase is ambiguous
+        let max_harmonic = ((cycle.len() - 1) / 2).min(table_len / 2 - 1);
+        let scale = 1.0 / cycle.len() as f32;
+
+        let mut levels = Vec::new();
+        let mut level_harmonics = Vec::new();
+        let mut num_harmonics = max_harmonic;
+        loop {
+            let mut level_spectrum = vec![Complex::default(); table_len];
+            level_spectrum[0] = spectrum[0] * scale;
+            for harmonic in 1..=num_harmonics {
+                level_spectrum[harmonic] = spectrum[harmonic] * scale;
+                level_spectrum[table_len - harmonic] = spectrum[cycle.len() - harmonic] * scale;
+            }
+            inverse_fft.process(&mut level_spectrum);
+
+            levels.push(level_spectrum.iter().map(|value| value.re).collect());
+            level_harmonics.push(num_harmonics);
+
+            if num_harmonics <= 1 {
+                break;
+            }
+            num_harmonics /= 2;
+        }
+
+        Ok(Self {
+            table_len,
+            levels,
+            level_harmonics,
+        })
+    }
+
+    /// Splits a buffer of back-to-back single cycles (a common format
+    /// for wavetable files) into one wavetable per cycle.
+    ///
+    /// `samples` must contain a whole number of cycles (at least one)
+    pub fn from_cycles(samples: &[f32], cycle_len: usize) -> Result<Vec<Self>, WavetableError> {
+        if cycle_len == 0 || samples.len() < cycle_len {
+            return Err(WavetableError::InvalidCycleLen(cycle_len));
+        }
+
+        let cycles = samples.chunks_exact(cycle_len);
+        if !cycles.remainder().is_empty() {
+            return Err(WavetableError::InvalidCycleLen(cycle_len));
+        }
+
+        cycles.map(Self::new).collect()
+    }
+
+    /// A pure sine wave, which is cheaper to play back than calling `sin()` every sample.
+    ///
+    /// Since a sine has no harmonics above its fundamental, only one level is stored.
+    pub fn sine() -> Self {
+        let table_len = Self::DEFAULT_TABLE_LEN;
+        let table = (0..table_len)
+            .map(|i| (TAU * i as f32 / table_len as f32).sin())
+            .collect();
+
+        Self {
+            table_len,
+            levels: vec![table],
+            level_harmonics: vec![1],
+        }
+    }
+
+    pub fn table_len(&self) -> usize {
+        self.table_len
+    }
+
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn level(&self, level: usize) -> Option<&[f32]> {
+        self.levels.get(level).map(Vec::as_slice)
+    }
+
+    /// The highest harmonic contained in `level`
+    pub fn level_harmonics(&self, level: usize) -> Option<usize> {
+        self.level_harmonics.get(level).copied()
+    }
+
+    /// Returns the most detailed level that can be played at `frequency` without
+    /// any of its harmonics exceeding the Nyquist frequency (or the least detailed
+    /// level, if even its fundamental exceeds the Nyquist frequency)
+    pub fn level_for_frequency(&self, frequency: f32, sample_rate: impl Into<SampleRate>) -> usize {
+        let nyquist = sample_rate.into().get() as f32 / 2.0;
+        let frequency = frequency.abs();
+
+        self.level_harmonics
+            .iter()
+            .position(|harmonics| *harmonics as f32 * frequency <= nyquist)
+            .unwrap_or(self.levels.len() - 1)
+    }
+
+    /// Reads from a `phase` (0.0 to 1.0) in `level`, interpolating between samples
+    pub fn sample_at(&self, level: usize, phase: f32) -> f32 {
+        let table = &self.levels[level.min(self.levels.len() - 1)];
+        let position = phase.rem_euclid(1.0) * self.table_len as f32;
+        let index = position as usize % self.table_len;
+        let next_index = (index + 1) % self.table_len;
+
+        linear_interpolate(table[index], table[next_index], position.fract())
+    }
+}
+
+#[cfg(test)]
+mod test_wavetable {
+    use std::f32::consts::TAU;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{Wavetable, WavetableError};
+
+    fn sawtooth_cycle(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| 2.0 * i as f32 / len as f32 - 1.0)
+            .collect()
+    }
+
+    #[test]
+    fn it_should_resample_cycle_to_table_len() {
+        let cycle: Vec<f32> = (0..100).map(|i| (TAU * i as f32 / 100.0).sin()).collect();
+        let wavetable = Wavetable::new_with_table_len(&cycle, 256).unwrap();
+
+        assert_eq!(wavetable.table_len(), 256);
+        wavetable
+            .level(0)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .for_each(|(i, sample)| {
+                assert_difference_is_within_tolerance(
+                    *sample,
+                    (TAU * i as f32 / 256.0).sin(),
+                    0.0001,
+                )
+            });
+    }
+
+    #[test]
+    fn it_should_halve_harmonics_at_every_level() {
+        let wavetable = Wavetable::new_with_table_len(&sawtooth_cycle(2048), 64).unwrap();
+
+        let harmonics: Vec<usize> = (0..wavetable.num_levels())
+            .map(|level| wavetable.level_harmonics(level).unwrap())
+            .collect();
+        assert_eq!(harmonics, vec![31, 15, 7, 3, 1]);
+
+        // the last level only contains the fundamental
+        let last_level = wavetable.level(wavetable.num_levels() - 1).unwrap();
+        let amplitude = last_level.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+        last_level.iter().enumerate().for_each(|(i, sample)| {
+            assert_difference_is_within_tolerance(
+                *sample,
+                -amplitude * (TAU * i as f32 / 64.0).sin(),
+                0.005,
+            )
+        });
+    }
+
+    #[test]
+    fn it_should_choose_level_below_nyquist() {
+        let wavetable = Wavetable::new(&sawtooth_cycle(2048)).unwrap();
+
+        assert_eq!(wavetable.level_for_frequency(20.0, 44100), 0);
+
+        let level = wavetable.level_for_frequency(5000.0, 44100);
+        let harmonics = wavetable.level_harmonics(level).unwrap();
+        assert!(harmonics as f32 * 5000.0 <= 22050.0);
+        assert!(wavetable.level_harmonics(level - 1).unwrap() as f32 * 5000.0 > 22050.0);
+
+        assert_eq!(
+            wavetable.level_for_frequency(30000.0, 44100),
+            wavetable.num_levels() - 1
+        );
+    }
+
+    #[test]
+    fn it_should_split_buffer_into_cycles() {
+        let mut samples = sawtooth_cycle(32);
+        samples.extend(sawtooth_cycle(32).iter().map(|sample| -sample));
+
+        let wavetables = Wavetable::from_cycles(&samples, 32).unwrap();
+        assert_eq!(wavetables.len(), 2);
+        assert_difference_is_within_tolerance(
+            wavetables[0].sample_at(0, 0.25),
+            -wavetables[1].sample_at(0, 0.25),
+            0.0001,
+        );
+
+        assert_eq!(
+            Wavetable::from_cycles(&samples, 0).unwrap_err(),
+            WavetableError::InvalidCycleLen(0)
+        );
+        assert_eq!(
+            Wavetable::from_cycles(&samples, 48).unwrap_err(),
+            WavetableError::InvalidCycleLen(48)
+        );
+        assert_eq!(Wavetable::new(&[]).unwrap_err(), WavetableError::EmptyCycle);
+        assert_eq!(
+            Wavetable::new_with_table_len(&samples, 100).unwrap_err(),
+            WavetableError::InvalidTableLen(100)
+        );
+    }
+}
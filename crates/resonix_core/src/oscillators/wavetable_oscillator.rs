@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use crate::{
+    linear_interpolate, OscillatorInterface, PhaseAccumulator, SampleRate, SineInterface, Wavetable,
+};
+
+/// Plays back one or more single-cycle `Wavetable`s at the given frequency and sample rate.
+///
+/// When multiple tables are loaded, `morph` sweeps smoothly through them
+/// (0.0 plays the first table, 1.0 plays the last, and anything in between
+/// crossfades between the two nearest tables).
+///
+/// The mip-map level of each table is chosen according to the current frequency,
+/// so that no harmonics exceed the Nyquist frequency.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WavetableOscillator {
+    phase_accumulator: PhaseAccumulator,
+    tables: Vec<Arc<Wavetable>>,
+    morph: f32,
+}
+
+impl WavetableOscillator {
+    pub fn new(tables: Vec<Arc<Wavetable>>) -> Self {
+        Self::new_with_config(1u32, 0.0, tables)
+    }
+
+    pub fn new_with_config(
+        sample_rate: impl Into<SampleRate>,
+        frequency: impl Into<f32>,
+        tables: Vec<Arc<Wavetable>>,
+    ) -> Self {
+        Self {
+            phase_accumulator: PhaseAccumulator::new(sample_rate, frequency.into()),
+            tables,
+            morph: 0.0,
+        }
+    }
+
+    /// A sine oscillator that reads from a lookup table rather than calling `sin()` every sample
+    pub fn new_sine(sample_rate: impl Into<SampleRate>, frequency: impl Into<f32>) -> Self {
+        Self::new_with_config(sample_rate, frequency, vec![Arc::new(Wavetable::sine())])
+    }
+
+    pub fn tables(&self) -> &[Arc<Wavetable>] {
+        &self.tables
+    }
+
+    pub fn set_tables(&mut self, tables: Vec<Arc<Wavetable>>) -> &mut Self {
+        self.tables = tables;
+        self
+    }
+
+    pub fn morph(&self) -> f32 {
+        self.morph
+    }
+
+    /// Position (from 0.0 to 1.0) between the first and last tables
+    pub fn set_morph(&mut self, morph: f32) -> &mut Self {
+        self.morph = morph.clamp(0.0, 1.0);
+        self
+    }
+
+    fn sample_table(&self, table: &Wavetable, phase: f32) -> f32 {
+        let level = table.level_for_frequency(
+            self.phase_accumulator.frequency(),
+            self.phase_accumulator.sample_rate(),
+        );
+        table.sample_at(level, phase)
+    }
+}
+
+impl SineInterface for WavetableOscillator {
+    fn next_sample(&mut self) -> f32 {
+        if self.phase_accumulator.sample_rate().get() == 0 || self.tables.is_empty() {
+            return 0.0;
+        }
+
+        let phase = self.phase_accumulator.phase();
+        let position = self.morph * (self.tables.len() - 1) as f32;
+        let index = position as usize;
+        let fraction = position.fract();
+
+        let sample = if fraction == 0.0 {
+            self.sample_table(&self.tables[index], phase)
+        } else {
+            linear_interpolate(
+                self.sample_table(&self.tables[index], phase),
+                self.sample_table(&self.tables[index + 1], phase),
+                fraction,
+            )
+        };
+
+        self.phase_accumulator.advance();
+
+        sample
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.phase_accumulator.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.phase_accumulator.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.phase_accumulator.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.phase_accumulator.frequency()
+    }
+}
+
+impl OscillatorInterface for WavetableOscillator {
+    fn phase(&self) -> f32 {
+        self.phase_accumulator.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.phase_accumulator.set_phase(phase);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_wavetable_oscillator {
+    use std::sync::Arc;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{Sine, SineInterface, Wavetable, WavetableOscillator};
+
+    #[test]
+    fn table_lookup_sine_should_match_sine() {
+        let mut sine = Sine::new_with_config(44100, 440.0);
+        let mut table_sine = WavetableOscillator::new_sine(44100, 440.0);
+
+        for _ in 0..1000 {
+            assert_difference_is_within_tolerance(
+                table_sine.next_sample(),
+                sine.next_sample(),
+                0.001,
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_morph_between_tables() {
+        let sine = Wavetable::sine();
+        let inverted_sine = Wavetable::new(
+            &sine
+                .level(0)
+                .unwrap()
+                .iter()
+                .map(|sample| -sample)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let mut oscillator = WavetableOscillator::new_with_config(
+            4,
+            1.0,
+            vec![Arc::new(sine), Arc::new(inverted_sine)],
+        );
+
+        assert_difference_is_within_tolerance(oscillator.next_sample(), 0.0, 0.0001);
+        assert_difference_is_within_tolerance(oscillator.next_sample(), 1.0, 0.0001);
+
+        // a quarter of the way from the sine to the inverted sine
+        oscillator.set_morph(0.25);
+        oscillator.next_sample();
+        assert_difference_is_within_tolerance(oscillator.next_sample(), -0.5, 0.0001);
+
+        oscillator.set_morph(1.0);
+        oscillator.next_sample();
+        assert_difference_is_within_tolerance(oscillator.next_sample(), -1.0, 0.0001);
+    }
+
+    #[test]
+    fn it_should_output_silence_without_tables() {
+        let mut oscillator = WavetableOscillator::new_with_config(44100, 440.0, Vec::new());
+        assert_eq!(oscillator.next_sample(), 0.0);
+    }
+}
@@ -0,0 +1,9 @@
+mod pan_balance;
+mod pan_equal_power;
+mod pan_mode;
+mod pan_ring;
+
+pub use pan_balance::*;
+pub use pan_equal_power::*;
+pub use pan_mode::*;
+pub use pan_ring::*;
@@ -0,0 +1,70 @@
+use std::f32::consts::FRAC_PI_2;
+
+/// Calculates the gain of each channel of a multichannel signal when
+/// adjusting its balance, where `position` ranges from -1.0 (towards the first channel)
+/// to 1.0 (towards the last channel).
+///
+/// Unlike `pan_equal_power`, which places a mono signal among many channels,
+/// balance keeps every channel of the signal in place: channels on the same side as
+/// `position` are left unchanged, while channels on the opposite side are faded out.
+/// At a position of 0.0, every gain is 1.0.
+///
+/// Creates a new buffer and writes into it. To avoid unnecessary allocations, use `pan_balance_to_buffer`
+pub fn pan_balance(position: f32, num_channels: u32) -> Vec<f32> {
+    let mut output_buffer = vec![0.0; num_channels as usize];
+    pan_balance_to_buffer(position, &mut output_buffer);
+    output_buffer
+}
+
+/// Calculates the gain of each channel of a multichannel signal when
+/// adjusting its balance, where `position` ranges from -1.0 to 1.0.
+///
+/// Writes into an existing buffer (one gain per channel) to avoid unnecessary allocations
+pub fn pan_balance_to_buffer(position: f32, write_buffer: &mut [f32]) -> &mut [f32] {
+    let position = position.clamp(-1.0, 1.0);
+    let num_channels = write_buffer.len();
+
+    write_buffer.iter_mut().enumerate().for_each(|(i, gain)| {
+        let channel_position = if num_channels > 1 {
+            i as f32 / (num_channels - 1) as f32 * 2.0 - 1.0
+        } else {
+            0.0
+        };
+
+        *gain = if position * channel_position >= 0.0 {
+            1.0
+        } else {
+            (position.abs() * channel_position.abs() * FRAC_PI_2).cos()
+        };
+    });
+
+    write_buffer
+}
+
+#[cfg(test)]
+mod test_pan_balance {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use crate::pan_balance;
+
+    #[test]
+    fn it_should_leave_centered_signal_unchanged() {
+        assert_eq!(pan_balance(0.0, 2), vec![1.0, 1.0]);
+        assert_eq!(pan_balance(0.0, 4), vec![1.0; 4]);
+        assert_eq!(pan_balance(1.0, 1), vec![1.0]);
+    }
+
+    #[test]
+    fn it_should_fade_out_opposite_channels() {
+        let gains = pan_balance(-1.0, 2);
+        assert_eq!(gains[0], 1.0);
+        assert!(gains[1].abs() < 0.000001);
+
+        let gains = pan_balance(0.5, 2);
+        assert!((gains[0] - FRAC_1_SQRT_2).abs() < 0.000001);
+        assert_eq!(gains[1], 1.0);
+
+        // the middle channel of 3 is never faded
+        assert_eq!(pan_balance(1.0, 3)[1..], [1.0, 1.0]);
+    }
+}
@@ -0,0 +1,74 @@
+use std::f32::consts::FRAC_PI_2;
+
+/// Calculates the gain of each output channel when panning a mono signal across
+/// a line of speakers, where `position` ranges from -1.0 (first channel) to 1.0 (last channel).
+///
+/// The signal is crossfaded between the two nearest channels with an equal-power (sine/cosine)
+/// curve, so the perceived loudness stays constant as the position moves.
+/// For stereo output, this is the classic equal-power pan law.
+///
+/// Creates a new buffer and writes into it. To avoid unnecessary allocations, use `pan_equal_power_to_buffer`
+pub fn pan_equal_power(position: f32, num_channels_out: u32) -> Vec<f32> {
+    let mut output_buffer = vec![0.0; num_channels_out as usize];
+    pan_equal_power_to_buffer(position, &mut output_buffer);
+    output_buffer
+}
+
+/// Calculates the gain of each output channel when panning a mono signal across
+/// a line of speakers, where `position` ranges from -1.0 (first channel) to 1.0 (last channel).
+///
+/// Writes into an existing buffer (one gain per output channel) to avoid unnecessary allocations
+pub fn pan_equal_power_to_buffer(position: f32, write_buffer: &mut [f32]) -> &mut [f32] {
+    write_buffer.fill(0.0);
+
+    match write_buffer.len() {
+        0 => {}
+        1 => write_buffer[0] = 1.0,
+        num_channels_out => {
+            let progress = (position.clamp(-1.0, 1.0) + 1.0) / 2.0;
+            let scaled_position = progress * (num_channels_out - 1) as f32;
+            let first_channel = (scaled_position.floor() as usize).min(num_channels_out - 2);
+            let fraction = scaled_position - first_channel as f32;
+
+            write_buffer[first_channel] = (fraction * FRAC_PI_2).cos();
+            write_buffer[first_channel + 1] = (fraction * FRAC_PI_2).sin();
+        }
+    }
+
+    write_buffer
+}
+
+#[cfg(test)]
+mod test_pan_equal_power {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use crate::pan_equal_power;
+
+    #[test]
+    fn it_should_pan_hard_left_and_right() {
+        assert_eq!(pan_equal_power(-1.0, 2), vec![1.0, 0.0]);
+        assert!((pan_equal_power(1.0, 2)[0]).abs() < 0.000001);
+        assert_eq!(pan_equal_power(1.0, 2)[1], 1.0);
+    }
+
+    #[test]
+    fn it_should_keep_equal_power_in_the_center() {
+        let gains = pan_equal_power(0.0, 2);
+        assert!((gains[0] - FRAC_1_SQRT_2).abs() < 0.000001);
+        assert!((gains[1] - FRAC_1_SQRT_2).abs() < 0.000001);
+    }
+
+    #[test]
+    fn it_should_keep_constant_power_across_many_channels() {
+        (0..=20).for_each(|i| {
+            let position = i as f32 / 10.0 - 1.0;
+            let gains = pan_equal_power(position, 5);
+            let power: f32 = gains.iter().map(|gain| gain * gain).sum();
+            assert!((power - 1.0).abs() < 0.000001);
+            assert!(gains.iter().filter(|gain| **gain > 0.000001).count() <= 2);
+        });
+
+        // channel 2 of 5 is in the middle
+        assert_eq!(pan_equal_power(0.0, 5), vec![0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+}
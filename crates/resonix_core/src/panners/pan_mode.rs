@@ -0,0 +1,28 @@
+use crate::{pan_equal_power, pan_equal_power_to_buffer, pan_ring, pan_ring_to_buffer};
+
+/// How a mono signal's position is mapped onto the gain of each output channel
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PanMode {
+    /// Position ranges from -1.0 (first channel) to 1.0 (last channel),
+    /// as with left-to-right stereo panning. See `pan_equal_power`.
+    #[default]
+    EqualPower,
+    /// Position is an azimuth (in turns) around a ring of evenly spaced speakers. See `pan_ring`.
+    Ring,
+}
+
+impl PanMode {
+    pub fn as_panner(&self) -> impl Fn(f32, u32) -> Vec<f32> {
+        match self {
+            PanMode::EqualPower => pan_equal_power,
+            PanMode::Ring => pan_ring,
+        }
+    }
+
+    pub fn as_panner_to_buffer(&self) -> impl for<'a> Fn(f32, &'a mut [f32]) -> &'a mut [f32] {
+        match self {
+            PanMode::EqualPower => pan_equal_power_to_buffer,
+            PanMode::Ring => pan_ring_to_buffer,
+        }
+    }
+}
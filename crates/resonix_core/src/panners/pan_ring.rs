@@ -0,0 +1,95 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Calculates the gain of each output channel when panning a mono signal around a ring
+/// of evenly spaced speakers, using 2D vector base amplitude panning (VBAP).
+///
+/// `azimuth` is measured in turns: 0.0 is the first channel's speaker, and moving towards
+/// 1.0 passes each successive channel's speaker before wrapping back around to the first.
+///
+/// Only the two speakers on either side of the source ever receive signal, and
+/// their gains are normalized so that the perceived loudness stays constant.
+///
+/// Creates a new buffer and writes into it. To avoid unnecessary allocations, use `pan_ring_to_buffer`
+pub fn pan_ring(azimuth: f32, num_channels_out: u32) -> Vec<f32> {
+    let mut output_buffer = vec![0.0; num_channels_out as usize];
+    pan_ring_to_buffer(azimuth, &mut output_buffer);
+    output_buffer
+}
+
+/// Calculates the gain of each output channel when panning a mono signal around a ring
+/// of evenly spaced speakers, where `azimuth` is measured in turns.
+///
+/// Writes into an existing buffer (one gain per output channel) to avoid unnecessary allocations
+pub fn pan_ring_to_buffer(azimuth: f32, write_buffer: &mut [f32]) -> &mut [f32] {
+    write_buffer.fill(0.0);
+
+    let num_channels_out = write_buffer.len();
+    if num_channels_out == 0 {
+        return write_buffer;
+    }
+    if num_channels_out == 1 {
+        write_buffer[0] = 1.0;
+        return write_buffer;
+    }
+
+    let scaled_azimuth = azimuth.rem_euclid(1.0) * num_channels_out as f32;
+    let first_channel = (scaled_azimuth.floor() as usize).min(num_channels_out - 1);
+    let second_channel = (first_channel + 1) % num_channels_out;
+    let fraction = scaled_azimuth - first_channel as f32;
+
+    let (first_gain, second_gain) = if num_channels_out == 2 {
+        // two speakers directly opposite each other can't form a vector base,
+        // so fall back to an equal-power crossfade between them
+        ((fraction * FRAC_PI_2).cos(), (fraction * FRAC_PI_2).sin())
+    } else {
+        let speaker_spacing = TAU / num_channels_out as f32;
+        let angle = fraction * speaker_spacing;
+        let first_gain = (speaker_spacing - angle).sin();
+        let second_gain = angle.sin();
+        let norm = (first_gain * first_gain + second_gain * second_gain).sqrt();
+        (first_gain / norm, second_gain / norm)
+    };
+
+    write_buffer[first_channel] = first_gain;
+    write_buffer[second_channel] = second_gain;
+
+    write_buffer
+}
+
+#[cfg(test)]
+mod test_pan_ring {
+    use crate::pan_ring;
+
+    #[test]
+    fn it_should_place_source_on_speaker() {
+        assert_eq!(pan_ring(0.0, 4), vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(pan_ring(0.5, 4), vec![0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn it_should_split_between_adjacent_speakers() {
+        let gains = pan_ring(1.5 / 6.0, 6);
+        assert_eq!(gains[0], 0.0);
+        assert!((gains[1] - gains[2]).abs() < 0.000001);
+        assert!(gains[3..].iter().all(|gain| *gain == 0.0));
+    }
+
+    #[test]
+    fn it_should_wrap_around_the_ring() {
+        let gains = pan_ring(-0.125, 4);
+        assert!((gains[3] - gains[0]).abs() < 0.000001);
+        assert!(gains[3] > 0.0);
+        assert_eq!(pan_ring(1.25, 4), pan_ring(0.25, 4));
+    }
+
+    #[test]
+    fn it_should_keep_constant_power() {
+        [2, 3, 5, 8].into_iter().for_each(|num_channels| {
+            (0..100).for_each(|i| {
+                let gains = pan_ring(i as f32 / 100.0, num_channels);
+                let power: f32 = gains.iter().map(|gain| gain * gain).sum();
+                assert!((power - 1.0).abs() < 0.00001);
+            });
+        });
+    }
+}
@@ -0,0 +1,7 @@
+mod resample_buffer;
+mod resample_quality;
+mod resampler;
+
+pub use resample_buffer::*;
+pub use resample_quality::*;
+pub use resampler::*;
@@ -0,0 +1,139 @@
+use crate::{AudioBuffer, ResampleQuality, Resampler, SampleRate};
+
+/// Converts a whole buffer of samples from one sample rate to another.
+///
+/// The output is as long as the input (in time), rounded up to the nearest
+/// whole sample at the new sample rate.
+pub fn resample_samples(
+    samples: &[f32],
+    input_sample_rate: impl Into<SampleRate>,
+    output_sample_rate: impl Into<SampleRate>,
+    quality: ResampleQuality,
+) -> Vec<f32> {
+    let input_sample_rate = input_sample_rate.into();
+    let output_sample_rate = output_sample_rate.into();
+    if input_sample_rate == output_sample_rate
+        || input_sample_rate.get() == 0
+        || output_sample_rate.get() == 0
+    {
+        return samples.to_vec();
+    }
+
+    let num_output_samples = (samples.len() as u64 * output_sample_rate.get() as u64)
+        .div_ceil(input_sample_rate.get() as u64) as usize;
+
+    let mut resampler = Resampler::new(input_sample_rate, output_sample_rate, quality);
+    let mut output = Vec::with_capacity(num_output_samples);
+    resampler.process(samples, &mut output);
+    // flush the final samples out with silence from beyond the end of the input
+    resampler.process(&vec![0.0; resampler.latency() + 1], &mut output);
+    output.truncate(num_output_samples);
+
+    output
+}
+
+/// Converts every channel of `buffer` to `output_sample_rate`
+pub fn resample_buffer(
+    buffer: &AudioBuffer,
+    output_sample_rate: impl Into<SampleRate>,
+    quality: ResampleQuality,
+) -> AudioBuffer {
+    let output_sample_rate = output_sample_rate.into();
+    let channels = buffer
+        .channels()
+        .iter()
+        .map(|channel| resample_samples(channel, buffer.sample_rate(), output_sample_rate, quality))
+        .collect();
+
+    AudioBuffer::from_channels(channels, output_sample_rate)
+        .expect("Every channel should be resampled to the same length")
+}
+
+#[cfg(test)]
+mod test_resample_buffer {
+    use std::f32::consts::TAU;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{resample_buffer, resample_samples, AudioBuffer, ResampleQuality};
+
+    fn sine(frequency: f32, sample_rate: u32, num_samples: usize) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| (TAU * frequency * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn it_should_preserve_length_in_time() {
+        let buffer = AudioBuffer::new(2, 44100, 44100);
+        let resampled = resample_buffer(&buffer, 48000, ResampleQuality::High);
+
+        assert_eq!(resampled.sample_rate().get(), 48000);
+        assert_eq!(*resampled.num_channels(), 2);
+        assert_eq!(resampled.num_frames(), 48000);
+        assert_eq!(
+            resample_samples(&[0.0; 3], 44100, 48000, ResampleQuality::Fast).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn it_should_not_alter_samples_at_the_same_sample_rate() {
+        let input = sine(440.0, 44100, 100);
+        assert_eq!(
+            resample_samples(&input, 44100, 44100, ResampleQuality::Best),
+            input
+        );
+    }
+
+    #[test]
+    fn it_should_reproduce_sine_waves_at_new_sample_rate() {
+        for (quality, tolerance) in [
+            (ResampleQuality::Fast, 0.01),
+            (ResampleQuality::Medium, 0.001),
+            (ResampleQuality::High, 0.001),
+            (ResampleQuality::Best, 0.001),
+        ] {
+            for (input_sample_rate, output_sample_rate) in [(44100, 48000), (48000, 44100)] {
+                let input = sine(1000.0, input_sample_rate, input_sample_rate as usize / 10);
+                let output =
+                    resample_samples(&input, input_sample_rate, output_sample_rate, quality);
+                let expected = sine(1000.0, output_sample_rate, output.len());
+
+                // skip the edges, where the kernel reads silence beyond the input
+                let edge = 200;
+                output[edge..output.len() - edge]
+                    .iter()
+                    .zip(expected[edge..].iter())
+                    .for_each(|(sample, expected)| {
+                        assert_difference_is_within_tolerance(*sample, *expected, tolerance)
+                    });
+            }
+        }
+    }
+
+    #[test]
+    fn band_limited_resampling_should_remove_frequencies_above_new_nyquist() {
+        // 20kHz can't be represented at 22050Hz, and would otherwise alias down to 2050Hz
+        let input = sine(20_000.0, 48000, 4800);
+
+        let band_limited = resample_samples(&input, 48000, 22050, ResampleQuality::High);
+        let interpolated = resample_samples(&input, 48000, 22050, ResampleQuality::Fast);
+
+        let edge = 200;
+        assert!(rms(&band_limited[edge..band_limited.len() - edge]) < 0.001);
+        assert!(rms(&interpolated[edge..interpolated.len() - edge]) > 0.1);
+    }
+
+    #[test]
+    fn it_should_preserve_dc_offset() {
+        let output = resample_samples(&[0.5; 1000], 44100, 32000, ResampleQuality::Best);
+        output[100..output.len() - 100]
+            .iter()
+            .for_each(|sample| assert_difference_is_within_tolerance(*sample, 0.5, 0.0001));
+    }
+}
@@ -0,0 +1,48 @@
+/// Trades off the accuracy of sample-rate conversion against its CPU cost
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum ResampleQuality {
+    /// Linear interpolation between neighboring samples.
+    ///
+    /// Very cheap, but audibly dulls high frequencies and does nothing to prevent aliasing.
+    Fast,
+    /// Cubic (Catmull-Rom) interpolation between neighboring samples.
+    ///
+    /// Still cheap, and smoother than `Fast`, but also does nothing to prevent aliasing.
+    Medium,
+    /// Band-limited (windowed-sinc) interpolation, with 32 zero crossings on each side.
+    #[default]
+    High,
+    /// Band-limited (windowed-sinc) interpolation, with 64 zero crossings on each side
+    /// and a steeper cutoff than `High`.
+    Best,
+}
+
+/// How output samples are calculated from the surrounding input samples
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum ResampleKernel {
+    Linear,
+    Cubic,
+    Sinc {
+        /// Number of zero crossings of the sinc function on each side of the kernel
+        zero_crossings: usize,
+        /// Where the low-pass cutoff sits, as a fraction of the lower Nyquist frequency
+        rolloff: f64,
+    },
+}
+
+impl ResampleQuality {
+    pub(crate) fn kernel(&self) -> ResampleKernel {
+        match self {
+            ResampleQuality::Fast => ResampleKernel::Linear,
+            ResampleQuality::Medium => ResampleKernel::Cubic,
+            ResampleQuality::High => ResampleKernel::Sinc {
+                zero_crossings: 32,
+                rolloff: 0.9,
+            },
+            ResampleQuality::Best => ResampleKernel::Sinc {
+                zero_crossings: 64,
+                rolloff: 0.95,
+            },
+        }
+    }
+}
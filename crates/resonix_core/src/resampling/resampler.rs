@@ -0,0 +1,416 @@
+use std::{f64::consts::PI, sync::OnceLock};
+
+use crate::{cubic_interpolate, linear_interpolate, ResampleKernel, ResampleQuality, SampleRate};
+
+/// Converts a single, continuous stream of samples from one sample rate to another.
+///
+/// Input is pushed in blocks of any size, and output samples are pulled out
+/// as soon as enough input has arrived to calculate them. Output sample `n`
+/// always lines up with input time `n * input_sample_rate / output_sample_rate`,
+/// but calculating it requires `latency()` input samples beyond that point.
+///
+/// For multichannel audio, use one `Resampler` per channel.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    input_sample_rate: SampleRate,
+    output_sample_rate: SampleRate,
+    quality: ResampleQuality,
+    kernel: ResampleKernel,
+    /// How far the read position moves through the input for every output sample
+    step: f64,
+    /// Low-pass cutoff, relative to the input's Nyquist frequency
+    cutoff: f64,
+    /// Number of input samples read on each side of the read position
+    half_len: usize,
+    /// One side of the (symmetric) windowed-sinc kernel, sampled `SINC_TABLE_RESOLUTION` times per zero crossing
+    sinc_table: &'static [f32],
+    /// Input samples that may still be needed to calculate upcoming output
+    history: Vec<f32>,
+    /// Index into `history` of the input sample at or just before the read position
+    index: usize,
+    /// Distance from `index` to the read position, between 0.0 and 1.0
+    fraction: f64,
+}
+
+impl Resampler {
+    const SINC_TABLE_RESOLUTION: usize = 256;
+
+    pub fn new(
+        input_sample_rate: impl Into<SampleRate>,
+        output_sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> Self {
+        let mut resampler = Self {
+            input_sample_rate: input_sample_rate.into(),
+            output_sample_rate: output_sample_rate.into(),
+            quality,
+            kernel: quality.kernel(),
+            step: 1.0,
+            cutoff: 1.0,
+            half_len: 0,
+            sinc_table: &[],
+            history: Vec::new(),
+            index: 0,
+            fraction: 0.0,
+        };
+
+        // build every table up front, so that switching quality later
+        // (possibly on the audio thread) never has to
+        [ResampleQuality::High, ResampleQuality::Best]
+            .into_iter()
+            .for_each(|quality| {
+                Self::sinc_table(quality);
+            });
+
+        resampler.update_kernel();
+        resampler.reset();
+        resampler
+    }
+
+    pub fn input_sample_rate(&self) -> SampleRate {
+        self.input_sample_rate
+    }
+
+    pub fn set_input_sample_rate(&mut self, input_sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.input_sample_rate = input_sample_rate.into();
+        self.update_kernel();
+        self
+    }
+
+    pub fn output_sample_rate(&self) -> SampleRate {
+        self.output_sample_rate
+    }
+
+    pub fn set_output_sample_rate(
+        &mut self,
+        output_sample_rate: impl Into<SampleRate>,
+    ) -> &mut Self {
+        self.output_sample_rate = output_sample_rate.into();
+        self.update_kernel();
+        self
+    }
+
+    pub fn quality(&self) -> ResampleQuality {
+        self.quality
+    }
+
+    pub fn set_quality(&mut self, quality: ResampleQuality) -> &mut Self {
+        self.quality = quality;
+        self.kernel = quality.kernel();
+        self.update_kernel();
+        self
+    }
+
+    /// Number of output samples produced for every input sample
+    pub fn ratio(&self) -> f64 {
+        1.0 / self.step
+    }
+
+    /// Number of input samples that must be pushed beyond an output
+    /// sample's position before that output sample can be calculated
+    pub fn latency(&self) -> usize {
+        self.half_len
+    }
+
+    /// What `latency()` would be with the given sample rates and quality
+    pub fn latency_for(
+        input_sample_rate: impl Into<SampleRate>,
+        output_sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> usize {
+        let step = Self::step(input_sample_rate.into(), output_sample_rate.into());
+        Self::kernel_shape(quality.kernel(), step).1
+    }
+
+    /// Makes room for kernels with up to `latency` samples on each side (see `latency_for`),
+    /// so that later changes to the sample rates or quality don't allocate
+    /// (as long as input is pushed as it's needed, rather than in large blocks)
+    pub fn reserve_latency(&mut self, latency: usize) {
+        // input is discarded once half of `history` has been read past, so at most
+        // two kernels' worth of samples are kept, plus the one being pushed
+        let capacity = 4 * latency + 2;
+        self.history
+            .reserve(capacity.saturating_sub(self.history.len()));
+    }
+
+    /// Whether more input needs to be pushed before the next output sample can be calculated
+    pub fn needs_input(&self) -> bool {
+        self.index + self.half_len >= self.history.len()
+    }
+
+    /// Adds samples to the end of the input stream
+    pub fn push(&mut self, samples: &[f32]) {
+        self.discard_consumed_input();
+        self.history.extend_from_slice(samples);
+    }
+
+    /// Adds a single sample to the end of the input stream
+    pub fn push_sample(&mut self, sample: f32) {
+        self.discard_consumed_input();
+        self.history.push(sample);
+    }
+
+    /// Calculates the next output sample, or returns `None` if more input is needed first
+    pub fn next_sample(&mut self) -> Option<f32> {
+        if self.needs_input() {
+            return None;
+        }
+
+        let sample = self.sample_at_read_position();
+
+        self.fraction += self.step;
+        let whole_samples = self.fraction.floor();
+        self.index += whole_samples as usize;
+        self.fraction -= whole_samples;
+
+        Some(sample)
+    }
+
+    /// Pushes `input` and appends every output sample that can now be calculated to `output`
+    pub fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        self.push(input);
+        while let Some(sample) = self.next_sample() {
+            output.push(sample);
+        }
+    }
+
+    /// Clears all input and restarts the output stream from time 0
+    pub fn reset(&mut self) {
+        self.history.clear();
+        // the kernel reads silence before the start of the stream
+        self.history.resize(self.half_len, 0.0);
+        self.index = self.half_len;
+        self.fraction = 0.0;
+    }
+
+    fn sample_at_read_position(&self) -> f32 {
+        let index = self.index;
+        let fraction = self.fraction;
+
+        // no interpolation is necessary when reading exactly from an input sample
+        if self.step == 1.0 && fraction == 0.0 {
+            return self.history[index];
+        }
+
+        match self.kernel {
+            ResampleKernel::Linear => linear_interpolate(
+                self.history[index],
+                self.history[index + 1],
+                fraction as f32,
+            ),
+            ResampleKernel::Cubic => cubic_interpolate(
+                self.history[index - 1],
+                self.history[index],
+                self.history[index + 1],
+                self.history[index + 2],
+                fraction as f32,
+            ),
+            ResampleKernel::Sinc { .. } => {
+                let first_index = index + 1 - self.half_len;
+                let (sum, weight_sum) = self.history[first_index..=index + self.half_len]
+                    .iter()
+                    .enumerate()
+                    .fold((0.0, 0.0), |(sum, weight_sum), (i, sample)| {
+                        let distance = (i + first_index) as f64 - index as f64 - fraction;
+                        let weight = self.sinc_weight(distance);
+                        (sum + sample * weight, weight_sum + weight)
+                    });
+
+                // normalizing keeps DC gain at exactly 1.0, regardless of fractional position
+                if weight_sum.abs() > f32::EPSILON {
+                    sum / weight_sum
+                } else {
+                    sum
+                }
+            }
+        }
+    }
+
+    /// Reads the kernel's weight for an input sample `distance` samples away from the read position
+    fn sinc_weight(&self, distance: f64) -> f32 {
+        let table_position = (distance * self.cutoff).abs() * Self::SINC_TABLE_RESOLUTION as f64;
+        let table_index = table_position.floor() as usize;
+        if table_index + 1 >= self.sinc_table.len() {
+            return 0.0;
+        }
+
+        linear_interpolate(
+            self.sinc_table[table_index],
+            self.sinc_table[table_index + 1],
+            (table_position - table_index as f64) as f32,
+        )
+    }
+
+    fn update_kernel(&mut self) {
+        self.step = Self::step(self.input_sample_rate, self.output_sample_rate);
+
+        let previous_half_len = self.half_len;
+        (self.cutoff, self.half_len) = Self::kernel_shape(self.kernel, self.step);
+        self.sinc_table = Self::sinc_table(self.quality);
+
+        // make room for any extra samples a wider kernel reads before the read position
+        if self.half_len > previous_half_len && !self.history.is_empty() {
+            let num_new_samples = self.half_len - previous_half_len;
+            self.history
+                .splice(0..0, std::iter::repeat_n(0.0, num_new_samples));
+            self.index += num_new_samples;
+        }
+    }
+
+    fn step(input_sample_rate: SampleRate, output_sample_rate: SampleRate) -> f64 {
+        let input_sample_rate = input_sample_rate.get();
+        let output_sample_rate = output_sample_rate.get();
+        if input_sample_rate == 0 || output_sample_rate == 0 {
+            1.0
+        } else {
+            input_sample_rate as f64 / output_sample_rate as f64
+        }
+    }
+
+    /// Low-pass cutoff and number of input samples read on each side of the read position
+    fn kernel_shape(kernel: ResampleKernel, step: f64) -> (f64, usize) {
+        match kernel {
+            ResampleKernel::Linear => (1.0, 1),
+            ResampleKernel::Cubic => (1.0, 2),
+            ResampleKernel::Sinc {
+                zero_crossings,
+                rolloff,
+            } => {
+                // when downsampling, the cutoff must drop below the *output's* Nyquist
+                // frequency, which widens the kernel by the same amount
+                let cutoff = rolloff * (1.0 / step).min(1.0);
+                (cutoff, (zero_crossings as f64 / cutoff).ceil() as usize)
+            }
+        }
+    }
+
+    /// Sinc tables are shared between all resamplers and only ever built once
+    fn sinc_table(quality: ResampleQuality) -> &'static [f32] {
+        static HIGH_SINC_TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+        static BEST_SINC_TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+
+        let table = match quality {
+            ResampleQuality::Fast | ResampleQuality::Medium => return &[],
+            ResampleQuality::High => &HIGH_SINC_TABLE,
+            ResampleQuality::Best => &BEST_SINC_TABLE,
+        };
+        let ResampleKernel::Sinc { zero_crossings, .. } = quality.kernel() else {
+            return &[];
+        };
+
+        table.get_or_init(|| Self::build_sinc_table(zero_crossings))
+    }
+
+    fn build_sinc_table(zero_crossings: usize) -> Vec<f32> {
+        let len = zero_crossings * Self::SINC_TABLE_RESOLUTION;
+        (0..=len)
+            .map(|i| {
+                let x = i as f64 / Self::SINC_TABLE_RESOLUTION as f64;
+                let sinc = if i == 0 {
+                    1.0
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                // Blackman window, stretched over the kernel's full width
+                let window_phase = PI * x / zero_crossings as f64;
+                let window = 0.42 + 0.5 * window_phase.cos() + 0.08 * (2.0 * window_phase).cos();
+                (sinc * window) as f32
+            })
+            .collect()
+    }
+
+    /// Drops input samples that have already been read past, but only once
+    /// enough have built up that moving the remaining samples is worth it
+    fn discard_consumed_input(&mut self) {
+        let first_needed_index = (self.index + 1)
+            .saturating_sub(self.half_len)
+            .min(self.history.len());
+        if first_needed_index > 0 && first_needed_index * 2 >= self.history.len() {
+            self.history.drain(..first_needed_index);
+            self.index -= first_needed_index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_resampler {
+    use crate::{resample_samples, ResampleQuality, Resampler};
+
+    #[test]
+    fn it_should_produce_the_same_output_when_streaming_in_blocks() {
+        let input: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.05).sin()).collect();
+        let expected = resample_samples(&input, 44100, 48000, ResampleQuality::High);
+
+        let mut resampler = Resampler::new(44100, 48000, ResampleQuality::High);
+        let mut output = Vec::new();
+        input
+            .chunks(37)
+            .for_each(|block| resampler.process(block, &mut output));
+
+        // the final samples are still waiting on input beyond the end of the stream
+        assert!(output.len() < expected.len());
+        assert!(output.len() > expected.len() - resampler.latency() * 2);
+        output
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(sample, expected)| assert!((sample - expected).abs() < 0.0001));
+    }
+
+    #[test]
+    fn it_should_wait_for_enough_input() {
+        let mut resampler = Resampler::new(44100, 44100, ResampleQuality::Medium);
+        assert!(resampler.needs_input());
+        assert_eq!(resampler.next_sample(), None);
+
+        resampler.push(&[0.5, 0.25]);
+        assert_eq!(resampler.next_sample(), None);
+
+        resampler.push_sample(0.0);
+        assert_eq!(resampler.next_sample(), Some(0.5));
+        assert_eq!(resampler.next_sample(), None);
+    }
+
+    #[test]
+    fn it_should_not_reallocate_within_reserved_latency() {
+        let mut resampler = Resampler::new(44100, 44100, ResampleQuality::Fast);
+        resampler.reserve_latency(Resampler::latency_for(96000, 44100, ResampleQuality::Best));
+        let history = resampler.history.as_ptr();
+
+        let mut output = Vec::new();
+        for i in 0..1000 {
+            if i == 100 {
+                resampler.set_quality(ResampleQuality::Best);
+            }
+            if i == 500 {
+                resampler.set_input_sample_rate(96000);
+            }
+            resampler.push_sample(1.0);
+            while let Some(sample) = resampler.next_sample() {
+                output.push(sample);
+            }
+        }
+
+        assert_eq!(
+            resampler.latency(),
+            Resampler::latency_for(96000, 44100, ResampleQuality::Best)
+        );
+        assert_eq!(resampler.history.as_ptr(), history);
+    }
+
+    #[test]
+    fn it_should_keep_streaming_when_sample_rate_changes() {
+        let mut resampler = Resampler::new(48000, 48000, ResampleQuality::High);
+        let mut output = Vec::new();
+        resampler.process(&[1.0; 200], &mut output);
+
+        // downsampling widens the kernel
+        resampler.set_output_sample_rate(24000);
+        resampler.process(&[1.0; 500], &mut output);
+
+        assert!(output.len() > 200);
+        // steady DC input is preserved
+        output[40..]
+            .iter()
+            .for_each(|sample| assert!((sample - 1.0).abs() < 0.001));
+    }
+}
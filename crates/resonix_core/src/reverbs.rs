@@ -0,0 +1,5 @@
+mod all_pass_filter;
+mod comb_filter;
+mod reverb;
+
+pub use reverb::*;
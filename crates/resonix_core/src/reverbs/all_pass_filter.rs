@@ -0,0 +1,43 @@
+use crate::{
+    reverbs::comb_filter::{scale_tuning, MAX_SAMPLE_RATE},
+    DelayLine, SampleRate,
+};
+
+/// A Schroeder all-pass filter, which smears a signal out over time
+/// (without changing its frequency content) to make echoes more dense
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct AllPassFilter {
+    delay_line: DelayLine,
+    /// Delay length (in samples) at the reference sample rate
+    tuning: usize,
+    delay: usize,
+}
+
+impl AllPassFilter {
+    const FEEDBACK: f32 = 0.5;
+
+    pub(crate) fn new(tuning: usize, sample_rate: SampleRate) -> Self {
+        let mut all_pass_filter = Self {
+            delay_line: DelayLine::new(scale_tuning(tuning, SampleRate::from(MAX_SAMPLE_RATE))),
+            tuning,
+            delay: tuning,
+        };
+        all_pass_filter.set_sample_rate(sample_rate);
+        all_pass_filter
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: SampleRate) {
+        self.delay = scale_tuning(self.tuning, sample_rate).clamp(1, self.delay_line.max_delay());
+    }
+
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.delay_line.sample(self.delay);
+        self.delay_line.write(input + delayed * Self::FEEDBACK);
+        delayed - input
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.delay_line.clear();
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{DelayLine, SampleRate};
+
+/// A feedback comb filter with a low-pass filter in its feedback path,
+/// which makes high frequencies die out faster than low frequencies
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct CombFilter {
+    delay_line: DelayLine,
+    /// Delay length (in samples) at the reference sample rate
+    tuning: usize,
+    delay: usize,
+    filter_store: f32,
+}
+
+impl CombFilter {
+    pub(crate) fn new(tuning: usize, sample_rate: SampleRate) -> Self {
+        let mut comb_filter = Self {
+            delay_line: DelayLine::new(scale_tuning(tuning, SampleRate::from(MAX_SAMPLE_RATE))),
+            tuning,
+            delay: tuning,
+            filter_store: 0.0,
+        };
+        comb_filter.set_sample_rate(sample_rate);
+        comb_filter
+    }
+
+    pub(crate) fn set_sample_rate(&mut self, sample_rate: SampleRate) {
+        self.delay = scale_tuning(self.tuning, sample_rate).clamp(1, self.delay_line.max_delay());
+    }
+
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.delay_line.sample(self.delay);
+        self.filter_store = output * (1.0 - damping) + self.filter_store * damping;
+        // flush values too small to hear, which are very slow to calculate with
+        if self.filter_store.abs() < f32::MIN_POSITIVE {
+            self.filter_store = 0.0;
+        }
+        self.delay_line.write(input + self.filter_store * feedback);
+        output
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.delay_line.clear();
+        self.filter_store = 0.0;
+    }
+}
+
+/// Delay lines are allocated with enough room for this sample rate
+pub(crate) const MAX_SAMPLE_RATE: u32 = 96000;
+
+/// The sample rate that delay tunings are given at
+pub(crate) const TUNING_SAMPLE_RATE: u32 = 44100;
+
+/// Converts a delay length at the `TUNING_SAMPLE_RATE` to a delay length at `sample_rate`
+pub(crate) fn scale_tuning(tuning: usize, sample_rate: SampleRate) -> usize {
+    (tuning as f64 * sample_rate.get() as f64 / TUNING_SAMPLE_RATE as f64).round() as usize
+}
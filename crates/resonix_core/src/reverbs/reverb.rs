@@ -0,0 +1,350 @@
+use std::time::Duration;
+
+use crate::{
+    reverbs::{
+        all_pass_filter::AllPassFilter,
+        comb_filter::{CombFilter, MAX_SAMPLE_RATE},
+    },
+    DelayLine, Interpolation, NumChannels, SampleRate,
+};
+
+/// The comb and all-pass filters that produce one channel of reverb
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+struct ReverbChannel {
+    comb_filters: Vec<CombFilter>,
+    all_pass_filters: Vec<AllPassFilter>,
+}
+
+impl ReverbChannel {
+    fn new(spread: usize, sample_rate: SampleRate) -> Self {
+        Self {
+            comb_filters: Reverb::COMB_TUNINGS
+                .iter()
+                .map(|tuning| CombFilter::new(tuning + spread, sample_rate))
+                .collect(),
+            all_pass_filters: Reverb::ALL_PASS_TUNINGS
+                .iter()
+                .map(|tuning| AllPassFilter::new(tuning + spread, sample_rate))
+                .collect(),
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let comb_sum = self
+            .comb_filters
+            .iter_mut()
+            .map(|comb_filter| comb_filter.process(input, feedback, damping))
+            .sum();
+
+        self.all_pass_filters
+            .iter_mut()
+            .fold(comb_sum, |sample, all_pass_filter| {
+                all_pass_filter.process(sample)
+            })
+    }
+}
+
+/// A multichannel algorithmic reverb, based on Jezar's public domain "Freeverb".
+///
+/// Every channel of input is mixed together and sent through a separate bank of
+/// parallel comb filters and series all-pass filters for each channel. Each bank's
+/// delay lengths are slightly offset, which makes the channels sound wide and uncorrelated.
+///
+/// The reverb contains no randomness, so the same input always produces the same output.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Reverb {
+    sample_rate: SampleRate,
+    room_size: f32,
+    damping: f32,
+    width: f32,
+    wet: f32,
+    dry: f32,
+    pre_delay: Duration,
+    pre_delay_line: DelayLine,
+    channels: Vec<ReverbChannel>,
+    /// The most recent output of each channel's filters (before mixing)
+    reverb_frame: Vec<f32>,
+}
+
+impl Reverb {
+    pub const DEFAULT_ROOM_SIZE: f32 = 0.5;
+    pub const DEFAULT_DAMPING: f32 = 0.5;
+    pub const DEFAULT_WIDTH: f32 = 1.0;
+    pub const DEFAULT_WET: f32 = 0.33;
+    pub const DEFAULT_DRY: f32 = 1.0;
+
+    /// Memory for this much pre-delay is allocated up front
+    pub const MAX_PRE_DELAY: Duration = Duration::from_millis(500);
+
+    /// Comb filter delay lengths (in samples at 44.1 kHz)
+    const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+
+    /// All-pass filter delay lengths (in samples at 44.1 kHz)
+    const ALL_PASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+
+    /// Offset added to every delay length for each successive channel (in samples at 44.1 kHz)
+    const CHANNEL_SPREAD: usize = 23;
+
+    /// Keeps the sum of all the comb filters from clipping
+    const INPUT_GAIN: f32 = 0.015;
+
+    pub fn new(num_channels: impl Into<NumChannels>, sample_rate: impl Into<SampleRate>) -> Self {
+        let sample_rate = sample_rate.into();
+        let num_channels = *num_channels.into();
+        let max_pre_delay = (Self::MAX_PRE_DELAY.as_secs_f64() * MAX_SAMPLE_RATE as f64) as usize;
+        Self {
+            sample_rate,
+            room_size: Self::DEFAULT_ROOM_SIZE,
+            damping: Self::DEFAULT_DAMPING,
+            width: Self::DEFAULT_WIDTH,
+            wet: Self::DEFAULT_WET,
+            dry: Self::DEFAULT_DRY,
+            pre_delay: Duration::ZERO,
+            pre_delay_line: DelayLine::new(max_pre_delay),
+            channels: (0..num_channels)
+                .map(|i| ReverbChannel::new(i * Self::CHANNEL_SPREAD, sample_rate))
+                .collect(),
+            reverb_frame: vec![0.0; num_channels],
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.channels.len())
+    }
+
+    pub fn room_size(&self) -> f32 {
+        self.room_size
+    }
+
+    /// Larger rooms (from 0.0 to 1.0) ring out for longer
+    pub fn set_room_size(&mut self, room_size: f32) -> &mut Self {
+        self.room_size = room_size.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn damping(&self) -> f32 {
+        self.damping
+    }
+
+    /// Higher damping (from 0.0 to 1.0) makes high frequencies die out faster
+    pub fn set_damping(&mut self, damping: f32) -> &mut Self {
+        self.damping = damping.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Stereo width of the reverb (from 0.0 to 1.0): at 0.0, every channel of reverb is the same
+    pub fn set_width(&mut self, width: f32) -> &mut Self {
+        self.width = width.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Gain of the reverb in the output
+    pub fn set_wet(&mut self, wet: f32) -> &mut Self {
+        self.wet = wet;
+        self
+    }
+
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Gain of the original signal in the output
+    pub fn set_dry(&mut self, dry: f32) -> &mut Self {
+        self.dry = dry;
+        self
+    }
+
+    pub fn pre_delay(&self) -> Duration {
+        self.pre_delay
+    }
+
+    /// Time before the reverb starts (clamped to `MAX_PRE_DELAY`)
+    pub fn set_pre_delay(&mut self, pre_delay: Duration) -> &mut Self {
+        self.pre_delay = pre_delay.min(Self::MAX_PRE_DELAY);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self.channels.iter_mut().for_each(|channel| {
+            channel
+                .comb_filters
+                .iter_mut()
+                .for_each(|comb_filter| comb_filter.set_sample_rate(self.sample_rate));
+            channel
+                .all_pass_filters
+                .iter_mut()
+                .for_each(|all_pass_filter| all_pass_filter.set_sample_rate(self.sample_rate));
+        });
+        self
+    }
+
+    /// Silences the reverb's tail
+    pub fn clear(&mut self) -> &mut Self {
+        self.pre_delay_line.clear();
+        self.channels.iter_mut().for_each(|channel| {
+            channel.comb_filters.iter_mut().for_each(CombFilter::clear);
+            channel
+                .all_pass_filters
+                .iter_mut()
+                .for_each(AllPassFilter::clear);
+        });
+        self
+    }
+
+    /// Adds reverb to one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        let input = frame.iter().sum::<f32>() * Self::INPUT_GAIN;
+
+        let pre_delay = self.pre_delay.as_secs_f32() * self.sample_rate.get() as f32;
+        // the input is always written, so the pre-delay line is already filled
+        // with recent audio when the pre-delay is increased
+        let delayed_input = if pre_delay >= 1.0 {
+            self.pre_delay_line.read(pre_delay, Interpolation::Nearest)
+        } else {
+            input
+        };
+        self.pre_delay_line.write(input);
+        let input = delayed_input;
+
+        let feedback = self.room_size * 0.28 + 0.7;
+        let damping = self.damping * 0.4;
+
+        // each channel is mixed with the other channels, which narrows the reverb
+        // as `width` decreases (at 0.0, every channel is the average of all channels)
+        let num_channels = self.channels.len().max(1) as f32;
+        let others_gain = self.wet * (1.0 - self.width) / num_channels;
+        let own_gain = self.wet * self.width + others_gain;
+
+        let mut reverb_sum = 0.0;
+        self.channels
+            .iter_mut()
+            .zip(self.reverb_frame.iter_mut())
+            .for_each(|(channel, reverb)| {
+                *reverb = channel.process(input, feedback, damping);
+                reverb_sum += *reverb;
+            });
+
+        frame
+            .iter_mut()
+            .zip(self.reverb_frame.iter())
+            .for_each(|(sample, reverb)| {
+                *sample =
+                    *sample * self.dry + reverb * own_gain + (reverb_sum - reverb) * others_gain;
+            });
+    }
+}
+
+#[cfg(test)]
+mod test_reverb {
+    use std::time::Duration;
+
+    use crate::Reverb;
+
+    fn impulse_response(reverb: &mut Reverb, num_frames: usize) -> Vec<Vec<f32>> {
+        let num_channels = *reverb.num_channels();
+        (0..num_frames)
+            .map(|i| {
+                let mut frame = vec![if i == 0 { 1.0 } else { 0.0 }; num_channels];
+                reverb.process_frame(&mut frame);
+                frame
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_should_be_deterministic() {
+        let mut first = Reverb::new(2, 44100);
+        let mut second = Reverb::new(2, 44100);
+
+        assert_eq!(
+            impulse_response(&mut first, 5000),
+            impulse_response(&mut second, 5000)
+        );
+    }
+
+    #[test]
+    fn it_should_start_after_pre_delay() {
+        let mut reverb = Reverb::new(1, 44100);
+        reverb.set_dry(0.0);
+        let without_pre_delay = impulse_response(&mut reverb, 3000);
+
+        reverb.clear().set_pre_delay(Duration::from_millis(10));
+        let with_pre_delay = impulse_response(&mut reverb, 3000);
+
+        // the first echo arrives after the shortest comb filter delay
+        let first_echo = |response: &[Vec<f32>]| response.iter().position(|frame| frame[0] != 0.0);
+        assert_eq!(first_echo(&without_pre_delay), Some(1116));
+        assert_eq!(first_echo(&with_pre_delay), Some(1116 + 441));
+    }
+
+    #[test]
+    fn it_should_delay_recent_input_when_pre_delay_is_increased() {
+        let mut reverb = Reverb::new(1, 44100);
+        reverb.set_dry(0.0);
+
+        // the impulse is processed without pre-delay, then repeated once the pre-delay
+        // reaches back to it
+        let mut frame = [1.0];
+        reverb.process_frame(&mut frame);
+        reverb.set_pre_delay(Duration::from_millis(10));
+        let response: Vec<f32> = (1..3000)
+            .map(|_| {
+                let mut frame = [0.0];
+                reverb.process_frame(&mut frame);
+                frame[0]
+            })
+            .collect();
+
+        let mut without_pre_delay = Reverb::new(1, 44100);
+        without_pre_delay.set_dry(0.0);
+        let expected = impulse_response(&mut without_pre_delay, 3000);
+
+        assert_eq!(response[..1116 + 440], expected[1..1116 + 441].concat());
+        assert_ne!(response[1116 + 440], expected[1116 + 441][0]);
+    }
+
+    #[test]
+    fn it_should_decorrelate_channels_according_to_width() {
+        let mut reverb = Reverb::new(3, 44100);
+        reverb.set_dry(0.0);
+        let wide = impulse_response(&mut reverb, 3000);
+        assert!(wide
+            .iter()
+            .any(|frame| frame[0] != frame[1] && frame[1] != frame[2]));
+
+        reverb.clear().set_width(0.0);
+        let narrow = impulse_response(&mut reverb, 3000);
+        narrow.iter().for_each(|frame| {
+            assert!((frame[0] - frame[1]).abs() < 0.000001);
+            assert!((frame[1] - frame[2]).abs() < 0.000001);
+        });
+    }
+
+    #[test]
+    fn it_should_ring_longer_in_larger_rooms() {
+        let energy = |room_size: f32| {
+            let mut reverb = Reverb::new(1, 44100);
+            reverb.set_dry(0.0).set_room_size(room_size);
+            impulse_response(&mut reverb, 44100)[22050..]
+                .iter()
+                .map(|frame| frame[0] * frame[0])
+                .sum::<f32>()
+        };
+
+        assert!(energy(0.9) > energy(0.1) * 10.0);
+    }
+}
@@ -0,0 +1,8 @@
+mod oversampler;
+mod oversampling;
+mod shaping_curve;
+mod wave_shaper;
+
+pub use oversampling::*;
+pub use shaping_curve::*;
+pub use wave_shaper::*;
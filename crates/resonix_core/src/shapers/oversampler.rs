@@ -0,0 +1,98 @@
+use std::f32::consts::{PI, TAU};
+
+use crate::Oversampling;
+
+/// Runs a function at a multiple of the sample rate for a single channel.
+///
+/// Incoming samples are upsampled with linear interpolation, and the results are filtered
+/// with a windowed-sinc lowpass filter before being decimated back down to the original rate.
+///
+/// All memory is allocated up front for the highest oversampling factor.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub(crate) struct Oversampler {
+    factor: usize,
+    previous_input: f32,
+    coefficients: Vec<f32>,
+    /// The most recent oversampled results (a circular buffer)
+    history: Vec<f32>,
+    write_index: usize,
+}
+
+impl Oversampler {
+    /// Number of filter taps for every step of the oversampling factor
+    const TAPS_PER_FACTOR: usize = 16;
+
+    /// Cutoff of the decimation filter, relative to the original Nyquist frequency
+    const CUTOFF: f32 = 0.9;
+
+    const MAX_TAPS: usize = Self::TAPS_PER_FACTOR * 4 - 1;
+
+    pub(crate) fn new(oversampling: Oversampling) -> Self {
+        let mut oversampler = Self {
+            factor: 1,
+            previous_input: 0.0,
+            coefficients: Vec::with_capacity(Self::MAX_TAPS),
+            history: vec![0.0; Self::MAX_TAPS],
+            write_index: 0,
+        };
+        oversampler.set_oversampling(oversampling);
+        oversampler
+    }
+
+    pub(crate) fn set_oversampling(&mut self, oversampling: Oversampling) {
+        self.factor = oversampling.factor();
+        let num_taps = Self::TAPS_PER_FACTOR * self.factor - 1;
+        let center = (num_taps - 1) as f32 / 2.0;
+        // cutoff in cycles per (oversampled) sample
+        let cutoff = Self::CUTOFF * 0.5 / self.factor as f32;
+
+        // Blackman-windowed sinc
+        self.coefficients.clear();
+        self.coefficients.extend((0..num_taps).map(|i| {
+            let x = i as f32 - center;
+            let sinc = if x == 0.0 {
+                2.0 * cutoff
+            } else {
+                (TAU * cutoff * x).sin() / (PI * x)
+            };
+            let window_position = i as f32 / (num_taps - 1) as f32;
+            let window = 0.42 - 0.5 * (TAU * window_position).cos()
+                + 0.08 * (2.0 * TAU * window_position).cos();
+            sinc * window
+        }));
+        let sum: f32 = self.coefficients.iter().sum();
+        self.coefficients
+            .iter_mut()
+            .for_each(|coefficient| *coefficient /= sum);
+
+        self.clear();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.previous_input = 0.0;
+        self.history.fill(0.0);
+        self.write_index = 0;
+    }
+
+    /// Runs `f` on `factor` upsampled versions of `input` and returns the decimated result
+    #[inline]
+    pub(crate) fn process(&mut self, input: f32, f: impl Fn(f32) -> f32) -> f32 {
+        let num_taps = self.coefficients.len();
+
+        for step in 1..=self.factor {
+            let progress = step as f32 / self.factor as f32;
+            let upsampled = self.previous_input + (input - self.previous_input) * progress;
+            self.history[self.write_index] = f(upsampled);
+            self.write_index = (self.write_index + 1) % num_taps;
+        }
+        self.previous_input = input;
+
+        // the filter is symmetric, so the order of the history doesn't matter
+        // as long as each sample lines up with its coefficient
+        self.coefficients
+            .iter()
+            .enumerate()
+            .map(|(i, coefficient)| coefficient * self.history[(self.write_index + i) % num_taps])
+            .sum()
+    }
+}
@@ -0,0 +1,22 @@
+/// How many times faster than the sample rate a `WaveShaper` runs its curve.
+///
+/// Shaping a signal creates new harmonics, and any harmonics above the Nyquist frequency
+/// fold back down as (usually inharmonic) aliasing. Oversampling moves the Nyquist frequency
+/// higher while shaping, so that those harmonics can be filtered out instead.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Oversampling {
+    #[default]
+    None,
+    X2,
+    X4,
+}
+
+impl Oversampling {
+    pub fn factor(&self) -> usize {
+        match self {
+            Oversampling::None => 1,
+            Oversampling::X2 => 2,
+            Oversampling::X4 => 4,
+        }
+    }
+}
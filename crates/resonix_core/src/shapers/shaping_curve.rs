@@ -0,0 +1,80 @@
+/// A transfer function that maps each input sample to an output sample
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+pub enum ShapingCurve {
+    /// Smoothly saturates towards ±1.0 using `tanh`
+    #[default]
+    SoftClip,
+    /// Cuts off everything beyond ±1.0
+    HardClip,
+    /// Reflects everything beyond ±1.0 back towards 0.0, which adds lots of bright harmonics
+    Foldback,
+    /// Reduces the amplitude resolution to the given number of bits
+    Bitcrush { bits: u32 },
+    /// A lookup table that is stretched evenly across inputs from -1.0 to 1.0 (like
+    /// the Web Audio API's `WaveShaperNode` curve). Values in between table entries are
+    /// linearly interpolated, and inputs beyond ±1.0 use the first/last entry.
+    Custom(Vec<f32>),
+}
+
+impl ShapingCurve {
+    #[inline]
+    pub fn apply(&self, sample: f32) -> f32 {
+        match self {
+            ShapingCurve::SoftClip => sample.tanh(),
+            ShapingCurve::HardClip => sample.clamp(-1.0, 1.0),
+            ShapingCurve::Foldback => 1.0 - ((sample + 1.0).rem_euclid(4.0) - 2.0).abs(),
+            ShapingCurve::Bitcrush { bits } => {
+                let num_levels = 2.0f32.powi(bits.saturating_sub(1).min(31) as i32);
+                (sample * num_levels).round() / num_levels
+            }
+            ShapingCurve::Custom(curve) => match curve.len() {
+                0 => sample,
+                1 => curve[0],
+                len => {
+                    let position = (sample.clamp(-1.0, 1.0) + 1.0) / 2.0 * (len - 1) as f32;
+                    let index = (position.floor() as usize).min(len - 2);
+                    let fraction = position - index as f32;
+                    curve[index] + (curve[index + 1] - curve[index]) * fraction
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_shaping_curve {
+    use crate::ShapingCurve;
+
+    #[test]
+    fn it_should_clip() {
+        assert_eq!(ShapingCurve::SoftClip.apply(0.0), 0.0);
+        assert!((ShapingCurve::SoftClip.apply(10.0) - 1.0).abs() < 0.0001);
+        assert_eq!(ShapingCurve::HardClip.apply(-3.0), -1.0);
+        assert_eq!(ShapingCurve::HardClip.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn it_should_fold_back() {
+        let output: Vec<f32> = [0.0, 0.5, 1.0, 1.5, 3.0, -1.25]
+            .into_iter()
+            .map(|sample| ShapingCurve::Foldback.apply(sample))
+            .collect();
+        assert_eq!(output, vec![0.0, 0.5, 1.0, 0.5, -1.0, -0.75]);
+    }
+
+    #[test]
+    fn it_should_bitcrush() {
+        let curve = ShapingCurve::Bitcrush { bits: 3 };
+        assert_eq!(curve.apply(0.3), 0.25);
+        assert_eq!(curve.apply(-0.6), -0.5);
+    }
+
+    #[test]
+    fn it_should_interpolate_custom_curve() {
+        let curve = ShapingCurve::Custom(vec![-1.0, 0.0, 0.5]);
+        assert_eq!(curve.apply(-1.0), -1.0);
+        assert_eq!(curve.apply(-0.5), -0.5);
+        assert_eq!(curve.apply(0.5), 0.25);
+        assert_eq!(curve.apply(2.0), 0.5);
+    }
+}
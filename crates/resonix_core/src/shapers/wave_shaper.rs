@@ -0,0 +1,213 @@
+use crate::{shapers::oversampler::Oversampler, NumChannels, Oversampling, ShapingCurve};
+
+/// Distorts a multichannel signal by passing every sample through a `ShapingCurve`.
+///
+/// The signal is multiplied by `drive` before it is shaped, so higher drive pushes more
+/// of the signal into the nonlinear parts of the curve.
+///
+/// Sample rate reduction holds every Nth input sample for N samples before shaping,
+/// which (together with `ShapingCurve::Bitcrush`) gives classic "bitcrusher" sounds.
+///
+/// With oversampling, the curve is run at a multiple of the sample rate and the result is
+/// lowpass filtered, which greatly reduces aliasing at the cost of a few samples of latency.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct WaveShaper {
+    curve: ShapingCurve,
+    drive: f32,
+    oversampling: Oversampling,
+    sample_rate_reduction: usize,
+    /// Counts up to `sample_rate_reduction` before a new input sample is held
+    hold_counter: usize,
+    /// One held sample for every channel
+    held_samples: Vec<f32>,
+    /// One oversampler for every channel
+    oversamplers: Vec<Oversampler>,
+}
+
+impl WaveShaper {
+    pub const DEFAULT_DRIVE: f32 = 1.0;
+    pub const DEFAULT_SAMPLE_RATE_REDUCTION: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>, curve: ShapingCurve) -> Self {
+        let num_channels = *num_channels.into();
+        Self {
+            curve,
+            drive: Self::DEFAULT_DRIVE,
+            oversampling: Oversampling::default(),
+            sample_rate_reduction: Self::DEFAULT_SAMPLE_RATE_REDUCTION,
+            hold_counter: 0,
+            held_samples: vec![0.0; num_channels],
+            oversamplers: vec![Oversampler::new(Oversampling::default()); num_channels],
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.held_samples.len())
+    }
+
+    pub fn curve(&self) -> &ShapingCurve {
+        &self.curve
+    }
+
+    pub fn set_curve(&mut self, curve: ShapingCurve) -> &mut Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Linear gain applied to the signal before it is shaped
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    pub fn set_drive(&mut self, drive: f32) -> &mut Self {
+        self.drive = drive;
+        self
+    }
+
+    pub fn oversampling(&self) -> Oversampling {
+        self.oversampling
+    }
+
+    /// Clears any oversampling history when the oversampling changes
+    pub fn set_oversampling(&mut self, oversampling: Oversampling) -> &mut Self {
+        if self.oversampling != oversampling {
+            self.oversampling = oversampling;
+            self.oversamplers
+                .iter_mut()
+                .for_each(|oversampler| oversampler.set_oversampling(oversampling));
+        }
+        self
+    }
+
+    /// Number of samples that each held input sample lasts for (1 means no reduction)
+    pub fn sample_rate_reduction(&self) -> usize {
+        self.sample_rate_reduction
+    }
+
+    /// Clamped to at least 1 (no reduction)
+    pub fn set_sample_rate_reduction(&mut self, sample_rate_reduction: usize) -> &mut Self {
+        self.sample_rate_reduction = sample_rate_reduction.max(1);
+        self.hold_counter = 0;
+        self
+    }
+
+    /// Resets held samples and oversampling history
+    pub fn clear(&mut self) -> &mut Self {
+        self.hold_counter = 0;
+        self.held_samples.fill(0.0);
+        self.oversamplers.iter_mut().for_each(Oversampler::clear);
+        self
+    }
+
+    /// Shapes one frame of audio in place (with one sample per channel)
+    pub fn process_frame(&mut self, frame: &mut [f32]) {
+        let hold_new_sample = self.hold_counter == 0;
+        self.hold_counter = (self.hold_counter + 1) % self.sample_rate_reduction;
+
+        let curve = &self.curve;
+        let drive = self.drive;
+        let oversampling = self.oversampling;
+
+        frame
+            .iter_mut()
+            .zip(self.held_samples.iter_mut())
+            .zip(self.oversamplers.iter_mut())
+            .for_each(|((sample, held_sample), oversampler)| {
+                if hold_new_sample {
+                    *held_sample = *sample;
+                }
+                let driven = *held_sample * drive;
+                *sample = match oversampling {
+                    Oversampling::None => curve.apply(driven),
+                    _ => oversampler.process(driven, |x| curve.apply(x)),
+                };
+            });
+    }
+}
+
+#[cfg(test)]
+mod test_wave_shaper {
+    use std::f32::consts::TAU;
+
+    use crate::{Oversampling, ShapingCurve, WaveShaper};
+
+    const SAMPLE_RATE: f32 = 44100.0;
+
+    fn render_sine(wave_shaper: &mut WaveShaper, frequency: f32, num_samples: usize) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| {
+                let mut frame = [(TAU * frequency * i as f32 / SAMPLE_RATE).sin()];
+                wave_shaper.process_frame(&mut frame);
+                frame[0]
+            })
+            .collect()
+    }
+
+    /// Magnitude of the given frequency in `samples`
+    fn magnitude(samples: &[f32], frequency: f32) -> f32 {
+        let (re, im) = samples
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, sample)| {
+                let phase = TAU * frequency * i as f32 / SAMPLE_RATE;
+                (re + sample * phase.cos(), im - sample * phase.sin())
+            });
+        (re * re + im * im).sqrt() / samples.len() as f32
+    }
+
+    #[test]
+    fn it_should_apply_drive_before_curve() {
+        let mut wave_shaper = WaveShaper::new(2, ShapingCurve::HardClip);
+        wave_shaper.set_drive(4.0);
+
+        let mut frame = [0.1, -0.5];
+        wave_shaper.process_frame(&mut frame);
+        assert_eq!(frame, [0.4, -1.0]);
+    }
+
+    #[test]
+    fn it_should_reduce_sample_rate() {
+        let mut wave_shaper = WaveShaper::new(1, ShapingCurve::HardClip);
+        wave_shaper.set_sample_rate_reduction(3);
+
+        let output: Vec<f32> = [0.1, 0.2, 0.3, 0.4, 0.5]
+            .into_iter()
+            .map(|sample| {
+                let mut frame = [sample];
+                wave_shaper.process_frame(&mut frame);
+                frame[0]
+            })
+            .collect();
+        assert_eq!(output, vec![0.1, 0.1, 0.1, 0.4, 0.4]);
+    }
+
+    #[test]
+    fn it_should_preserve_low_frequencies_when_oversampling() {
+        let mut wave_shaper = WaveShaper::new(1, ShapingCurve::SoftClip);
+        wave_shaper.set_oversampling(Oversampling::X4);
+
+        let output = render_sine(&mut wave_shaper, 100.0, 4410);
+
+        // a unit sine would have a magnitude of 0.5, and tanh only slightly compresses the peaks
+        let fundamental = magnitude(&output, 100.0);
+        assert!(fundamental > 0.35 && fundamental < 0.5, "{fundamental}");
+        assert!(output
+            .iter()
+            .all(|sample| sample.abs() <= 1.0f32.tanh() + 0.01));
+    }
+
+    #[test]
+    fn it_should_reduce_aliasing_when_oversampling() {
+        // the 5th harmonic of a hard clipped 10 kHz sine (50 kHz) aliases down to 5.9 kHz
+        let alias = 50000.0 - SAMPLE_RATE;
+
+        let mut wave_shaper = WaveShaper::new(1, ShapingCurve::HardClip);
+        wave_shaper.set_drive(4.0);
+        let aliased = magnitude(&render_sine(&mut wave_shaper, 10000.0, 4410), alias);
+
+        wave_shaper.set_oversampling(Oversampling::X4);
+        let oversampled = magnitude(&render_sine(&mut wave_shaper, 10000.0, 4410), alias);
+
+        assert!(oversampled < aliased / 4.0, "{oversampled} vs {aliased}");
+    }
+}
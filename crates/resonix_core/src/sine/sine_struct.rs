@@ -1,6 +1,6 @@
 use std::f32::consts::PI;
 
-use crate::{SampleRate, SineInterface};
+use crate::{OscillatorInterface, SampleRate, SineInterface};
 
 const ZERO_SAMPLE_RATE: SampleRate = SampleRate::new_const(0);
 
@@ -73,6 +73,17 @@ impl SineInterface for Sine {
     }
 }
 
+impl OscillatorInterface for Sine {
+    fn phase(&self) -> f32 {
+        (self.phase / TWO_PI).rem_euclid(1.0)
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.phase = phase.rem_euclid(1.0) * TWO_PI;
+        self
+    }
+}
+
 impl Sine {
     pub fn new() -> Self {
         Self {
@@ -111,7 +122,7 @@ impl Sine {
 mod test_sine {
     use resonix_test_utils::assert_difference_is_within_tolerance;
 
-    use crate::{Sine, SineInterface};
+    use crate::{OscillatorInterface, Sine, SineInterface};
 
     #[test]
     fn it_should_produce_sine_values_at_given_frequency() {
@@ -156,6 +167,17 @@ mod test_sine {
         assert_difference_is_within_tolerance(sine.next_sample(), 0.0, ASSERTION_TOLERANCE);
     }
 
+    #[test]
+    fn it_should_reset_phase() {
+        let mut sine = Sine::new_with_config(4u32, 1.0);
+        sine.next_sample();
+        assert_difference_is_within_tolerance(sine.phase(), 0.25, 0.00001);
+
+        sine.reset_phase();
+        assert_eq!(sine.phase(), 0.0);
+        assert_eq!(sine.next_sample(), 0.0);
+    }
+
     #[test]
     fn it_should_work_when_sample_rate_is_0() {
         const SAMPLE_RATE: u32 = 0;
@@ -0,0 +1,5 @@
+mod spectrum_analyser;
+mod window_function;
+
+pub use spectrum_analyser::*;
+pub use window_function::*;
@@ -0,0 +1,342 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rustfft::{num_complex::Complex, Fft, FftPlanner};
+
+use crate::{Decibel, WindowFunction};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum SpectrumAnalyserError {
+    #[error("FFT size must be a power of 2 between {min} and {max}. Received {0}", min = SpectrumAnalyser::MIN_FFT_SIZE, max = SpectrumAnalyser::MAX_FFT_SIZE)]
+    InvalidFftSize(usize),
+    #[error("Smoothing time constant must be between 0.0 and 1.0. Received {0}")]
+    InvalidSmoothingTimeConstant(f32),
+    #[error(
+        "Minimum decibels ({min_decibels}) must be less than maximum decibels ({max_decibels})"
+    )]
+    InvalidDecibelRange {
+        min_decibels: f32,
+        max_decibels: f32,
+    },
+}
+
+/// Calculates the frequency spectrum of blocks of time-domain samples.
+///
+/// Each call to `analyse` windows the given samples, takes their FFT,
+/// and blends the resulting magnitudes with those of previous calls according
+/// to the `smoothing_time_constant` (0.0 is no smoothing, 1.0 never changes).
+///
+/// Magnitudes can then be read as linear values, in dBFS, or normalized
+/// between `min_decibels` and `max_decibels` (useful for drawing).
+#[derive(Clone)]
+pub struct SpectrumAnalyser {
+    fft_size: usize,
+    window_function: WindowFunction,
+    window_coefficients: Vec<f32>,
+    smoothing_time_constant: f32,
+    min_decibels: f32,
+    max_decibels: f32,
+    fft: Arc<dyn Fft<f32>>,
+    fft_buffer: Vec<Complex<f32>>,
+    fft_scratch: Vec<Complex<f32>>,
+    magnitudes: Vec<f32>,
+}
+
+impl SpectrumAnalyser {
+    pub const MIN_FFT_SIZE: usize = 32;
+    pub const MAX_FFT_SIZE: usize = 32768;
+    pub const DEFAULT_FFT_SIZE: usize = 2048;
+    pub const DEFAULT_SMOOTHING_TIME_CONSTANT: f32 = 0.8;
+    pub const DEFAULT_MIN_DECIBELS: f32 = -100.0;
+    pub const DEFAULT_MAX_DECIBELS: f32 = -30.0;
+
+    pub fn new(fft_size: usize) -> Result<Self, SpectrumAnalyserError> {
+        Self::validate_fft_size(fft_size)?;
+
+        let window_function = WindowFunction::default();
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        let scratch_len = fft.get_inplace_scratch_len();
+
+        Ok(Self {
+            fft_size,
+            window_function,
+            window_coefficients: window_function.coefficients(fft_size),
+            smoothing_time_constant: Self::DEFAULT_SMOOTHING_TIME_CONSTANT,
+            min_decibels: Self::DEFAULT_MIN_DECIBELS,
+            max_decibels: Self::DEFAULT_MAX_DECIBELS,
+            fft,
+            fft_buffer: vec![Complex::default(); fft_size],
+            fft_scratch: vec![Complex::default(); scratch_len],
+            magnitudes: vec![0.0; fft_size / 2],
+        })
+    }
+
+    /// Checks that `fft_size` is a power of 2 between `MIN_FFT_SIZE` and `MAX_FFT_SIZE`
+    pub fn validate_fft_size(fft_size: usize) -> Result<(), SpectrumAnalyserError> {
+        if !fft_size.is_power_of_two()
+            || !(Self::MIN_FFT_SIZE..=Self::MAX_FFT_SIZE).contains(&fft_size)
+        {
+            return Err(SpectrumAnalyserError::InvalidFftSize(fft_size));
+        }
+        Ok(())
+    }
+
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Re-plans the FFT for the new size. Clears any previously smoothed magnitudes.
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Result<&mut Self, SpectrumAnalyserError> {
+        if fft_size == self.fft_size {
+            return Ok(self);
+        }
+
+        let mut new_analyser = Self::new(fft_size)?;
+        new_analyser
+            .set_window_function(self.window_function)
+            .set_smoothing_time_constant(self.smoothing_time_constant)?
+            .set_decibel_range(self.min_decibels, self.max_decibels)?;
+        *self = new_analyser;
+
+        Ok(self)
+    }
+
+    /// The number of frequency bins in the resulting spectrum (half the `fft_size`)
+    pub fn frequency_bin_count(&self) -> usize {
+        self.fft_size / 2
+    }
+
+    pub fn window_function(&self) -> WindowFunction {
+        self.window_function
+    }
+
+    pub fn set_window_function(&mut self, window_function: WindowFunction) -> &mut Self {
+        self.window_function = window_function;
+        self.window_coefficients = window_function.coefficients(self.fft_size);
+        self
+    }
+
+    pub fn smoothing_time_constant(&self) -> f32 {
+        self.smoothing_time_constant
+    }
+
+    pub fn set_smoothing_time_constant(
+        &mut self,
+        smoothing_time_constant: f32,
+    ) -> Result<&mut Self, SpectrumAnalyserError> {
+        if !(0.0..=1.0).contains(&smoothing_time_constant) {
+            return Err(SpectrumAnalyserError::InvalidSmoothingTimeConstant(
+                smoothing_time_constant,
+            ));
+        }
+        self.smoothing_time_constant = smoothing_time_constant;
+        Ok(self)
+    }
+
+    pub fn min_decibels(&self) -> f32 {
+        self.min_decibels
+    }
+
+    pub fn max_decibels(&self) -> f32 {
+        self.max_decibels
+    }
+
+    pub fn set_decibel_range(
+        &mut self,
+        min_decibels: f32,
+        max_decibels: f32,
+    ) -> Result<&mut Self, SpectrumAnalyserError> {
+        if min_decibels >= max_decibels {
+            return Err(SpectrumAnalyserError::InvalidDecibelRange {
+                min_decibels,
+                max_decibels,
+            });
+        }
+        self.min_decibels = min_decibels;
+        self.max_decibels = max_decibels;
+        Ok(self)
+    }
+
+    /// Analyses the most recent `fft_size` samples of `time_domain_data`,
+    /// updating the smoothed magnitude spectrum. If fewer than `fft_size`
+    /// samples are provided, the beginning of the block is padded with silence.
+    ///
+    /// Returns the smoothed, linear magnitudes
+    pub fn analyse(&mut self, time_domain_data: &[f32]) -> &[f32] {
+        let num_samples = time_domain_data.len().min(self.fft_size);
+        let padding = self.fft_size - num_samples;
+        let samples = &time_domain_data[(time_domain_data.len() - num_samples)..];
+
+        self.fft_buffer[..padding].fill(Complex::default());
+        self.fft_buffer[padding..]
+            .iter_mut()
+            .zip(samples.iter())
+            .zip(self.window_coefficients[padding..].iter())
+            .for_each(|((bin, sample), coefficient)| {
+                *bin = Complex::new(sample * coefficient, 0.0);
+            });
+
+        self.fft
+            .process_with_scratch(&mut self.fft_buffer, &mut self.fft_scratch);
+
+        let scale = 1.0 / self.fft_size as f32;
+        let smoothing = self.smoothing_time_constant;
+        self.magnitudes
+            .iter_mut()
+            .zip(self.fft_buffer.iter())
+            .for_each(|(magnitude, bin)| {
+                let new_magnitude = bin.norm() * scale;
+                *magnitude = smoothing * *magnitude + (1.0 - smoothing) * new_magnitude;
+                // prevent denormals from accumulating while smoothing silence
+                if !magnitude.is_normal() {
+                    *magnitude = 0.0;
+                }
+            });
+
+        &self.magnitudes
+    }
+
+    /// Smoothed, linear magnitudes from the most recent call to `analyse`
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+
+    /// Writes the smoothed magnitudes in dBFS into `buffer`
+    pub fn decibels_into<'a>(&self, buffer: &'a mut [f32]) -> &'a mut [f32] {
+        buffer
+            .iter_mut()
+            .zip(self.magnitudes.iter())
+            .for_each(|(sample, magnitude)| *sample = Decibel::calculate_full_scale(*magnitude));
+        buffer
+    }
+
+    /// Writes the smoothed magnitudes into `buffer`, scaled so that `min_decibels`
+    /// is 0.0 and `max_decibels` is 1.0 (values outside this range are clamped)
+    pub fn normalized_into<'a>(&self, buffer: &'a mut [f32]) -> &'a mut [f32] {
+        let range = self.max_decibels - self.min_decibels;
+        buffer
+            .iter_mut()
+            .zip(self.magnitudes.iter())
+            .for_each(|(sample, magnitude)| {
+                let decibels = Decibel::calculate_full_scale(*magnitude);
+                *sample = ((decibels - self.min_decibels) / range).clamp(0.0, 1.0);
+            });
+        buffer
+    }
+
+    /// Clears any previously smoothed magnitudes
+    pub fn reset(&mut self) -> &mut Self {
+        self.magnitudes.fill(0.0);
+        self
+    }
+}
+
+impl Default for SpectrumAnalyser {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_FFT_SIZE).expect("Default FFT size should be valid")
+    }
+}
+
+impl Debug for SpectrumAnalyser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpectrumAnalyser")
+            .field("fft_size", &self.fft_size)
+            .field("window_function", &self.window_function)
+            .field("smoothing_time_constant", &self.smoothing_time_constant)
+            .field("min_decibels", &self.min_decibels)
+            .field("max_decibels", &self.max_decibels)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test_spectrum_analyser {
+    use std::f32::consts::TAU;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{SpectrumAnalyser, SpectrumAnalyserError, WindowFunction};
+
+    #[test]
+    fn it_should_reject_invalid_fft_sizes() {
+        assert_eq!(
+            SpectrumAnalyser::new(100).unwrap_err(),
+            SpectrumAnalyserError::InvalidFftSize(100)
+        );
+        assert!(SpectrumAnalyser::new(16).is_err());
+        assert!(SpectrumAnalyser::new(65536).is_err());
+        assert!(SpectrumAnalyser::new(1024).is_ok());
+    }
+
+    #[test]
+    fn it_should_find_peak_at_frequency_of_sine_wave() {
+        const FFT_SIZE: usize = 1024;
+        const BIN: usize = 64;
+
+        let mut analyser = SpectrumAnalyser::new(FFT_SIZE).unwrap();
+        analyser.set_smoothing_time_constant(0.0).unwrap();
+
+        let samples: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| (TAU * BIN as f32 * i as f32 / FFT_SIZE as f32).sin())
+            .collect();
+
+        let magnitudes = analyser.analyse(&samples);
+        let peak_bin = crate::peak_amplitude(magnitudes).unwrap().buffer_index;
+
+        assert_eq!(peak_bin, BIN);
+    }
+
+    #[test]
+    fn it_should_measure_amplitude_without_window() {
+        const FFT_SIZE: usize = 256;
+        const BIN: usize = 8;
+
+        let mut analyser = SpectrumAnalyser::new(FFT_SIZE).unwrap();
+        analyser
+            .set_window_function(WindowFunction::Rectangular)
+            .set_smoothing_time_constant(0.0)
+            .unwrap();
+
+        let samples: Vec<f32> = (0..FFT_SIZE)
+            .map(|i| (TAU * BIN as f32 * i as f32 / FFT_SIZE as f32).sin())
+            .collect();
+
+        // a full-scale sine splits its energy between positive and negative frequencies
+        let magnitudes = analyser.analyse(&samples);
+        assert_difference_is_within_tolerance(magnitudes[BIN], 0.5, 0.0001);
+    }
+
+    #[test]
+    fn it_should_smooth_between_analyses() {
+        const FFT_SIZE: usize = 64;
+
+        let mut analyser = SpectrumAnalyser::new(FFT_SIZE).unwrap();
+        analyser
+            .set_window_function(WindowFunction::Rectangular)
+            .set_smoothing_time_constant(0.5)
+            .unwrap();
+
+        let dc = vec![1.0; FFT_SIZE];
+        assert_difference_is_within_tolerance(analyser.analyse(&dc)[0], 0.5, 0.0001);
+        assert_difference_is_within_tolerance(analyser.analyse(&dc)[0], 0.75, 0.0001);
+    }
+
+    #[test]
+    fn it_should_normalize_between_min_and_max_decibels() {
+        let mut analyser = SpectrumAnalyser::new(64).unwrap();
+        analyser
+            .set_window_function(WindowFunction::Rectangular)
+            .set_smoothing_time_constant(0.0)
+            .unwrap()
+            .set_decibel_range(-40.0, 0.0)
+            .unwrap();
+
+        analyser.analyse(&[1.0; 64]);
+
+        let mut normalized = vec![0.0; analyser.frequency_bin_count()];
+        analyser.normalized_into(&mut normalized);
+
+        // DC bin is full scale, others are silent
+        assert_eq!(normalized[0], 1.0);
+        assert_eq!(normalized[1], 0.0);
+    }
+}
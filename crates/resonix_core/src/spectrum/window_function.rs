@@ -0,0 +1,56 @@
+use std::f32::consts::TAU;
+
+/// Tapers the edges of a block of samples before taking its FFT,
+/// which reduces spectral leakage between frequency bins.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum WindowFunction {
+    /// Leaves the block of samples unaltered
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowFunction {
+    /// Returns the window's coefficient for sample `index` of a block of `size` samples
+    pub fn coefficient(&self, index: usize, size: usize) -> f32 {
+        if size <= 1 {
+            return 1.0;
+        }
+
+        let phase = TAU * index as f32 / size as f32;
+
+        match self {
+            WindowFunction::Rectangular => 1.0,
+            WindowFunction::Hann => 0.5 - 0.5 * phase.cos(),
+            WindowFunction::Hamming => 0.54 - 0.46 * phase.cos(),
+            WindowFunction::Blackman => 0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos(),
+        }
+    }
+
+    /// Returns all of the window's coefficients for a block of `size` samples
+    pub fn coefficients(&self, size: usize) -> Vec<f32> {
+        (0..size).map(|i| self.coefficient(i, size)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test_window_function {
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::WindowFunction;
+
+    #[test]
+    fn hann_window_should_be_zero_at_edges_and_one_in_middle() {
+        let coefficients = WindowFunction::Hann.coefficients(8);
+
+        assert_difference_is_within_tolerance(coefficients[0], 0.0, 0.0001);
+        assert_difference_is_within_tolerance(coefficients[4], 1.0, 0.0001);
+    }
+
+    #[test]
+    fn rectangular_window_should_not_alter_samples() {
+        assert_eq!(WindowFunction::Rectangular.coefficients(4), vec![1.0; 4]);
+    }
+}
@@ -5,6 +5,10 @@ use std::ops::{Div, Mul};
 /// and 0 dB to be inaudibly quiet at any reasonable listening level
 pub const DECIBEL_DEFAULT_REFERENCE_AMPLITUDE: f32 = 0.00001;
 
+/// allows the maximum amplitude of (1.0) to be 0 dB (i.e. dBFS),
+/// with all quieter amplitudes being negative
+pub const DECIBEL_FULL_SCALE_REFERENCE_AMPLITUDE: f32 = 1.0;
+
 /// decibel (dB) level is defined as: `d = 20 · log10(a/a0)`
 /// where a0 is a given reference amplitude.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
@@ -25,6 +29,19 @@ impl Decibel {
     pub fn calculate_with_default_reference(amplitude: f32) -> f32 {
         Self::calculate(DECIBEL_DEFAULT_REFERENCE_AMPLITUDE, amplitude)
     }
+
+    pub fn calculate_full_scale(amplitude: f32) -> f32 {
+        Self::calculate(DECIBEL_FULL_SCALE_REFERENCE_AMPLITUDE, amplitude)
+    }
+
+    /// The inverse of `calculate`: `a = a0 · 10^(d/20)`
+    pub fn calculate_amplitude(reference_amplitude: f32, decibels: f32) -> f32 {
+        10.0f32.powf(decibels / 20.0) * reference_amplitude
+    }
+
+    pub fn calculate_amplitude_full_scale(decibels: f32) -> f32 {
+        Self::calculate_amplitude(DECIBEL_FULL_SCALE_REFERENCE_AMPLITUDE, decibels)
+    }
 }
 
 impl Default for Decibel {
@@ -51,4 +68,34 @@ mod test_decibel {
         let result = Decibel::calculate_with_default_reference(1.0);
         assert_eq!(result, 100.0);
     }
+
+    #[test]
+    pub fn it_should_return_0_for_full_scale_amplitude_1() {
+        let result = Decibel::calculate_full_scale(1.0);
+        assert_eq!(result, 0.0);
+    }
+
+    #[test]
+    pub fn it_should_return_neg_6_for_full_scale_amplitude_0_5() {
+        let result = Decibel::calculate_full_scale(0.5);
+        assert!((result - -6.0206).abs() < 0.001);
+    }
+
+    #[test]
+    pub fn it_should_return_amplitude_0_for_neg_inf_full_scale() {
+        let result = Decibel::calculate_amplitude_full_scale(f32::NEG_INFINITY);
+        assert_eq!(result, 0.0);
+    }
+
+    #[test]
+    pub fn it_should_convert_decibels_back_to_amplitude() {
+        let result = Decibel::calculate_amplitude_full_scale(Decibel::calculate_full_scale(0.5));
+        assert!((result - 0.5).abs() < 0.00001);
+
+        let result = Decibel::calculate_amplitude(
+            crate::DECIBEL_DEFAULT_REFERENCE_AMPLITUDE,
+            Decibel::calculate_with_default_reference(0.25),
+        );
+        assert!((result - 0.25).abs() < 0.00001);
+    }
 }
@@ -1,7 +1,13 @@
+mod atomic_f32;
 mod lazy_cached;
 mod max;
 mod min;
+mod sample_queue;
+mod smoothed_value;
 
+pub use atomic_f32::*;
 pub(crate) use lazy_cached::*;
 pub use max::*;
 pub use min::*;
+pub use sample_queue::*;
+pub use smoothed_value::*;
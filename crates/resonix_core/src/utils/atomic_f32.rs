@@ -0,0 +1,56 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// An `f32` that can be shared between threads without locking.
+///
+/// Internally, the float is stored as its bit representation inside an `AtomicU32`,
+/// which makes it useful for publishing values from the audio thread
+/// (such as meter readings) that the main thread can poll at any time.
+#[derive(Debug, Default)]
+pub struct AtomicF32(AtomicU32);
+
+impl AtomicF32 {
+    pub fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    pub fn load(&self, ordering: Ordering) -> f32 {
+        f32::from_bits(self.0.load(ordering))
+    }
+
+    pub fn store(&self, value: f32, ordering: Ordering) {
+        self.0.store(value.to_bits(), ordering)
+    }
+
+    pub fn swap(&self, value: f32, ordering: Ordering) -> f32 {
+        f32::from_bits(self.0.swap(value.to_bits(), ordering))
+    }
+}
+
+impl From<f32> for AtomicF32 {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod test_atomic_f32 {
+    use std::sync::atomic::Ordering;
+
+    use crate::AtomicF32;
+
+    #[test]
+    fn it_should_store_and_load_values() {
+        let atomic = AtomicF32::new(0.25);
+        assert_eq!(atomic.load(Ordering::Relaxed), 0.25);
+
+        atomic.store(-1.5, Ordering::Relaxed);
+        assert_eq!(atomic.load(Ordering::Relaxed), -1.5);
+    }
+
+    #[test]
+    fn it_should_return_previous_value_when_swapping() {
+        let atomic = AtomicF32::default();
+        assert_eq!(atomic.swap(1.0, Ordering::Relaxed), 0.0);
+        assert_eq!(atomic.load(Ordering::Relaxed), 1.0);
+    }
+}
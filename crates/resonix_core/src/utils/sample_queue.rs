@@ -0,0 +1,187 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::AtomicF32;
+
+#[derive(Debug)]
+struct SampleQueueInner {
+    samples: Box<[AtomicF32]>,
+    /// Total number of samples ever popped
+    read_index: AtomicUsize,
+    /// Total number of samples ever pushed
+    write_index: AtomicUsize,
+}
+
+/// A fixed-capacity, lock-free queue of samples for moving audio between
+/// threads (e.g. from the audio thread to a background thread) without
+/// locking or allocating.
+///
+/// The queue is single-producer, single-consumer: at any one time, at most one
+/// thread may be pushing and at most one thread may be popping. Clones share the
+/// same underlying queue (so that each side can own a copy), so callers must make
+/// sure that clones on the same side never push (or pop) concurrently; otherwise
+/// samples can be overwritten or read twice.
+#[derive(Debug, Clone)]
+pub struct SampleQueue {
+    inner: Arc<SampleQueueInner>,
+}
+
+impl SampleQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(SampleQueueInner {
+                samples: (0..capacity).map(|_| AtomicF32::default()).collect(),
+                read_index: AtomicUsize::new(0),
+                write_index: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.inner.samples.len()
+    }
+
+    /// Number of samples waiting to be popped
+    pub fn len(&self) -> usize {
+        let write_index = self.inner.write_index.load(Ordering::Acquire);
+        let read_index = self.inner.read_index.load(Ordering::Acquire);
+        write_index.wrapping_sub(read_index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of samples that can be pushed before the queue is full
+    pub fn free_len(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Pushes as many samples from `samples` as will fit into the queue.
+    ///
+    /// Returns the number of samples pushed.
+    pub fn push_slice(&self, samples: &[f32]) -> usize {
+        let capacity = self.capacity();
+        let write_index = self.inner.write_index.load(Ordering::Relaxed);
+        let read_index = self.inner.read_index.load(Ordering::Acquire);
+        let free_len = capacity - write_index.wrapping_sub(read_index);
+        let num_samples = samples.len().min(free_len);
+
+        samples[..num_samples]
+            .iter()
+            .enumerate()
+            .for_each(|(i, sample)| {
+                self.inner.samples[write_index.wrapping_add(i) % capacity]
+                    .store(*sample, Ordering::Relaxed);
+            });
+
+        self.inner
+            .write_index
+            .store(write_index.wrapping_add(num_samples), Ordering::Release);
+
+        num_samples
+    }
+
+    /// Returns `false` if the queue was full
+    pub fn push(&self, sample: f32) -> bool {
+        self.push_slice(&[sample]) == 1
+    }
+
+    /// Pops as many samples into `buffer` as are available.
+    ///
+    /// Returns the number of samples popped.
+    pub fn pop_into(&self, buffer: &mut [f32]) -> usize {
+        let capacity = self.capacity();
+        let read_index = self.inner.read_index.load(Ordering::Relaxed);
+        let write_index = self.inner.write_index.load(Ordering::Acquire);
+        let num_samples = buffer.len().min(write_index.wrapping_sub(read_index));
+
+        buffer[..num_samples]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, sample)| {
+                *sample = self.inner.samples[read_index.wrapping_add(i) % capacity]
+                    .load(Ordering::Relaxed);
+            });
+
+        self.inner
+            .read_index
+            .store(read_index.wrapping_add(num_samples), Ordering::Release);
+
+        num_samples
+    }
+
+    pub fn pop(&self) -> Option<f32> {
+        let mut buffer = [0.0];
+        (self.pop_into(&mut buffer) == 1).then_some(buffer[0])
+    }
+}
+
+#[cfg(test)]
+mod test_sample_queue {
+    use crate::SampleQueue;
+
+    #[test]
+    fn it_should_pop_samples_in_order() {
+        let queue = SampleQueue::new(4);
+        assert!(queue.push(1.0));
+        assert!(queue.push(2.0));
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop(), Some(1.0));
+        assert_eq!(queue.pop(), Some(2.0));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn it_should_not_push_past_capacity() {
+        let queue = SampleQueue::new(3);
+        assert_eq!(queue.push_slice(&[1.0, 2.0, 3.0, 4.0]), 3);
+        assert!(!queue.push(5.0));
+        assert_eq!(queue.free_len(), 0);
+
+        let mut buffer = [0.0; 5];
+        assert_eq!(queue.pop_into(&mut buffer), 3);
+        assert_eq!(&buffer[..3], &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn it_should_wrap_around() {
+        let queue = SampleQueue::new(3);
+        let mut buffer = [0.0; 2];
+
+        for i in 0..10 {
+            let i = i as f32;
+            assert_eq!(queue.push_slice(&[i, -i]), 2);
+            assert_eq!(queue.pop_into(&mut buffer), 2);
+            assert_eq!(buffer, [i, -i]);
+        }
+    }
+
+    #[test]
+    fn it_should_move_samples_between_threads() {
+        let queue = SampleQueue::new(64);
+        let producer = queue.clone();
+
+        let thread = std::thread::spawn(move || {
+            let mut next = 0;
+            while next < 1000 {
+                if producer.push(next as f32) {
+                    next += 1;
+                }
+            }
+        });
+
+        let mut received = Vec::new();
+        while received.len() < 1000 {
+            if let Some(sample) = queue.pop() {
+                received.push(sample);
+            }
+        }
+        thread.join().unwrap();
+
+        assert!(received.iter().enumerate().all(|(i, s)| *s == i as f32));
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use crate::SampleRate;
+
+/// A value that moves to each new target gradually (in a straight line over
+/// `smoothing_time`), rather than jumping there immediately.
+///
+/// This prevents the clicks and "zipper noise" that are heard
+/// when a parameter like gain or cutoff frequency jumps abruptly.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct SmoothedValue {
+    sample_rate: SampleRate,
+    smoothing_time: Duration,
+    value: f32,
+    target: f32,
+    /// Amount the value moves every sample
+    step: f32,
+    steps_remaining: u32,
+}
+
+impl SmoothedValue {
+    pub fn new(sample_rate: impl Into<SampleRate>, smoothing_time: Duration, value: f32) -> Self {
+        Self {
+            sample_rate: sample_rate.into(),
+            smoothing_time,
+            value,
+            target: value,
+            step: 0.0,
+            steps_remaining: 0,
+        }
+    }
+
+    /// The current (possibly in-between) value
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The value that is being moved towards
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn is_smoothing(&self) -> bool {
+        self.steps_remaining > 0
+    }
+
+    /// Starts moving towards `target` from the current value
+    pub fn set_target(&mut self, target: f32) -> &mut Self {
+        if target == self.target {
+            return self;
+        }
+
+        self.target = target;
+        self.steps_remaining =
+            (self.smoothing_time.as_secs_f32() * self.sample_rate.get() as f32).round() as u32;
+
+        if self.steps_remaining == 0 {
+            self.value = target;
+        } else {
+            self.step = (target - self.value) / self.steps_remaining as f32;
+        }
+
+        self
+    }
+
+    /// Jumps to `value` immediately, without smoothing
+    pub fn set_value(&mut self, value: f32) -> &mut Self {
+        self.value = value;
+        self.target = value;
+        self.steps_remaining = 0;
+        self
+    }
+
+    /// Advances by one sample and returns the new value
+    pub fn next_value(&mut self) -> f32 {
+        if self.steps_remaining > 0 {
+            self.steps_remaining -= 1;
+            self.value = if self.steps_remaining == 0 {
+                self.target
+            } else {
+                self.value + self.step
+            };
+        }
+
+        self.value
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.smoothing_time
+    }
+
+    /// Takes effect the next time the target changes
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.smoothing_time = smoothing_time;
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    /// Takes effect the next time the target changes
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_smoothed_value {
+    use std::time::Duration;
+
+    use crate::SmoothedValue;
+
+    #[test]
+    fn it_should_move_to_target_in_a_straight_line() {
+        let mut value = SmoothedValue::new(1000, Duration::from_millis(4), 0.0);
+        value.set_target(1.0);
+
+        let values: Vec<f32> = (0..5).map(|_| value.next_value()).collect();
+        assert_eq!(values, vec![0.25, 0.5, 0.75, 1.0, 1.0]);
+        assert!(!value.is_smoothing());
+    }
+
+    #[test]
+    fn it_should_jump_without_smoothing_time() {
+        let mut value = SmoothedValue::new(1000, Duration::ZERO, 0.0);
+        value.set_target(1.0);
+        assert_eq!(value.value(), 1.0);
+
+        value.set_smoothing_time(Duration::from_millis(2));
+        value.set_target(0.0);
+        value.set_value(0.5);
+        assert_eq!(value.next_value(), 0.5);
+    }
+}
@@ -271,8 +271,15 @@ fn run_processor_message<N: Node + 'static>(
             request_id: id,
             parent_node_uid,
             child_node_uid,
+            from_index,
+            to_index,
         } => {
-            let result = processor.connect(parent_node_uid, child_node_uid);
+            let result = processor.connect_with_indexes(
+                parent_node_uid,
+                child_node_uid,
+                from_index,
+                to_index,
+            );
             ProcessorMessageResponse::Connect {
                 request_id: id,
                 result,
@@ -352,12 +359,27 @@ impl AudioContext<AudioInit> {
         &mut self,
         parent_node_uid: impl AsRef<NodeUid>,
         child_node_uid: impl AsRef<NodeUid>,
+    ) -> Result<EdgeIndex, MessageError> {
+        self.connect_with_indexes(parent_node_uid, child_node_uid, 0, 0)
+            .await
+    }
+
+    /// Asynchronously connect a specific output of one node to a specific input
+    /// of another node from the audio graph inside the audio thread
+    pub async fn connect_with_indexes(
+        &mut self,
+        parent_node_uid: impl AsRef<NodeUid>,
+        child_node_uid: impl AsRef<NodeUid>,
+        from_index: usize,
+        to_index: usize,
     ) -> Result<EdgeIndex, MessageError> {
         self.send_message_to_processor(
             |request_id| ProcessorMessageRequest::Connect {
                 request_id,
                 parent_node_uid: *parent_node_uid.as_ref(),
                 child_node_uid: *child_node_uid.as_ref(),
+                from_index,
+                to_index,
             },
             |node_message_response| {
                 let ProcessorMessageResponse::Connect { result, .. } = node_message_response else {
@@ -1,6 +1,7 @@
 pub mod audio_context;
 pub mod connection;
 pub mod messages;
+pub mod meter_handle;
 pub mod node_handle;
 pub mod node_type;
 pub mod nodes;
@@ -10,6 +11,7 @@ pub mod traits;
 pub use audio_context::*;
 pub use connection::*;
 pub(crate) use messages::*;
+pub use meter_handle::*;
 pub use node_handle::*;
 pub use node_type::*;
 pub use nodes::*;
@@ -79,6 +79,8 @@ pub(crate) enum ProcessorMessageRequest<N: Node + 'static> {
         request_id: u32,
         parent_node_uid: NodeUid,
         child_node_uid: NodeUid,
+        from_index: usize,
+        to_index: usize,
     },
     UpdateNode {
         request_id: u32,
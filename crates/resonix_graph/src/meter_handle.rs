@@ -0,0 +1,128 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use resonix_core::{AtomicF32, Decibel, NumChannels, PeakAmplitude};
+
+/// The `MeterHandle` allows reading the most recent per-channel
+/// readings of a meter node (such as a `PeakMeterNode` or `RmsMeterNode`)
+/// from the main thread, even after that node has been sent to the audio thread.
+///
+/// Readings are published by the audio thread without any locking or
+/// message passing, so they can be polled as often as needed (e.g. once per UI frame).
+///
+/// This struct can be safely and cheaply cloned
+#[derive(Debug, Clone)]
+pub struct MeterHandle {
+    levels: Arc<[AtomicF32]>,
+}
+
+impl MeterHandle {
+    pub(crate) fn new(num_channels: impl Into<NumChannels>) -> Self {
+        let num_channels: NumChannels = num_channels.into();
+        Self {
+            levels: (0..*num_channels).map(|_| AtomicF32::default()).collect(),
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.levels.len())
+    }
+
+    /// Returns the most recent linear amplitude reading for the given channel
+    pub fn level(&self, channel: usize) -> Option<f32> {
+        self.levels
+            .get(channel)
+            .map(|level| level.load(Ordering::Relaxed))
+    }
+
+    /// Returns the most recent reading for the given channel in dBFS
+    /// (i.e. an amplitude of 1.0 is 0 dB)
+    pub fn level_in_decibels(&self, channel: usize) -> Option<f32> {
+        self.level(channel).map(Decibel::calculate_full_scale)
+    }
+
+    /// Returns the most recent linear amplitude readings for all channels
+    pub fn levels(&self) -> Vec<f32> {
+        self.levels
+            .iter()
+            .map(|level| level.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    /// Writes the most recent linear amplitude readings into the given buffer
+    /// without allocating. Extra channels / extra buffer space are ignored.
+    pub fn levels_into<'a>(&self, buffer: &'a mut [f32]) -> &'a mut [f32] {
+        buffer
+            .iter_mut()
+            .zip(self.levels.iter())
+            .for_each(|(sample, level)| *sample = level.load(Ordering::Relaxed));
+        buffer
+    }
+
+    /// Returns the loudest channel reading and the index of that channel
+    pub fn loudest_channel(&self) -> Option<PeakAmplitude> {
+        // read the atomics directly to avoid allocating
+        self.levels
+            .iter()
+            .map(|level| level.load(Ordering::Relaxed))
+            .enumerate()
+            .filter(|(_, level)| !level.is_nan())
+            .map(|(i, level)| (i, level.abs()))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(buffer_index, peak_amplitude)| PeakAmplitude {
+                peak_amplitude,
+                buffer_index,
+            })
+    }
+
+    /// Called from the audio thread to publish the latest readings
+    pub(crate) fn publish(&self, levels: impl Iterator<Item = f32>) {
+        self.levels
+            .iter()
+            .zip(levels)
+            .for_each(|(atomic, level)| atomic.store(level, Ordering::Relaxed));
+    }
+}
+
+impl PartialEq for MeterHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.levels, &other.levels)
+    }
+}
+
+#[cfg(test)]
+mod test_meter_handle {
+    use crate::MeterHandle;
+
+    #[test]
+    fn should_read_published_levels() {
+        let handle = MeterHandle::new(3);
+        handle.publish([0.5, 1.0, 0.25].into_iter());
+
+        assert_eq!(handle.levels(), vec![0.5, 1.0, 0.25]);
+        assert_eq!(handle.level(1), Some(1.0));
+        assert_eq!(handle.level(3), None);
+        assert_eq!(handle.level_in_decibels(1), Some(0.0));
+
+        let loudest = handle.loudest_channel().unwrap();
+        assert_eq!(loudest.buffer_index, 1);
+        assert_eq!(loudest.peak_amplitude, 1.0);
+    }
+
+    #[test]
+    fn should_not_return_loudest_channel_without_channels() {
+        let handle = MeterHandle::new(0);
+
+        assert!(handle.loudest_channel().is_none());
+    }
+
+    #[test]
+    fn clones_should_share_readings() {
+        let handle = MeterHandle::new(1);
+        let clone = handle.clone();
+
+        handle.publish([0.75].into_iter());
+
+        assert_eq!(clone.level(0), Some(0.75));
+        assert_eq!(handle, clone);
+    }
+}
@@ -1,19 +1,89 @@
+pub mod abs_node;
+pub mod add_node;
+pub mod analyser_node;
+pub mod biquad_filter_node;
+pub mod buffer_player_node;
+pub mod chorus_node;
+pub mod clamp_node;
+pub mod compressor_node;
 pub mod constant_node;
+pub mod convolver_node;
 pub mod dac_node;
+pub mod delay_node;
 pub mod downmix_node;
+pub mod envelope_node;
+pub mod flanger_node;
+pub mod gain_node;
 pub mod granular_synthesizer_node;
+pub mod limiter_node;
+pub mod map_node;
+pub mod mixer_node;
 pub mod multicore_node;
 pub mod multiply_node;
+pub mod noise_node;
+pub mod panner_node;
 pub mod pass_through_node;
+pub mod peak_meter_node;
+pub mod phaser_node;
+pub mod pulse_node;
 pub mod record_node;
+pub mod resampler_node;
+pub mod reverb_node;
+pub mod rms_meter_node;
+pub mod sawtooth_node;
+pub mod scale_offset_node;
 pub mod sine_node;
+pub mod splitter_node;
+pub mod subtract_node;
+#[cfg(test)]
+mod test_utils;
+pub mod triangle_node;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod wav_writer_node;
+pub mod wave_shaper_node;
+pub mod wavetable_node;
+pub mod zip_node;
 
+pub use abs_node::*;
+pub use add_node::*;
+pub use analyser_node::*;
+pub use biquad_filter_node::*;
+pub use buffer_player_node::*;
+pub use chorus_node::*;
+pub use clamp_node::*;
+pub use compressor_node::*;
 pub use constant_node::*;
+pub use convolver_node::*;
 pub use dac_node::*;
+pub use delay_node::*;
 pub use downmix_node::*;
+pub use envelope_node::*;
+pub use flanger_node::*;
+pub use gain_node::*;
 pub use granular_synthesizer_node::*;
+pub use limiter_node::*;
+pub use map_node::*;
+pub use mixer_node::*;
 pub use multicore_node::*;
 pub use multiply_node::*;
+pub use noise_node::*;
+pub use panner_node::*;
 pub use pass_through_node::*;
+pub use peak_meter_node::*;
+pub use phaser_node::*;
+pub use pulse_node::*;
 pub use record_node::*;
+pub use resampler_node::*;
+pub use reverb_node::*;
+pub use rms_meter_node::*;
+pub use sawtooth_node::*;
+pub use scale_offset_node::*;
 pub use sine_node::*;
+pub use splitter_node::*;
+pub use subtract_node::*;
+pub use triangle_node::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use wav_writer_node::*;
+pub use wave_shaper_node::*;
+pub use wavetable_node::*;
+pub use zip_node::*;
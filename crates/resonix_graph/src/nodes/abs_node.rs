@@ -0,0 +1,124 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Outputs the absolute value of every sample of the incoming signal
+/// (which is also known as full-wave rectification)
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Absolute value of the signal
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct AbsNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+}
+
+impl AbsNode {
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+        }
+    }
+}
+
+impl Node for AbsNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("AbsNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output
+                .data_mut()
+                .iter_mut()
+                .zip(input.data().iter())
+                .for_each(|(output_sample, input_sample)| *output_sample = input_sample.abs()),
+            None => output.data_mut().fill(0.0),
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("AbsNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_abs_node {
+    use std::cell::RefCell;
+
+    use crate::{AbsNode, Connection, Node};
+
+    #[test]
+    fn should_output_absolute_value() {
+        let mut abs_node = AbsNode::new(3);
+
+        let input_connection = RefCell::new(Connection::from_test_data(
+            0,
+            3,
+            vec![-0.5, 0.0, 0.25],
+            0,
+            0,
+        ));
+        let output_connection = RefCell::new(Connection::new(3));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            abs_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.5, 0.0, 0.25]);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Takes two signals and adds them together
+///
+/// Input 0 - Signal 1
+/// Input 1 - Signal 2
+///
+/// Output 0 - Summed signal
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct AddNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+}
+
+impl AddNode {
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+        }
+    }
+}
+
+impl Node for AddNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("AddNode should have one and only one output connection");
+
+        output.data_mut().fill(0.0);
+        for input in inputs {
+            output
+                .data_mut()
+                .iter_mut()
+                .zip(input.data().iter())
+                .for_each(|(output_sample, input_sample)| *output_sample += input_sample);
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("AddNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_add_node {
+    use std::cell::RefCell;
+
+    use crate::{AddNode, Connection, Node};
+
+    #[test]
+    fn should_add_1st_and_2nd_inputs() {
+        let mut add_node = AddNode::new(2);
+
+        let left_input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![0.5, 1.0], 0, 0));
+        let right_input_connection =
+            RefCell::new(Connection::from_test_data(1, 2, vec![0.25, -2.0], 0, 1));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [
+                left_input_connection.borrow(),
+                right_input_connection.borrow(),
+            ];
+            let outputs = [output_connection.borrow_mut()];
+            add_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.75, -1.0]);
+    }
+}
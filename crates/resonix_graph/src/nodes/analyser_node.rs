@@ -0,0 +1,545 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use resonix_core::{
+    AtomicF32, NumChannels, SpectrumAnalyser, SpectrumAnalyserError, WindowFunction,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum AnalyserHandleError {
+    #[error("AnalyserNode keeps {capacity:?} samples per channel, so the FFT size can't be {fft_size:?}")]
+    FftSizeTooLarge { fft_size: usize, capacity: usize },
+    #[error(transparent)]
+    SpectrumAnalyser(#[from] SpectrumAnalyserError),
+}
+
+/// Recent audio shared between the `AnalyserNode` (writer) and any `AnalyserHandle`s (readers).
+///
+/// Samples are stored planar, with one ring of `capacity` samples per channel.
+#[derive(Debug)]
+struct AnalyserBuffer {
+    capacity: usize,
+    samples: Box<[AtomicF32]>,
+    frames_written: AtomicUsize,
+}
+
+impl AnalyserBuffer {
+    fn new(num_channels: NumChannels, capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: (0..(*num_channels * capacity))
+                .map(|_| AtomicF32::default())
+                .collect(),
+            frames_written: AtomicUsize::new(0),
+        }
+    }
+
+    fn num_channels(&self) -> usize {
+        self.samples.len() / self.capacity
+    }
+
+    /// Copies the most recent samples of `channel` into `buffer`, with the newest sample last
+    fn read_recent(&self, channel: usize, buffer: &mut [f32]) -> Option<usize> {
+        if channel >= self.num_channels() {
+            return None;
+        }
+
+        let frames_written = self.frames_written.load(Ordering::Acquire);
+        let num_samples = buffer.len().min(self.capacity);
+        let channel_samples =
+            &self.samples[(channel * self.capacity)..((channel + 1) * self.capacity)];
+
+        buffer[..num_samples]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, sample)| {
+                let samples_ago = num_samples - i;
+                *sample = if samples_ago > frames_written {
+                    // not enough audio has been processed yet
+                    0.0
+                } else {
+                    let frame_index = frames_written.wrapping_sub(samples_ago) % self.capacity;
+                    channel_samples[frame_index].load(Ordering::Relaxed)
+                };
+            });
+
+        Some(num_samples)
+    }
+}
+
+/// Passes audio through unaltered while keeping a record of the most
+/// recent samples, which can be read from the main thread as time-domain
+/// data or frequency spectra via the `AnalyserHandle` returned from `AnalyserNode::handle`.
+///
+/// No analysis is performed on the audio thread: samples are
+/// written into a lock-free ring buffer, and FFTs are only calculated
+/// when requested from the `AnalyserHandle`.
+///
+/// The ring buffer keeps `fft_size` samples per channel, which is the
+/// largest FFT size that any of the node's `AnalyserHandle`s can use.
+/// Changing it replaces the buffer, so any existing handles must be fetched again.
+///
+/// Input 0 - Signal to analyse
+///
+/// Output 0 - Unaltered input signal
+#[derive(Debug, Clone)]
+pub struct AnalyserNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    buffer: Arc<AnalyserBuffer>,
+    frames_written: usize,
+}
+
+impl AnalyserNode {
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, SpectrumAnalyser::DEFAULT_FFT_SIZE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        fft_size: usize,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            buffer: Arc::new(AnalyserBuffer::new(num_channels, fft_size)),
+            frames_written: 0,
+        }
+    }
+
+    /// The number of samples kept per channel (the largest FFT size a handle can use)
+    pub fn fft_size(&self) -> usize {
+        self.buffer.capacity
+    }
+
+    /// Reallocates the node's audio buffer to hold `fft_size` samples per channel.
+    /// Must be a power of 2 between 32 and 32768.
+    ///
+    /// This allocates, so it should be called before the node is added to the audio graph.
+    ///
+    /// Handles created before the FFT size changes keep reading the previous buffer,
+    /// which no longer receives audio, so call `handle` again after changing the FFT size.
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Result<&mut Self, SpectrumAnalyserError> {
+        SpectrumAnalyser::validate_fft_size(fft_size)?;
+        if fft_size != self.buffer.capacity {
+            self.buffer = Arc::new(AnalyserBuffer::new(self.num_channels, fft_size));
+            self.frames_written = 0;
+        }
+        Ok(self)
+    }
+
+    /// Returns a handle that can be used to read analysis data from the main thread.
+    ///
+    /// Each handle keeps its own FFT configuration and smoothing history.
+    pub fn handle(&self) -> AnalyserHandle {
+        AnalyserHandle::new(Arc::clone(&self.buffer))
+    }
+}
+
+impl Node for AnalyserNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let input = inputs
+            .next()
+            .expect("AnalyserNode should have one and only one input connection");
+        let input_data = input.data();
+
+        let capacity = self.buffer.capacity;
+        let frame_index = self.frames_written % capacity;
+        input_data.iter().enumerate().for_each(|(channel, sample)| {
+            self.buffer.samples[channel * capacity + frame_index].store(*sample, Ordering::Relaxed);
+        });
+        self.frames_written = self.frames_written.wrapping_add(1);
+        self.buffer
+            .frames_written
+            .store(self.frames_written, Ordering::Release);
+
+        // it's possible for an analyser to be the last node in a chain,
+        // so an outgoing connection is not required
+        if let Some(mut output) = outputs.next() {
+            output.update_data(|frame| frame.copy_from_slice(input_data));
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("AnalyserNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl PartialEq for AnalyserNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for AnalyserNode {}
+
+impl PartialOrd for AnalyserNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AnalyserNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+/// The `AnalyserHandle` reads the most recent audio recorded by an
+/// `AnalyserNode` from the main thread, returning it either as raw
+/// time-domain data or as a windowed FFT magnitude spectrum.
+///
+/// FFT size, smoothing, and decibel range are configured on the handle,
+/// since all analysis happens on the thread that owns the handle.
+///
+/// Cloning a handle shares the underlying audio data, but gives the
+/// clone its own FFT configuration and smoothing history.
+#[derive(Debug, Clone)]
+pub struct AnalyserHandle {
+    buffer: Arc<AnalyserBuffer>,
+    spectrum_analysers: Vec<SpectrumAnalyser>,
+    scratch: Vec<f32>,
+}
+
+impl AnalyserHandle {
+    fn new(buffer: Arc<AnalyserBuffer>) -> Self {
+        let num_channels = buffer.num_channels();
+        let fft_size = buffer.capacity;
+        let spectrum_analyser =
+            SpectrumAnalyser::new(fft_size).expect("AnalyserNode FFT size should be valid");
+        Self {
+            buffer,
+            // always keep at least one analyser around to hold configuration
+            spectrum_analysers: vec![spectrum_analyser; num_channels.max(1)],
+            scratch: vec![0.0; fft_size],
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.buffer.num_channels())
+    }
+
+    /// All analysers share the same configuration, so the first can be used for queries
+    fn first_analyser(&self) -> &SpectrumAnalyser {
+        &self.spectrum_analysers[0]
+    }
+
+    pub fn fft_size(&self) -> usize {
+        self.first_analyser().fft_size()
+    }
+
+    /// Sets the number of samples used for each FFT.
+    /// Must be a power of 2 between 32 and the `AnalyserNode`'s `fft_size`.
+    pub fn set_fft_size(&mut self, fft_size: usize) -> Result<&mut Self, AnalyserHandleError> {
+        if fft_size > self.buffer.capacity {
+            return Err(AnalyserHandleError::FftSizeTooLarge {
+                fft_size,
+                capacity: self.buffer.capacity,
+            });
+        }
+        for analyser in self.spectrum_analysers.iter_mut() {
+            analyser.set_fft_size(fft_size)?;
+        }
+        self.scratch.resize(fft_size, 0.0);
+        Ok(self)
+    }
+
+    /// The number of values returned in frequency data (half the `fft_size`)
+    pub fn frequency_bin_count(&self) -> usize {
+        self.fft_size() / 2
+    }
+
+    pub fn smoothing_time_constant(&self) -> f32 {
+        self.first_analyser().smoothing_time_constant()
+    }
+
+    /// Sets how much each new spectrum is blended with previous spectra
+    /// (0.0 is no smoothing, values approaching 1.0 change slowly)
+    pub fn set_smoothing_time_constant(
+        &mut self,
+        smoothing_time_constant: f32,
+    ) -> Result<&mut Self, SpectrumAnalyserError> {
+        for analyser in self.spectrum_analysers.iter_mut() {
+            analyser.set_smoothing_time_constant(smoothing_time_constant)?;
+        }
+        Ok(self)
+    }
+
+    pub fn min_decibels(&self) -> f32 {
+        self.first_analyser().min_decibels()
+    }
+
+    pub fn max_decibels(&self) -> f32 {
+        self.first_analyser().max_decibels()
+    }
+
+    /// Sets the range used to scale `normalized_frequency_data`
+    pub fn set_decibel_range(
+        &mut self,
+        min_decibels: f32,
+        max_decibels: f32,
+    ) -> Result<&mut Self, SpectrumAnalyserError> {
+        for analyser in self.spectrum_analysers.iter_mut() {
+            analyser.set_decibel_range(min_decibels, max_decibels)?;
+        }
+        Ok(self)
+    }
+
+    pub fn window_function(&self) -> WindowFunction {
+        self.first_analyser().window_function()
+    }
+
+    pub fn set_window_function(&mut self, window_function: WindowFunction) -> &mut Self {
+        for analyser in self.spectrum_analysers.iter_mut() {
+            analyser.set_window_function(window_function);
+        }
+        self
+    }
+
+    /// Copies the most recent samples of `channel` into `buffer`,
+    /// with the newest sample last. At most `fft_size` samples are copied.
+    ///
+    /// Returns the number of samples written, or `None` if `channel` does not exist.
+    pub fn time_domain_data(&self, channel: usize, buffer: &mut [f32]) -> Option<usize> {
+        let num_samples = buffer.len().min(self.fft_size());
+        self.buffer.read_recent(channel, &mut buffer[..num_samples])
+    }
+
+    fn analyse(&mut self, channel: usize) -> Option<&SpectrumAnalyser> {
+        self.buffer.read_recent(channel, &mut self.scratch)?;
+
+        let analyser = &mut self.spectrum_analysers[channel];
+        analyser.analyse(&self.scratch);
+        Some(analyser)
+    }
+
+    /// Calculates the spectrum of the most recent `fft_size` samples of `channel`,
+    /// writing smoothed linear magnitudes into `buffer` (one value per frequency bin).
+    pub fn magnitude_data(&mut self, channel: usize, buffer: &mut [f32]) -> Option<()> {
+        let analyser = self.analyse(channel)?;
+        buffer
+            .iter_mut()
+            .zip(analyser.magnitudes())
+            .for_each(|(sample, magnitude)| *sample = *magnitude);
+        Some(())
+    }
+
+    /// Calculates the spectrum of the most recent `fft_size` samples of `channel`,
+    /// writing smoothed magnitudes in dBFS into `buffer` (one value per frequency bin).
+    pub fn frequency_data(&mut self, channel: usize, buffer: &mut [f32]) -> Option<()> {
+        self.analyse(channel)?.decibels_into(buffer);
+        Some(())
+    }
+
+    /// Calculates the spectrum of the most recent `fft_size` samples of `channel`,
+    /// writing smoothed magnitudes into `buffer`, scaled from 0.0 (`min_decibels`)
+    /// to 1.0 (`max_decibels`).
+    pub fn normalized_frequency_data(&mut self, channel: usize, buffer: &mut [f32]) -> Option<()> {
+        self.analyse(channel)?.normalized_into(buffer);
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod test_analyser_node {
+    use std::cell::RefCell;
+
+    use resonix_core::{SpectrumAnalyser, WindowFunction};
+
+    use crate::{AnalyserHandleError, AnalyserNode, Connection, Node};
+
+    fn process_frames(analyser_node: &mut AnalyserNode, frames: &[Vec<f32>]) {
+        for frame in frames {
+            let input_connection = RefCell::new(Connection::from_test_data(
+                0,
+                frame.len(),
+                frame.clone(),
+                0,
+                0,
+            ));
+            let output_connection = RefCell::new(Connection::new(frame.len()));
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            analyser_node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+    }
+
+    #[test]
+    fn should_pass_audio_through() {
+        let mut analyser_node = AnalyserNode::new(2);
+
+        let input_connection = RefCell::new(Connection::from_test_data(0, 2, vec![0.1, 0.2], 0, 0));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            analyser_node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.1, 0.2]);
+    }
+
+    #[test]
+    fn should_return_most_recent_time_domain_data() {
+        let mut analyser_node = AnalyserNode::new(2);
+        let mut handle = analyser_node.handle();
+        handle.set_fft_size(32).unwrap();
+
+        let frames: Vec<Vec<f32>> = (0..40).map(|i| vec![i as f32, -(i as f32)]).collect();
+        process_frames(&mut analyser_node, &frames);
+
+        let mut buffer = vec![0.0; 4];
+        assert_eq!(handle.time_domain_data(0, &mut buffer), Some(4));
+        assert_eq!(buffer, vec![36.0, 37.0, 38.0, 39.0]);
+
+        assert_eq!(handle.time_domain_data(1, &mut buffer), Some(4));
+        assert_eq!(buffer, vec![-36.0, -37.0, -38.0, -39.0]);
+
+        assert_eq!(handle.time_domain_data(2, &mut buffer), None);
+    }
+
+    #[test]
+    fn should_pad_time_domain_data_with_silence_before_enough_audio_is_processed() {
+        let mut analyser_node = AnalyserNode::new(1);
+        let handle = analyser_node.handle();
+
+        process_frames(&mut analyser_node, &[vec![1.0], vec![2.0]]);
+
+        let mut buffer = vec![-1.0; 4];
+        handle.time_domain_data(0, &mut buffer);
+        assert_eq!(buffer, vec![0.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn should_return_frequency_spectrum_of_recent_audio() {
+        const FFT_SIZE: usize = 64;
+        const BIN: usize = 4;
+
+        let mut analyser_node = AnalyserNode::new(1);
+        let mut handle = analyser_node.handle();
+        handle
+            .set_fft_size(FFT_SIZE)
+            .unwrap()
+            .set_smoothing_time_constant(0.0)
+            .unwrap()
+            .set_window_function(WindowFunction::Rectangular);
+
+        let frames: Vec<Vec<f32>> = (0..FFT_SIZE)
+            .map(|i| vec![(std::f32::consts::TAU * (BIN * i) as f32 / FFT_SIZE as f32).sin()])
+            .collect();
+        process_frames(&mut analyser_node, &frames);
+
+        let mut magnitudes = vec![0.0; handle.frequency_bin_count()];
+        handle.magnitude_data(0, &mut magnitudes).unwrap();
+        let peak = resonix_core::peak_amplitude(&magnitudes).unwrap();
+        assert_eq!(peak.buffer_index, BIN);
+
+        let mut decibels = vec![0.0; handle.frequency_bin_count()];
+        handle.frequency_data(0, &mut decibels).unwrap();
+        assert!((decibels[BIN] - resonix_core::Decibel::calculate_full_scale(0.5)).abs() < 0.01);
+    }
+
+    #[test]
+    fn should_allocate_buffer_for_configured_fft_size() {
+        let mut analyser_node = AnalyserNode::new(2);
+        assert_eq!(analyser_node.fft_size(), SpectrumAnalyser::DEFAULT_FFT_SIZE);
+        assert_eq!(
+            analyser_node.buffer.samples.len(),
+            2 * SpectrumAnalyser::DEFAULT_FFT_SIZE
+        );
+
+        analyser_node.set_fft_size(4096).unwrap();
+        assert_eq!(analyser_node.buffer.samples.len(), 2 * 4096);
+        assert_eq!(analyser_node.handle().fft_size(), 4096);
+
+        assert!(analyser_node.set_fft_size(100).is_err());
+        assert_eq!(analyser_node.fft_size(), 4096);
+    }
+
+    #[test]
+    fn should_only_record_into_handles_fetched_after_fft_size_changes() {
+        let mut analyser_node = AnalyserNode::new(1);
+        let previous_handle = analyser_node.handle();
+
+        analyser_node.set_fft_size(64).unwrap();
+        let handle = analyser_node.handle();
+        process_frames(&mut analyser_node, &[vec![1.0]]);
+
+        let mut buffer = vec![-1.0; 1];
+        previous_handle.time_domain_data(0, &mut buffer);
+        assert_eq!(buffer, vec![0.0]);
+        handle.time_domain_data(0, &mut buffer);
+        assert_eq!(buffer, vec![1.0]);
+    }
+
+    #[test]
+    fn should_reject_fft_sizes_larger_than_the_node_buffer() {
+        let analyser_node = AnalyserNode::new(1);
+        let mut handle = analyser_node.handle();
+
+        assert_eq!(
+            handle.set_fft_size(4096).unwrap_err(),
+            AnalyserHandleError::FftSizeTooLarge {
+                fft_size: 4096,
+                capacity: SpectrumAnalyser::DEFAULT_FFT_SIZE,
+            }
+        );
+        assert!(handle.set_fft_size(1024).is_ok());
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{
+    biquad_frequency_response, BiquadFilter, BiquadFilterType, FrequencyResponse, NumChannels,
+    SampleRate,
+};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Filters the incoming signal with a low-pass, high-pass, band-pass, notch,
+/// all-pass, peaking, low-shelf, or high-shelf filter.
+///
+/// Frequency, Q, and gain can each be modulated by connecting a signal to
+/// their inputs: the first channel of a modulation input is added to the
+/// node's own setting (e.g. a frequency of 1000.0 modulated by a signal
+/// of -200.0 filters at 800.0 Hz), so modulation signals can have any number
+/// of channels (e.g. a mono LFO). All changes are smoothed.
+///
+/// Input 0 - Signal to filter
+/// Input 1 - Frequency modulation (optional)
+/// Input 2 - Q modulation (optional)
+/// Input 3 - Gain modulation, in dB (optional)
+///
+/// Output 0 - Filtered signal
+#[derive(Debug, Clone)]
+pub struct BiquadFilterNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    filter: BiquadFilter,
+    /// Settings before any modulation is added
+    frequency: f32,
+    q: f32,
+    gain: f32,
+}
+
+impl BiquadFilterNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const FREQUENCY_INPUT_INDEX: usize = 1;
+    pub const Q_INPUT_INDEX: usize = 2;
+    pub const GAIN_INPUT_INDEX: usize = 3;
+
+    pub fn new(num_channels: impl Into<NumChannels>, filter_type: BiquadFilterType) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels, filter_type)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        filter_type: BiquadFilterType,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE, filter_type)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        filter_type: BiquadFilterType,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            filter: BiquadFilter::new(num_channels, sample_rate, filter_type),
+            frequency: BiquadFilter::DEFAULT_FREQUENCY,
+            q: BiquadFilter::DEFAULT_Q,
+            gain: BiquadFilter::DEFAULT_GAIN,
+        }
+    }
+
+    pub fn filter_type(&self) -> BiquadFilterType {
+        self.filter.filter_type()
+    }
+
+    pub fn set_filter_type(&mut self, filter_type: BiquadFilterType) -> &mut Self {
+        self.filter.set_filter_type(filter_type);
+        self
+    }
+
+    /// Cutoff or center frequency (before modulation)
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    pub fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.frequency = frequency;
+        self.filter.set_frequency(frequency);
+        self
+    }
+
+    /// Q (before modulation)
+    pub fn q(&self) -> f32 {
+        self.q
+    }
+
+    pub fn set_q(&mut self, q: f32) -> &mut Self {
+        self.q = q;
+        self.filter.set_q(q);
+        self
+    }
+
+    /// Gain in dB (before modulation)
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Only used by the peaking and shelf filter types
+    pub fn set_gain(&mut self, gain: f32) -> &mut Self {
+        self.gain = gain;
+        self.filter.set_gain(gain);
+        self
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.filter.smoothing_time()
+    }
+
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.filter.set_smoothing_time(smoothing_time);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.filter.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.filter.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Calculates how the filter changes the level and phase of each of
+    /// the given `frequencies` (e.g. for plotting the filter's curve in a UI).
+    ///
+    /// Modulation inputs are not included in the response. Once the node has been
+    /// moved into the audio thread, use `biquad_frequency_response` with its settings instead.
+    pub fn frequency_response(&self, frequencies: &[f32]) -> Vec<FrequencyResponse> {
+        biquad_frequency_response(
+            self.filter_type(),
+            self.frequency,
+            self.q,
+            self.gain,
+            self.sample_rate(),
+            frequencies,
+        )
+    }
+}
+
+impl Node for BiquadFilterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut frequency_modulation = 0.0;
+        let mut q_modulation = 0.0;
+        let mut gain_modulation = 0.0;
+
+        for input in inputs {
+            let modulation = input.data().first().copied().unwrap_or_default();
+            match input.to_index() {
+                Self::FREQUENCY_INPUT_INDEX => frequency_modulation = modulation,
+                Self::Q_INPUT_INDEX => q_modulation = modulation,
+                Self::GAIN_INPUT_INDEX => gain_modulation = modulation,
+                _ => signal = Some(input),
+            }
+        }
+
+        self.filter
+            .set_frequency(self.frequency + frequency_modulation)
+            .set_q(self.q + q_modulation)
+            .set_gain(self.gain + gain_modulation);
+
+        let mut output = outputs
+            .next()
+            .expect("BiquadFilterNode should have one and only one output connection");
+
+        match signal {
+            Some(signal) => output.data_mut().copy_from_slice(signal.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.filter.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        4
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    /// Modulation inputs only use their first channel
+    fn accepts_any_num_channels(&self, input_index: usize) -> bool {
+        input_index != Self::SIGNAL_INPUT_INDEX
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("BiquadFilterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<BiquadFilterNodeMessage>()?;
+
+        match message {
+            BiquadFilterNodeMessage::SetFilterType { filter_type } => {
+                self.set_filter_type(filter_type);
+            }
+            BiquadFilterNodeMessage::SetFrequency { frequency } => {
+                self.set_frequency(frequency);
+            }
+            BiquadFilterNodeMessage::SetQ { q } => {
+                self.set_q(q);
+            }
+            BiquadFilterNodeMessage::SetGain { gain } => {
+                self.set_gain(gain);
+            }
+            BiquadFilterNodeMessage::SetSmoothingTime { smoothing_time } => {
+                self.set_smoothing_time(smoothing_time);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum BiquadFilterNodeMessage {
+    SetFilterType { filter_type: BiquadFilterType },
+    SetFrequency { frequency: f32 },
+    SetQ { q: f32 },
+    SetGain { gain: f32 },
+    SetSmoothingTime { smoothing_time: Duration },
+}
+
+impl PartialEq for BiquadFilterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for BiquadFilterNode {}
+
+impl PartialOrd for BiquadFilterNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BiquadFilterNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_biquad_filter_node {
+    use std::{cell::RefCell, f32::consts::TAU, time::Duration};
+
+    use resonix_core::BiquadFilterType;
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{BiquadFilterNode, Connection, Node};
+
+    const SAMPLE_RATE: u32 = 48000;
+
+    /// Filters a sine wave and returns the peak amplitude of the output after it settles
+    fn filtered_sine_amplitude(
+        node: &mut BiquadFilterNode,
+        frequency: f32,
+        frequency_modulation: Option<f32>,
+    ) -> f32 {
+        let modulation_connection = RefCell::new(Connection::from_test_data(
+            1,
+            1,
+            vec![frequency_modulation.unwrap_or_default()],
+            0,
+            BiquadFilterNode::FREQUENCY_INPUT_INDEX,
+        ));
+        let output_connection = RefCell::new(Connection::new(1));
+
+        (0..SAMPLE_RATE / 10)
+            .map(|i| {
+                let sample = (TAU * frequency * i as f32 / SAMPLE_RATE as f32).sin();
+                let signal_connection =
+                    RefCell::new(Connection::from_test_data(0, 1, vec![sample], 0, 0));
+                {
+                    let outputs = [output_connection.borrow_mut()];
+                    if frequency_modulation.is_some() {
+                        let inputs = [modulation_connection.borrow(), signal_connection.borrow()];
+                        node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+                    } else {
+                        let inputs = [signal_connection.borrow()];
+                        node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+                    }
+                }
+                let output = output_connection.borrow().data()[0];
+                output
+            })
+            .skip(SAMPLE_RATE as usize / 20)
+            .fold(0.0, |max, sample| sample.abs().max(max))
+    }
+
+    #[test]
+    fn should_filter_signal() {
+        let mut node =
+            BiquadFilterNode::new_with_full_config(0, 1, SAMPLE_RATE, Default::default());
+        node.set_frequency(500.0);
+
+        assert!(filtered_sine_amplitude(&mut node, 100.0, None) > 0.95);
+        assert!(filtered_sine_amplitude(&mut node, 5000.0, None) < 0.02);
+
+        node.set_filter_type(BiquadFilterType::HighPass);
+        assert!(filtered_sine_amplitude(&mut node, 5000.0, None) > 0.95);
+    }
+
+    #[test]
+    fn should_add_modulation_to_frequency() {
+        let mut node =
+            BiquadFilterNode::new_with_full_config(0, 1, SAMPLE_RATE, Default::default());
+        node.set_frequency(500.0)
+            .set_smoothing_time(Duration::from_millis(1));
+
+        // modulated up to 10 kHz, 5 kHz passes through
+        assert!(filtered_sine_amplitude(&mut node, 5000.0, Some(9500.0)) > 0.95);
+        assert_eq!(node.frequency(), 500.0);
+    }
+
+    #[test]
+    fn should_accept_any_num_channels_for_modulation_inputs_only() {
+        let node = BiquadFilterNode::new(2, Default::default());
+
+        assert!(!node.accepts_any_num_channels(BiquadFilterNode::SIGNAL_INPUT_INDEX));
+        assert!(node.accepts_any_num_channels(BiquadFilterNode::FREQUENCY_INPUT_INDEX));
+        assert!(node.accepts_any_num_channels(BiquadFilterNode::Q_INPUT_INDEX));
+        assert!(node.accepts_any_num_channels(BiquadFilterNode::GAIN_INPUT_INDEX));
+    }
+
+    #[test]
+    fn should_calculate_frequency_response() {
+        let mut node =
+            BiquadFilterNode::new_with_full_config(0, 2, SAMPLE_RATE, Default::default());
+        node.set_filter_type(BiquadFilterType::Peaking)
+            .set_frequency(1000.0)
+            .set_gain(6.0);
+
+        let response = node.frequency_response(&[20.0, 1000.0]);
+        assert_difference_is_within_tolerance(response[0].magnitude_db(), 0.0, 0.1);
+        assert_difference_is_within_tolerance(response[1].magnitude_db(), 6.0, 0.01);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, BiquadFilterNodeMessage};
+
+        let mut node = BiquadFilterNode::new(1, BiquadFilterType::LowPass);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(BiquadFilterNodeMessage::SetFrequency { frequency: 2000.0 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.frequency(), 2000.0);
+    }
+}
@@ -0,0 +1,670 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use resonix_core::{AudioBuffer, Interpolation, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    async_channel::{Receiver, Sender},
+    resonix_dac::DACConfig,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Playback state shared between the `BufferPlayerNode` and any `BufferPlayerHandle`s
+#[derive(Debug, Default)]
+struct BufferPlayerState {
+    is_playing: AtomicBool,
+    /// Incremented every time playback reaches the end of the buffer
+    num_times_finished: AtomicUsize,
+    /// Playhead position (in frames of the buffer) stored as the bits of an `f64`
+    playhead: AtomicU64,
+}
+
+/// Plays back a shared, multichannel `AudioBuffer`.
+///
+/// Playback can be started, stopped, looped between arbitrary loop points,
+/// and sped up / slowed down / reversed by changing the playback rate
+/// (samples are interpolated when reading between frames of the buffer).
+///
+/// If the buffer's sample rate differs from the audio context's sample rate,
+/// the playback rate is adjusted so that the buffer plays back at its original pitch.
+///
+/// Each output channel plays the buffer channel with the same index. If the
+/// buffer has fewer channels than the node, buffer channels are repeated
+/// (e.g. a mono buffer plays through all output channels).
+///
+/// When playback reaches the end of the buffer (or the beginning, when
+/// playing in reverse) without looping, playback stops and a
+/// playback-finished event is published to the `BufferPlayerHandle`.
+///
+/// Buffers replaced by `BufferPlayerNodeMessage::SetBuffer` are sent back
+/// to be dropped on the main thread (see `replaced_buffers`).
+///
+/// Output 0 - Buffer audio (silence while stopped)
+#[derive(Debug, Clone)]
+pub struct BufferPlayerNode {
+    uid: NodeUid,
+    num_outgoing_channels: NumChannels,
+    buffer: Arc<AudioBuffer>,
+    sample_rate: SampleRate,
+    playback_rate: f32,
+    interpolation: Interpolation,
+    is_playing: bool,
+    is_looping: bool,
+    loop_start: usize,
+    loop_end: usize,
+    playhead: f64,
+    state: Arc<BufferPlayerState>,
+    #[cfg(feature = "dac")]
+    replaced_buffers_tx: Sender<Arc<AudioBuffer>>,
+    #[cfg(feature = "dac")]
+    replaced_buffers_rx: Receiver<Arc<AudioBuffer>>,
+}
+
+impl BufferPlayerNode {
+    /// How many replaced buffers can wait to be dropped on the main thread
+    #[cfg(feature = "dac")]
+    pub const REPLACED_BUFFERS_CAPACITY: usize = 8;
+
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, buffer: Arc<AudioBuffer>) -> Self {
+        Self::new_with_uid(0, num_outgoing_channels, buffer)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        buffer: Arc<AudioBuffer>,
+    ) -> Self {
+        // until the audio context's sample rate is known, play back at the buffer's own rate
+        let sample_rate = buffer.sample_rate();
+        Self::new_with_full_config(uid, num_outgoing_channels, sample_rate, buffer)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        buffer: Arc<AudioBuffer>,
+    ) -> Self {
+        #[cfg(feature = "dac")]
+        let (replaced_buffers_tx, replaced_buffers_rx) =
+            async_channel::bounded(Self::REPLACED_BUFFERS_CAPACITY);
+        Self {
+            uid,
+            num_outgoing_channels: num_outgoing_channels.into(),
+            buffer,
+            sample_rate: sample_rate.into(),
+            playback_rate: 1.0,
+            interpolation: Interpolation::default(),
+            is_playing: false,
+            is_looping: false,
+            loop_start: 0,
+            loop_end: 0,
+            playhead: 0.0,
+            state: Arc::new(BufferPlayerState::default()),
+            #[cfg(feature = "dac")]
+            replaced_buffers_tx,
+            #[cfg(feature = "dac")]
+            replaced_buffers_rx,
+        }
+    }
+
+    /// Returns a handle that can be used to observe playback from the main thread
+    pub fn handle(&self) -> BufferPlayerHandle {
+        BufferPlayerHandle {
+            num_times_finished: self.state.num_times_finished.load(Ordering::Acquire),
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    pub fn buffer(&self) -> &Arc<AudioBuffer> {
+        &self.buffer
+    }
+
+    /// Replaces the buffer being played. The playhead is
+    /// kept where it is, unless it falls outside the new buffer.
+    pub fn set_buffer(&mut self, buffer: Arc<AudioBuffer>) -> &mut Self {
+        self.replace_buffer(buffer);
+        self
+    }
+
+    /// Receives the buffers that were replaced by `BufferPlayerNodeMessage::SetBuffer`,
+    /// so that they can be dropped on the main thread rather than the audio thread.
+    ///
+    /// Once `REPLACED_BUFFERS_CAPACITY` are waiting to be received,
+    /// any further replaced buffers are dropped on the audio thread.
+    #[cfg(feature = "dac")]
+    pub fn replaced_buffers(&self) -> Receiver<Arc<AudioBuffer>> {
+        self.replaced_buffers_rx.clone()
+    }
+
+    /// Like `set_buffer`, but returns the replaced buffer
+    fn replace_buffer(&mut self, buffer: Arc<AudioBuffer>) -> Arc<AudioBuffer> {
+        let replaced_buffer = std::mem::replace(&mut self.buffer, buffer);
+        self.playhead = self.playhead.clamp(0.0, self.buffer.num_frames() as f64);
+        self.publish_playhead();
+        replaced_buffer
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self
+    }
+
+    pub fn playback_rate(&self) -> f32 {
+        self.playback_rate
+    }
+
+    /// 1.0 is normal speed, 0.5 is half speed (an octave lower),
+    /// and negative values play the buffer in reverse.
+    pub fn set_playback_rate(&mut self, playback_rate: f32) -> &mut Self {
+        self.playback_rate = playback_rate;
+        self
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) -> &mut Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.is_playing
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.is_looping
+    }
+
+    pub fn set_is_looping(&mut self, is_looping: bool) -> &mut Self {
+        self.is_looping = is_looping;
+        self
+    }
+
+    /// Returns the loop region as `(loop_start, loop_end)` frames, where `loop_end` is exclusive.
+    ///
+    /// If the configured loop points are invalid (e.g. `loop_end` is 0 or
+    /// comes before `loop_start`), the whole buffer is looped.
+    pub fn loop_points(&self) -> (usize, usize) {
+        let num_frames = self.buffer.num_frames();
+        let loop_end = self.loop_end.min(num_frames);
+        if self.loop_start < loop_end {
+            (self.loop_start, loop_end)
+        } else {
+            (0, num_frames)
+        }
+    }
+
+    /// Sets the loop region in frames of the buffer (`loop_end` is exclusive)
+    pub fn set_loop_points(&mut self, loop_start: usize, loop_end: usize) -> &mut Self {
+        self.loop_start = loop_start;
+        self.loop_end = loop_end;
+        self
+    }
+
+    /// Current playhead position in frames of the buffer
+    pub fn playhead(&self) -> f64 {
+        self.playhead
+    }
+
+    /// Moves the playhead to the given frame of the buffer
+    pub fn seek(&mut self, frame: f64) -> &mut Self {
+        self.playhead = frame.clamp(0.0, self.buffer.num_frames() as f64);
+        self.publish_playhead();
+        self
+    }
+
+    /// Starts (or resumes) playback from the current playhead position.
+    ///
+    /// If the playhead has already reached the end of the buffer
+    /// (or the beginning, when playing in reverse), playback starts over.
+    pub fn start(&mut self) -> &mut Self {
+        let num_frames = self.buffer.num_frames() as f64;
+        if self.playback_rate >= 0.0 && self.playhead >= num_frames {
+            self.playhead = 0.0;
+        } else if self.playback_rate < 0.0 && self.playhead <= 0.0 {
+            self.playhead = (num_frames - 1.0).max(0.0);
+        }
+        self.publish_playhead();
+        self.set_is_playing(true)
+    }
+
+    /// Pauses playback, leaving the playhead where it is
+    pub fn pause(&mut self) -> &mut Self {
+        self.set_is_playing(false)
+    }
+
+    /// Stops playback and rewinds the playhead to the beginning of the buffer
+    pub fn stop(&mut self) -> &mut Self {
+        self.playhead = 0.0;
+        self.publish_playhead();
+        self.set_is_playing(false)
+    }
+
+    fn set_is_playing(&mut self, is_playing: bool) -> &mut Self {
+        self.is_playing = is_playing;
+        self.state.is_playing.store(is_playing, Ordering::Release);
+        self
+    }
+
+    fn publish_playhead(&self) {
+        self.state
+            .playhead
+            .store(self.playhead.to_bits(), Ordering::Release);
+    }
+
+    fn finish(&mut self) {
+        self.set_is_playing(false);
+        self.state.num_times_finished.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Number of buffer frames to advance for every frame of output
+    fn increment(&self) -> f64 {
+        let sample_rate_ratio = if self.sample_rate.get() == 0 {
+            1.0
+        } else {
+            self.buffer.sample_rate().get() as f64 / self.sample_rate.get() as f64
+        };
+        self.playback_rate as f64 * sample_rate_ratio
+    }
+
+    fn advance_playhead(&mut self) {
+        let increment = self.increment();
+        self.playhead += increment;
+
+        let num_frames = self.buffer.num_frames() as f64;
+
+        if self.is_looping {
+            let (loop_start, loop_end) = self.loop_points();
+            let (loop_start, loop_end) = (loop_start as f64, loop_end as f64);
+            let loop_len = loop_end - loop_start;
+            if loop_len <= 0.0 {
+                return;
+            }
+            if increment >= 0.0 && self.playhead >= loop_end {
+                self.playhead = loop_start + (self.playhead - loop_end) % loop_len;
+            } else if increment < 0.0 && self.playhead < loop_start {
+                self.playhead = loop_end - (loop_start - self.playhead) % loop_len;
+            }
+        } else if self.playhead >= num_frames {
+            self.playhead = num_frames;
+            self.finish();
+        } else if self.playhead < 0.0 {
+            self.playhead = 0.0;
+            self.finish();
+        }
+    }
+}
+
+impl Node for BufferPlayerNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("BufferPlayerNode should have one and only one output connection");
+
+        if !self.is_playing || self.buffer.num_channels().get() == 0 {
+            output.update_data(|frame| frame.fill(0.0));
+            return;
+        }
+
+        let buffer = &self.buffer;
+        let interpolation = self.interpolation;
+        let playhead = self.playhead;
+        let loop_points = self.is_looping.then(|| self.loop_points());
+        let num_buffer_channels = buffer.num_channels().get();
+        output.update_data(|frame| {
+            frame.iter_mut().enumerate().for_each(|(i, sample)| {
+                let channel = buffer.channels()[i % num_buffer_channels].as_slice();
+                *sample = match loop_points {
+                    // interpolate from the end of the loop back into its start
+                    Some((loop_start, loop_end)) => {
+                        interpolation.sample_at_looped(channel, playhead, loop_start, loop_end)
+                    }
+                    None => interpolation.sample_at(channel, playhead),
+                };
+            })
+        });
+
+        self.advance_playhead();
+        self.publish_playhead();
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("BufferPlayerNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<BufferPlayerNodeMessage>()?;
+
+        match message {
+            BufferPlayerNodeMessage::Start => {
+                self.start();
+            }
+            BufferPlayerNodeMessage::Pause => {
+                self.pause();
+            }
+            BufferPlayerNodeMessage::Stop => {
+                self.stop();
+            }
+            BufferPlayerNodeMessage::Seek { frame } => {
+                self.seek(frame);
+            }
+            BufferPlayerNodeMessage::SetBuffer { buffer } => {
+                let replaced_buffer = self.replace_buffer(buffer);
+                // if the channel is full, there's nowhere else to drop it
+                let _ = self.replaced_buffers_tx.try_send(replaced_buffer);
+            }
+            BufferPlayerNodeMessage::SetPlaybackRate { playback_rate } => {
+                self.set_playback_rate(playback_rate);
+            }
+            BufferPlayerNodeMessage::SetInterpolation { interpolation } => {
+                self.set_interpolation(interpolation);
+            }
+            BufferPlayerNodeMessage::SetIsLooping { is_looping } => {
+                self.set_is_looping(is_looping);
+            }
+            BufferPlayerNodeMessage::SetLoopPoints {
+                loop_start,
+                loop_end,
+            } => {
+                self.set_loop_points(loop_start, loop_end);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum BufferPlayerNodeMessage {
+    Start,
+    Pause,
+    Stop,
+    Seek { frame: f64 },
+    SetBuffer { buffer: Arc<AudioBuffer> },
+    SetPlaybackRate { playback_rate: f32 },
+    SetInterpolation { interpolation: Interpolation },
+    SetIsLooping { is_looping: bool },
+    SetLoopPoints { loop_start: usize, loop_end: usize },
+}
+
+impl PartialEq for BufferPlayerNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for BufferPlayerNode {}
+
+impl PartialOrd for BufferPlayerNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BufferPlayerNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+/// The `BufferPlayerHandle` allows observing a `BufferPlayerNode`'s
+/// playback from the main thread, even after that node has been sent to the audio thread.
+///
+/// This struct can be safely and cheaply cloned
+#[derive(Debug, Clone)]
+pub struct BufferPlayerHandle {
+    state: Arc<BufferPlayerState>,
+    num_times_finished: usize,
+}
+
+impl BufferPlayerHandle {
+    pub fn is_playing(&self) -> bool {
+        self.state.is_playing.load(Ordering::Acquire)
+    }
+
+    /// Current playhead position in frames of the buffer
+    pub fn playhead(&self) -> f64 {
+        f64::from_bits(self.state.playhead.load(Ordering::Acquire))
+    }
+
+    /// Total number of times playback has reached the end of the buffer
+    pub fn num_times_finished(&self) -> usize {
+        self.state.num_times_finished.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if playback has finished since the last time this was called
+    pub fn poll_finished(&mut self) -> bool {
+        let num_times_finished = self.num_times_finished();
+        let has_finished = num_times_finished != self.num_times_finished;
+        self.num_times_finished = num_times_finished;
+        has_finished
+    }
+}
+
+#[cfg(test)]
+mod test_buffer_player_node {
+    use std::sync::Arc;
+
+    use resonix_core::{AudioBuffer, Interpolation};
+
+    use crate::{nodes::test_utils::process_frame, BufferPlayerNode};
+
+    fn render(node: &mut BufferPlayerNode, num_frames: usize) -> Vec<Vec<f32>> {
+        (0..num_frames).map(|_| process_frame(node, &[])).collect()
+    }
+
+    fn ramp_buffer() -> Arc<AudioBuffer> {
+        Arc::new(
+            AudioBuffer::from_channels(
+                vec![vec![0.0, 1.0, 2.0, 3.0], vec![0.0, -1.0, -2.0, -3.0]],
+                4,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn should_output_silence_until_started() {
+        let mut node = BufferPlayerNode::new(2, ramp_buffer());
+        assert_eq!(render(&mut node, 2), vec![vec![0.0, 0.0]; 2]);
+    }
+
+    #[test]
+    fn should_play_buffer_and_publish_finished_event() {
+        let mut node = BufferPlayerNode::new(2, ramp_buffer());
+        let mut handle = node.handle();
+        node.start();
+
+        assert_eq!(
+            render(&mut node, 5),
+            vec![
+                vec![0.0, 0.0],
+                vec![1.0, -1.0],
+                vec![2.0, -2.0],
+                vec![3.0, -3.0],
+                vec![0.0, 0.0]
+            ]
+        );
+        assert!(!handle.is_playing());
+        assert!(handle.poll_finished());
+        assert!(!handle.poll_finished());
+        assert_eq!(handle.num_times_finished(), 1);
+    }
+
+    #[test]
+    fn should_interpolate_at_fractional_playback_rates() {
+        let mut node = BufferPlayerNode::new(1, ramp_buffer());
+        node.set_playback_rate(0.5).start();
+
+        let output: Vec<f32> = render(&mut node, 4).into_iter().map(|f| f[0]).collect();
+        assert_eq!(output, vec![0.0, 0.5, 1.0, 1.5]);
+    }
+
+    #[test]
+    fn should_play_in_reverse() {
+        let mut node = BufferPlayerNode::new(1, ramp_buffer());
+        let handle = node.handle();
+        node.set_playback_rate(-1.0)
+            .set_interpolation(Interpolation::Nearest)
+            .start();
+
+        // starting in reverse begins from the end of the buffer
+        let output: Vec<f32> = render(&mut node, 5).into_iter().map(|f| f[0]).collect();
+        assert_eq!(output, vec![3.0, 2.0, 1.0, 0.0, 0.0]);
+        assert_eq!(handle.num_times_finished(), 1);
+    }
+
+    #[test]
+    fn should_loop_between_loop_points() {
+        let mut node = BufferPlayerNode::new(1, ramp_buffer());
+        let handle = node.handle();
+        node.set_is_looping(true).set_loop_points(1, 3).start();
+
+        let output: Vec<f32> = render(&mut node, 7).into_iter().map(|f| f[0]).collect();
+        assert_eq!(output, vec![0.0, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0]);
+        assert!(handle.is_playing());
+        assert_eq!(handle.num_times_finished(), 0);
+    }
+
+    #[test]
+    fn should_interpolate_across_loop_boundary() {
+        let mut node = BufferPlayerNode::new(1, ramp_buffer());
+        node.set_playback_rate(0.5)
+            .set_is_looping(true)
+            .set_loop_points(1, 3)
+            .start();
+
+        // halfway between the end of the loop (2.0) and its start (1.0)
+        let output: Vec<f32> = render(&mut node, 8).into_iter().map(|f| f[0]).collect();
+        assert_eq!(output, vec![0.0, 0.5, 1.0, 1.5, 2.0, 1.5, 1.0, 1.5]);
+    }
+
+    #[test]
+    fn should_compensate_for_buffer_sample_rate() {
+        // buffer is at 4 Hz, context is at 8 Hz: every frame is played twice
+        let mut node = BufferPlayerNode::new_with_full_config(0, 1, 8, ramp_buffer());
+        node.set_interpolation(Interpolation::Nearest).start();
+
+        let output: Vec<f32> = render(&mut node, 4).into_iter().map(|f| f[0]).collect();
+        assert_eq!(output, vec![0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn should_repeat_buffer_channels_for_extra_output_channels() {
+        let mono = Arc::new(AudioBuffer::from_channels(vec![vec![0.5]], 4).unwrap());
+        let mut node = BufferPlayerNode::new(3, mono);
+        node.start();
+
+        assert_eq!(render(&mut node, 1), vec![vec![0.5; 3]]);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, BufferPlayerNodeMessage, Node};
+
+        let mut node = BufferPlayerNode::new(2, ramp_buffer());
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(BufferPlayerNodeMessage::SetPlaybackRate {
+                playback_rate: -2.0,
+            }),
+        })
+        .unwrap();
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(BufferPlayerNodeMessage::Start),
+        })
+        .unwrap();
+
+        assert_eq!(node.playback_rate(), -2.0);
+        assert!(node.is_playing());
+
+        let previous_buffer = Arc::clone(node.buffer());
+        let replaced_buffers = node.replaced_buffers();
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(BufferPlayerNodeMessage::SetBuffer {
+                buffer: ramp_buffer(),
+            }),
+        })
+        .unwrap();
+
+        // the replaced buffer is sent back instead of being dropped in the audio thread
+        assert!(Arc::ptr_eq(
+            &replaced_buffers.try_recv().unwrap(),
+            &previous_buffer
+        ));
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{Chorus, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Thickens the incoming signal by mixing it with copies of itself that are delayed
+/// by a slowly moving amount, which sounds like several slightly detuned voices.
+///
+/// Works with any number of channels: each channel's LFO is offset by
+/// `channel_phase_offset`, which makes the chorus sound wide.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Dry signal mixed with delayed signal
+#[derive(Debug, Clone)]
+pub struct ChorusNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    chorus: Chorus,
+}
+
+impl ChorusNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            chorus: Chorus::new(num_channels, sample_rate),
+        }
+    }
+
+    /// Frequency of the LFO (in Hz)
+    pub fn rate(&self) -> f32 {
+        self.chorus.rate()
+    }
+
+    pub fn set_rate(&mut self, rate: f32) -> &mut Self {
+        self.chorus.set_rate(rate);
+        self
+    }
+
+    /// How much of `Chorus::SWEEP` the LFO moves the delay through (from 0.0 to 1.0)
+    pub fn depth(&self) -> f32 {
+        self.chorus.depth()
+    }
+
+    pub fn set_depth(&mut self, depth: f32) -> &mut Self {
+        self.chorus.set_depth(depth);
+        self
+    }
+
+    pub fn feedback(&self) -> f32 {
+        self.chorus.feedback()
+    }
+
+    /// Clamped between -0.95 and 0.95
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.chorus.set_feedback(feedback);
+        self
+    }
+
+    /// Balance between the original signal (0.0) and the delayed signal (1.0)
+    pub fn mix(&self) -> f32 {
+        self.chorus.mix()
+    }
+
+    pub fn set_mix(&mut self, mix: f32) -> &mut Self {
+        self.chorus.set_mix(mix);
+        self
+    }
+
+    /// Shortest delay, which the LFO sweeps upwards from
+    pub fn delay(&self) -> Duration {
+        self.chorus.delay()
+    }
+
+    /// Clamped to `Chorus::MAX_DELAY`
+    pub fn set_delay(&mut self, delay: Duration) -> &mut Self {
+        self.chorus.set_delay(delay);
+        self
+    }
+
+    /// How far (in turns) each channel's LFO is ahead of the previous channel's LFO
+    pub fn channel_phase_offset(&self) -> f32 {
+        self.chorus.channel_phase_offset()
+    }
+
+    pub fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) -> &mut Self {
+        self.chorus.set_channel_phase_offset(channel_phase_offset);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.chorus.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.chorus.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Silences the delay and restarts the LFO
+    pub fn clear(&mut self) -> &mut Self {
+        self.chorus.clear();
+        self
+    }
+}
+
+impl Node for ChorusNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ChorusNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.chorus.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ChorusNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<ChorusNodeMessage>()?;
+
+        match message {
+            ChorusNodeMessage::SetRate { rate } => {
+                self.set_rate(rate);
+            }
+            ChorusNodeMessage::SetDepth { depth } => {
+                self.set_depth(depth);
+            }
+            ChorusNodeMessage::SetFeedback { feedback } => {
+                self.set_feedback(feedback);
+            }
+            ChorusNodeMessage::SetMix { mix } => {
+                self.set_mix(mix);
+            }
+            ChorusNodeMessage::SetDelay { delay } => {
+                self.set_delay(delay);
+            }
+            ChorusNodeMessage::SetChannelPhaseOffset {
+                channel_phase_offset,
+            } => {
+                self.set_channel_phase_offset(channel_phase_offset);
+            }
+            ChorusNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum ChorusNodeMessage {
+    SetRate { rate: f32 },
+
+    SetDepth { depth: f32 },
+
+    SetFeedback { feedback: f32 },
+
+    SetMix { mix: f32 },
+
+    SetDelay { delay: Duration },
+
+    SetChannelPhaseOffset { channel_phase_offset: f32 },
+
+    Clear,
+}
+
+impl PartialEq for ChorusNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ChorusNode {}
+
+impl PartialOrd for ChorusNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ChorusNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_chorus_node {
+    use std::time::Duration;
+
+    use crate::{nodes::test_utils::process_frame, ChorusNode};
+
+    fn render(node: &mut ChorusNode, frames: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        frames
+            .iter()
+            .map(|frame| process_frame(node, &[(0, frame)]))
+            .collect()
+    }
+
+    #[test]
+    fn should_mix_delayed_signal() {
+        let mut node = ChorusNode::new_with_full_config(0, 1, 1000);
+        node.set_depth(0.0).set_delay(Duration::from_millis(2));
+
+        let mut frames = vec![vec![0.0]; 4];
+        frames[0] = vec![1.0];
+        assert_eq!(
+            render(&mut node, &frames),
+            vec![vec![0.5], vec![0.0], vec![0.5], vec![0.0]]
+        );
+    }
+
+    #[test]
+    fn should_produce_deterministic_stereo_chorus() {
+        let mut node = ChorusNode::new_with_full_config(0, 2, 1000);
+        node.set_rate(20.0).set_depth(1.0);
+
+        let frames: Vec<_> = (0..60).map(|i| vec![(i as f32 * 0.5).sin(); 2]).collect();
+        let response = render(&mut node, &frames);
+
+        insta::assert_debug_snapshot!(response[14..].iter().step_by(3).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, ChorusNodeMessage, Node};
+
+        let mut node = ChorusNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ChorusNodeMessage::SetDepth { depth: 0.25 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.depth(), 0.25);
+    }
+}
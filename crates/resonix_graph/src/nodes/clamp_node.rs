@@ -0,0 +1,221 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+#[cfg(feature = "dac")]
+use crate::messages::{UpdateNodeError, UpdateNodeMessage};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Restricts every sample of the incoming signal to be between `min` and `max`
+/// (which is also known as hard clipping)
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Clamped signal
+#[derive(Debug, Clone)]
+pub struct ClampNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    min: f32,
+    max: f32,
+}
+
+impl ClampNode {
+    pub fn new(num_channels: impl Into<NumChannels>, min: f32, max: f32) -> Self {
+        Self::new_with_uid(0, num_channels, min, max)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        min: f32,
+        max: f32,
+    ) -> Self {
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+            min,
+            max,
+        }
+    }
+
+    pub fn min_value(&self) -> f32 {
+        self.min
+    }
+
+    pub fn set_min(&mut self, min: f32) -> &mut Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max_value(&self) -> f32 {
+        self.max
+    }
+
+    pub fn set_max(&mut self, max: f32) -> &mut Self {
+        self.max = max;
+        self
+    }
+}
+
+impl Node for ClampNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ClampNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        // `max` takes priority if the bounds are crossed (`f32::clamp` would panic)
+        output
+            .data_mut()
+            .iter_mut()
+            .for_each(|sample| *sample = sample.max(self.min).min(self.max));
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ClampNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<ClampNodeMessage>()?;
+
+        match message {
+            ClampNodeMessage::SetMin { min } => {
+                self.set_min(min);
+            }
+            ClampNodeMessage::SetMax { max } => {
+                self.set_max(max);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum ClampNodeMessage {
+    SetMin { min: f32 },
+    SetMax { max: f32 },
+}
+
+impl PartialEq for ClampNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ClampNode {}
+
+impl PartialOrd for ClampNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ClampNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_clamp_node {
+    use std::cell::RefCell;
+
+    use crate::{ClampNode, Connection, Node};
+
+    #[test]
+    fn should_clamp_signal() {
+        let mut clamp_node = ClampNode::new(4, -0.5, 0.5);
+
+        let input_connection = RefCell::new(Connection::from_test_data(
+            0,
+            4,
+            vec![-1.0, -0.25, 0.25, 1.0],
+            0,
+            0,
+        ));
+        let output_connection = RefCell::new(Connection::new(4));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            clamp_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(
+            output_connection.borrow().data(),
+            &vec![-0.5, -0.25, 0.25, 0.5]
+        );
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, ClampNodeMessage};
+
+        let mut clamp_node = ClampNode::new(1, -1.0, 1.0);
+
+        clamp_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(ClampNodeMessage::SetMax { max: 0.5 }),
+            })
+            .unwrap();
+
+        assert_eq!(clamp_node.max_value(), 0.5);
+        assert_eq!(clamp_node.min_value(), -1.0);
+    }
+}
@@ -0,0 +1,367 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{Compressor, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Reduces the dynamic range of the incoming signal by turning it down
+/// whenever its level rises above the threshold.
+///
+/// When a sidechain signal is connected, the sidechain's level (rather than the
+/// signal's own level) decides how much the signal is turned down, which can be
+/// used to "duck" one sound whenever another sound plays.
+///
+/// Input 0 - Signal
+/// Input 1 - Sidechain (optional)
+///
+/// Output 0 - Compressed signal
+#[derive(Debug, Clone)]
+pub struct CompressorNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    compressor: Compressor,
+}
+
+impl CompressorNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const SIDECHAIN_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            compressor: Compressor::new(num_channels, sample_rate),
+        }
+    }
+
+    /// Level (in dBFS) above which the signal is compressed
+    pub fn threshold(&self) -> f32 {
+        self.compressor.threshold()
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) -> &mut Self {
+        self.compressor.set_threshold(threshold);
+        self
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.compressor.ratio()
+    }
+
+    /// Clamped to at least 1.0 (no compression)
+    pub fn set_ratio(&mut self, ratio: f32) -> &mut Self {
+        self.compressor.set_ratio(ratio);
+        self
+    }
+
+    /// Width of the knee in dB
+    pub fn knee(&self) -> f32 {
+        self.compressor.knee()
+    }
+
+    pub fn set_knee(&mut self, knee: f32) -> &mut Self {
+        self.compressor.set_knee(knee);
+        self
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.compressor.attack()
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) -> &mut Self {
+        self.compressor.set_attack(attack);
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.compressor.release()
+    }
+
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.compressor.set_release(release);
+        self
+    }
+
+    /// Gain (in dB) applied after compression
+    pub fn makeup_gain(&self) -> f32 {
+        self.compressor.makeup_gain()
+    }
+
+    pub fn set_makeup_gain(&mut self, makeup_gain: f32) -> &mut Self {
+        self.compressor.set_makeup_gain(makeup_gain);
+        self
+    }
+
+    pub fn lookahead(&self) -> Duration {
+        self.compressor.lookahead()
+    }
+
+    /// Delays the signal (up to `Compressor::MAX_LOOKAHEAD`), so that gain reduction can begin before peaks arrive
+    pub fn set_lookahead(&mut self, lookahead: Duration) -> &mut Self {
+        self.compressor.set_lookahead(lookahead);
+        self
+    }
+
+    /// The current gain reduction in dB
+    pub fn gain_reduction(&self) -> f32 {
+        self.compressor.gain_reduction()
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.compressor.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.compressor.set_sample_rate(sample_rate);
+        self
+    }
+}
+
+impl Node for CompressorNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut sidechain = None;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::SIDECHAIN_INPUT_INDEX => sidechain = Some(input),
+                _ => signal = Some(input),
+            }
+        }
+
+        let mut output = outputs
+            .next()
+            .expect("CompressorNode should have one and only one output connection");
+
+        match signal {
+            Some(signal) => output.data_mut().copy_from_slice(signal.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.compressor.process_frame(
+            output.data_mut(),
+            sidechain.as_ref().map(|sidechain| sidechain.data()),
+        );
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("CompressorNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<CompressorNodeMessage>()?;
+
+        match message {
+            CompressorNodeMessage::SetThreshold { threshold } => {
+                self.set_threshold(threshold);
+            }
+            CompressorNodeMessage::SetRatio { ratio } => {
+                self.set_ratio(ratio);
+            }
+            CompressorNodeMessage::SetKnee { knee } => {
+                self.set_knee(knee);
+            }
+            CompressorNodeMessage::SetAttack { attack } => {
+                self.set_attack(attack);
+            }
+            CompressorNodeMessage::SetRelease { release } => {
+                self.set_release(release);
+            }
+            CompressorNodeMessage::SetMakeupGain { makeup_gain } => {
+                self.set_makeup_gain(makeup_gain);
+            }
+            CompressorNodeMessage::SetLookahead { lookahead } => {
+                self.set_lookahead(lookahead);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum CompressorNodeMessage {
+    SetThreshold { threshold: f32 },
+    SetRatio { ratio: f32 },
+    SetKnee { knee: f32 },
+    SetAttack { attack: Duration },
+    SetRelease { release: Duration },
+    SetMakeupGain { makeup_gain: f32 },
+    SetLookahead { lookahead: Duration },
+}
+
+impl PartialEq for CompressorNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for CompressorNode {}
+
+impl PartialOrd for CompressorNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CompressorNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_compressor_node {
+    use std::{cell::RefCell, time::Duration};
+
+    use crate::{CompressorNode, Connection, Node};
+
+    #[test]
+    fn should_compress_loud_signal() {
+        let mut node = CompressorNode::new_with_full_config(0, 2, 1000);
+        node.set_threshold(-20.0)
+            .set_ratio(4.0)
+            .set_knee(0.0)
+            .set_attack(Duration::ZERO)
+            .set_makeup_gain(5.0);
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![1.0, -1.0], 0, 0));
+        let output_connection = RefCell::new(Connection::new(2));
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        // 15 dB of gain reduction, plus 5 dB of makeup gain
+        let output = output_connection.borrow().data().to_vec();
+        assert!((output[0] - 0.316228).abs() < 0.00001);
+        assert!((output[1] - -0.316228).abs() < 0.00001);
+        assert_eq!(node.gain_reduction(), -15.0);
+    }
+
+    #[test]
+    fn should_duck_signal_with_sidechain() {
+        let mut node = CompressorNode::new_with_full_config(0, 1, 1000);
+        node.set_threshold(-20.0)
+            .set_ratio(f32::INFINITY)
+            .set_knee(0.0)
+            .set_attack(Duration::ZERO);
+
+        let signal_connection = RefCell::new(Connection::from_test_data(0, 1, vec![0.05], 0, 0));
+        let sidechain_connection = RefCell::new(Connection::from_test_data(
+            1,
+            1,
+            vec![1.0],
+            0,
+            CompressorNode::SIDECHAIN_INPUT_INDEX,
+        ));
+        let output_connection = RefCell::new(Connection::new(1));
+        {
+            let inputs = [signal_connection.borrow(), sidechain_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        // the quiet signal is turned down by as much as the sidechain is over the threshold
+        assert!((output_connection.borrow().data()[0] - 0.005).abs() < 0.00001);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, CompressorNodeMessage};
+
+        let mut node = CompressorNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(CompressorNodeMessage::SetRatio { ratio: 8.0 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.ratio(), 8.0);
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    sync::Arc,
+};
+
+use resonix_core::{Convolver, ConvolverError, ImpulseResponse, NumChannels};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    async_channel::{Receiver, Sender},
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Convolves the incoming signal with an impulse response (e.g. to add the reverb of a real room).
+///
+/// Impulse responses can be mono (applied to every channel), stereo (applied to even
+/// and odd channels), or true stereo (4 channels, for 2-channel signals only).
+/// See `ImpulseResponse` for how they are prepared.
+///
+/// The output is delayed by the impulse response's `block_size` samples.
+///
+/// Preparing an `ImpulseResponse` is expensive, so when swapping impulse responses
+/// while audio is running, prepare the new one on the main thread and send it in a message.
+/// Room for the longest impulse response is allocated up front (see `new_with_max_len`),
+/// and replaced impulse responses are sent back to be dropped on the main thread
+/// (see `replaced_impulse_responses`).
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Convolved signal (without the dry signal)
+#[derive(Debug, Clone)]
+pub struct ConvolverNode {
+    uid: NodeUid,
+    convolver: Convolver,
+    #[cfg(feature = "dac")]
+    replaced_impulse_responses_tx: Sender<Arc<ImpulseResponse>>,
+    #[cfg(feature = "dac")]
+    replaced_impulse_responses_rx: Receiver<Arc<ImpulseResponse>>,
+}
+
+impl ConvolverNode {
+    /// How many replaced impulse responses can wait to be dropped on the main thread
+    #[cfg(feature = "dac")]
+    pub const REPLACED_IMPULSE_RESPONSES_CAPACITY: usize = 8;
+
+    /// Makes room for impulse responses as long as `impulse_response`
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+    ) -> Result<Self, ConvolverError> {
+        Self::new_with_uid(0, num_channels, impulse_response)
+    }
+
+    /// Makes room for impulse responses up to `max_num_frames` long,
+    /// so that they can be swapped in while audio is running
+    pub fn new_with_max_len(
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+        max_num_frames: usize,
+    ) -> Result<Self, ConvolverError> {
+        Self::new_with_full_config(0, num_channels, impulse_response, max_num_frames)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+    ) -> Result<Self, ConvolverError> {
+        let max_num_frames = impulse_response.num_frames();
+        Self::new_with_full_config(uid, num_channels, impulse_response, max_num_frames)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        impulse_response: Arc<ImpulseResponse>,
+        max_num_frames: usize,
+    ) -> Result<Self, ConvolverError> {
+        #[cfg(feature = "dac")]
+        let (replaced_impulse_responses_tx, replaced_impulse_responses_rx) =
+            async_channel::bounded(Self::REPLACED_IMPULSE_RESPONSES_CAPACITY);
+        Ok(Self {
+            uid,
+            convolver: Convolver::new_with_max_len(num_channels, impulse_response, max_num_frames)?,
+            #[cfg(feature = "dac")]
+            replaced_impulse_responses_tx,
+            #[cfg(feature = "dac")]
+            replaced_impulse_responses_rx,
+        })
+    }
+
+    pub fn impulse_response(&self) -> &Arc<ImpulseResponse> {
+        self.convolver.impulse_response()
+    }
+
+    /// The longest impulse response (in frames) that can be swapped in
+    pub fn max_num_frames(&self) -> usize {
+        self.convolver.max_num_frames()
+    }
+
+    /// The new impulse response must have the same block size and be no longer than `max_num_frames`
+    pub fn set_impulse_response(
+        &mut self,
+        impulse_response: Arc<ImpulseResponse>,
+    ) -> Result<&mut Self, ConvolverError> {
+        self.convolver.set_impulse_response(impulse_response)?;
+        Ok(self)
+    }
+
+    /// Receives the impulse responses that were replaced by `ConvolverNodeMessage::SetImpulseResponse`,
+    /// so that they can be dropped on the main thread rather than the audio thread.
+    ///
+    /// Once `REPLACED_IMPULSE_RESPONSES_CAPACITY` are waiting to be received,
+    /// any further replaced impulse responses are dropped on the audio thread.
+    #[cfg(feature = "dac")]
+    pub fn replaced_impulse_responses(&self) -> Receiver<Arc<ImpulseResponse>> {
+        self.replaced_impulse_responses_rx.clone()
+    }
+
+    /// Silences any reverb tail
+    pub fn clear(&mut self) -> &mut Self {
+        self.convolver.clear();
+        self
+    }
+}
+
+impl Node for ConvolverNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ConvolverNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.convolver.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.convolver.num_channels()
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.convolver.num_channels()
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ConvolverNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let uid = self.uid;
+        let message = update_node_message.try_into::<ConvolverNodeMessage>()?;
+
+        let result = match message {
+            ConvolverNodeMessage::SetImpulseResponse { impulse_response } => self
+                .convolver
+                .set_impulse_response(impulse_response)
+                .map(|replaced_impulse_response| {
+                    // if the channel is full, there's nowhere else to drop it
+                    let _ = self
+                        .replaced_impulse_responses_tx
+                        .try_send(replaced_impulse_response);
+                }),
+            ConvolverNodeMessage::Clear => {
+                self.clear();
+                Ok(())
+            }
+        };
+
+        result.map_err(|_| UpdateNodeError::InvalidData { uid })
+    }
+}
+
+pub enum ConvolverNodeMessage {
+    SetImpulseResponse {
+        impulse_response: Arc<ImpulseResponse>,
+    },
+    Clear,
+}
+
+impl PartialEq for ConvolverNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ConvolverNode {}
+
+impl PartialOrd for ConvolverNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ConvolverNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_convolver_node {
+    use std::{cell::RefCell, sync::Arc};
+
+    use resonix_core::{AudioBuffer, ImpulseResponse};
+
+    use crate::{Connection, ConvolverNode, Node};
+
+    fn impulse_response(channels: Vec<Vec<f32>>) -> Arc<ImpulseResponse> {
+        let buffer = AudioBuffer::from_channels(channels, 44100).unwrap();
+        Arc::new(ImpulseResponse::new(&buffer, 16, false).unwrap())
+    }
+
+    fn impulse_response_of(node: &mut ConvolverNode, num_frames: usize) -> Vec<Vec<f32>> {
+        let num_channels = *node.num_outgoing_channels();
+        let output_connection = RefCell::new(Connection::new(num_channels));
+        (0..num_frames)
+            .map(|i| {
+                let sample = if i == 0 { 1.0 } else { 0.0 };
+                let input_connection = RefCell::new(Connection::from_test_data(
+                    0,
+                    num_channels,
+                    vec![sample; num_channels],
+                    0,
+                    0,
+                ));
+                {
+                    let inputs = [input_connection.borrow()];
+                    let outputs = [output_connection.borrow_mut()];
+                    node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+                }
+                let output = output_connection.borrow().data().to_vec();
+                output
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_convolve_stereo_signal() {
+        let mut node =
+            ConvolverNode::new(2, impulse_response(vec![vec![0.5, 0.25], vec![-1.0, 0.0]]))
+                .unwrap();
+
+        let response = impulse_response_of(&mut node, 18);
+        assert!(response[..16].iter().flatten().all(|sample| *sample == 0.0));
+        assert!((response[16][0] - 0.5).abs() < 0.0001);
+        assert!((response[16][1] - -1.0).abs() < 0.0001);
+        assert!((response[17][0] - 0.25).abs() < 0.0001);
+        assert!(response[17][1].abs() < 0.0001);
+    }
+
+    #[test]
+    fn should_reject_true_stereo_for_mono_signal() {
+        assert!(ConvolverNode::new(1, impulse_response(vec![vec![1.0]; 4])).is_err());
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, ConvolverNodeMessage};
+
+        let previous_impulse_response = impulse_response(vec![vec![1.0]]);
+        let mut node =
+            ConvolverNode::new_with_max_len(1, Arc::clone(&previous_impulse_response), 32).unwrap();
+        let replaced_impulse_responses = node.replaced_impulse_responses();
+        let new_impulse_response = impulse_response(vec![vec![0.5, 0.5]]);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ConvolverNodeMessage::SetImpulseResponse {
+                impulse_response: Arc::clone(&new_impulse_response),
+            }),
+        })
+        .unwrap();
+
+        assert!(Arc::ptr_eq(node.impulse_response(), &new_impulse_response));
+        assert!(Arc::ptr_eq(
+            &replaced_impulse_responses.try_recv().unwrap(),
+            &previous_impulse_response
+        ));
+
+        // too long to fit without allocating
+        let result = node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ConvolverNodeMessage::SetImpulseResponse {
+                impulse_response: impulse_response(vec![vec![0.5; 64]]),
+            }),
+        });
+        assert!(result.is_err());
+        assert!(Arc::ptr_eq(node.impulse_response(), &new_impulse_response));
+    }
+}
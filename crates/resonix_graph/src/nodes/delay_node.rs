@@ -0,0 +1,475 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{DelayLine, Interpolation, NumChannels, SampleRate, SmoothedValue};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Delays the incoming signal, optionally feeding the delayed signal
+/// back into the delay to create repeating echoes.
+///
+/// Memory for `max_delay_time` is allocated when the node is created, so
+/// changing the delay time (or the sample rate) never allocates on the audio thread.
+///
+/// The delay time can be modulated (e.g. by a low-frequency oscillator for
+/// chorus and flanging effects) by connecting a signal to the delay time input:
+/// the first channel of that input is added to the delay time, in seconds, so the
+/// modulation signal can have any number of channels (e.g. a mono LFO).
+///
+/// Input 0 - Signal to delay
+/// Input 1 - Delay time modulation, in seconds (optional)
+///
+/// Output 0 - Dry signal mixed with the delayed signal
+#[derive(Debug, Clone)]
+pub struct DelayNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    sample_rate: SampleRate,
+    max_delay_time: Duration,
+    /// Delay time in samples (before modulation)
+    delay: SmoothedValue,
+    delay_time: Duration,
+    feedback: f32,
+    wet: f32,
+    dry: f32,
+    interpolation: Interpolation,
+    /// One delay line for every channel
+    delay_lines: Vec<DelayLine>,
+}
+
+impl DelayNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    /// Delay lines are allocated with enough room for `max_delay_time` at this sample rate.
+    /// At higher sample rates, the longest possible delay is shortened proportionally.
+    pub const MAX_SAMPLE_RATE: u32 = 96000;
+
+    pub const DEFAULT_DELAY_TIME: Duration = Duration::from_millis(250);
+    pub const DEFAULT_FEEDBACK: f32 = 0.0;
+    pub const DEFAULT_WET: f32 = 0.5;
+    pub const DEFAULT_DRY: f32 = 1.0;
+    pub const DEFAULT_SMOOTHING_TIME: Duration = Duration::from_millis(50);
+
+    /// Feedback is kept just below 1.0 so that echoes always eventually die out
+    pub const MAX_FEEDBACK: f32 = 0.99;
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const DELAY_TIME_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>, max_delay_time: Duration) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels, max_delay_time)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        max_delay_time: Duration,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE, max_delay_time)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        max_delay_time: Duration,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        let sample_rate = sample_rate.into();
+        let max_delay =
+            (max_delay_time.as_secs_f64() * Self::MAX_SAMPLE_RATE as f64).ceil() as usize;
+        let delay_time = Self::DEFAULT_DELAY_TIME.min(max_delay_time);
+
+        Self {
+            uid,
+            num_channels,
+            sample_rate,
+            max_delay_time,
+            delay: SmoothedValue::new(
+                sample_rate,
+                Self::DEFAULT_SMOOTHING_TIME,
+                delay_time.as_secs_f32() * sample_rate.get() as f32,
+            ),
+            delay_time,
+            feedback: Self::DEFAULT_FEEDBACK,
+            wet: Self::DEFAULT_WET,
+            dry: Self::DEFAULT_DRY,
+            interpolation: Interpolation::default(),
+            delay_lines: vec![DelayLine::new(max_delay); *num_channels],
+        }
+    }
+
+    pub fn max_delay_time(&self) -> Duration {
+        self.max_delay_time
+    }
+
+    /// Delay time (before modulation)
+    pub fn delay_time(&self) -> Duration {
+        self.delay_time
+    }
+
+    /// Changes are smoothed, which bends the pitch of the delayed signal like a tape delay.
+    ///
+    /// The delay time is clamped to `max_delay_time`.
+    pub fn set_delay_time(&mut self, delay_time: Duration) -> &mut Self {
+        self.delay_time = delay_time.min(self.max_delay_time);
+        self.delay
+            .set_target(self.delay_time.as_secs_f32() * self.sample_rate.get() as f32);
+        self
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.delay.smoothing_time()
+    }
+
+    /// How long it takes to move to a new delay time
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.delay.set_smoothing_time(smoothing_time);
+        self
+    }
+
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Amount of the delayed signal that is fed back into the delay (from -0.99 to 0.99)
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.feedback = feedback.clamp(-Self::MAX_FEEDBACK, Self::MAX_FEEDBACK);
+        self
+    }
+
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    /// Gain of the delayed signal in the output
+    pub fn set_wet(&mut self, wet: f32) -> &mut Self {
+        self.wet = wet;
+        self
+    }
+
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Gain of the original signal in the output
+    pub fn set_dry(&mut self, dry: f32) -> &mut Self {
+        self.dry = dry;
+        self
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Determines how the delay lines are read between samples
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) -> &mut Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sample_rate = sample_rate.into();
+        self.delay
+            .set_sample_rate(self.sample_rate)
+            .set_value(self.delay_time.as_secs_f32() * self.sample_rate.get() as f32);
+        self
+    }
+
+    /// Silences any echoes that are still playing
+    pub fn clear(&mut self) -> &mut Self {
+        self.delay_lines.iter_mut().for_each(DelayLine::clear);
+        self
+    }
+}
+
+impl Node for DelayNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut delay_time_modulation = 0.0;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::DELAY_TIME_INPUT_INDEX => {
+                    delay_time_modulation = input.data().first().copied().unwrap_or_default();
+                }
+                _ => signal = Some(input),
+            }
+        }
+
+        let delay = self.delay.next_value() + delay_time_modulation * self.sample_rate.get() as f32;
+
+        let mut output = outputs
+            .next()
+            .expect("DelayNode should have one and only one output connection");
+
+        match signal {
+            Some(signal) => output.data_mut().copy_from_slice(signal.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        output
+            .data_mut()
+            .iter_mut()
+            .zip(self.delay_lines.iter_mut())
+            .for_each(|(sample, delay_line)| {
+                let delayed = delay_line.read(delay, self.interpolation);
+                delay_line.write(*sample + delayed * self.feedback);
+                *sample = *sample * self.dry + delayed * self.wet;
+            });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    /// Modulation inputs only use their first channel
+    fn accepts_any_num_channels(&self, input_index: usize) -> bool {
+        input_index != Self::SIGNAL_INPUT_INDEX
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("DelayNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<DelayNodeMessage>()?;
+
+        match message {
+            DelayNodeMessage::SetDelayTime { delay_time } => {
+                self.set_delay_time(delay_time);
+            }
+            DelayNodeMessage::SetSmoothingTime { smoothing_time } => {
+                self.set_smoothing_time(smoothing_time);
+            }
+            DelayNodeMessage::SetFeedback { feedback } => {
+                self.set_feedback(feedback);
+            }
+            DelayNodeMessage::SetWet { wet } => {
+                self.set_wet(wet);
+            }
+            DelayNodeMessage::SetDry { dry } => {
+                self.set_dry(dry);
+            }
+            DelayNodeMessage::SetInterpolation { interpolation } => {
+                self.set_interpolation(interpolation);
+            }
+            DelayNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum DelayNodeMessage {
+    SetDelayTime { delay_time: Duration },
+    SetSmoothingTime { smoothing_time: Duration },
+    SetFeedback { feedback: f32 },
+    SetWet { wet: f32 },
+    SetDry { dry: f32 },
+    SetInterpolation { interpolation: Interpolation },
+    Clear,
+}
+
+impl PartialEq for DelayNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for DelayNode {}
+
+impl PartialOrd for DelayNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DelayNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_delay_node {
+    use std::time::Duration;
+
+    use resonix_core::Interpolation;
+
+    use crate::{nodes::test_utils::process_frame, DelayNode};
+
+    /// Runs a signal through the node (one frame per input sample)
+    fn render(
+        node: &mut DelayNode,
+        signal: &[f32],
+        delay_time_modulation: Option<f32>,
+    ) -> Vec<f32> {
+        signal
+            .iter()
+            .map(|sample| match delay_time_modulation {
+                Some(modulation) => process_frame(
+                    node,
+                    &[
+                        (0, &[*sample]),
+                        (DelayNode::DELAY_TIME_INPUT_INDEX, &[modulation]),
+                    ],
+                )[0],
+                None => process_frame(node, &[(0, &[*sample])])[0],
+            })
+            .collect()
+    }
+
+    fn impulse(len: usize) -> Vec<f32> {
+        let mut signal = vec![0.0; len];
+        signal[0] = 1.0;
+        signal
+    }
+
+    #[test]
+    fn should_repeat_with_feedback() {
+        let mut node = DelayNode::new_with_full_config(0, 1, 1000, Duration::from_millis(10));
+        node.set_smoothing_time(Duration::ZERO)
+            .set_delay_time(Duration::from_millis(3))
+            .set_feedback(0.5)
+            .set_wet(1.0)
+            .set_dry(0.0);
+
+        assert_eq!(
+            render(&mut node, &impulse(10), None),
+            vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.25]
+        );
+    }
+
+    #[test]
+    fn should_mix_wet_and_dry() {
+        let mut node = DelayNode::new_with_full_config(0, 1, 1000, Duration::from_millis(10));
+        node.set_smoothing_time(Duration::ZERO)
+            .set_delay_time(Duration::from_millis(2))
+            .set_wet(0.5)
+            .set_dry(1.0);
+
+        assert_eq!(
+            render(&mut node, &impulse(4), None),
+            vec![1.0, 0.0, 0.5, 0.0]
+        );
+    }
+
+    #[test]
+    fn should_add_modulation_to_delay_time() {
+        let mut node = DelayNode::new_with_full_config(0, 1, 1000, Duration::from_millis(10));
+        node.set_smoothing_time(Duration::ZERO)
+            .set_delay_time(Duration::from_millis(2))
+            .set_interpolation(Interpolation::Nearest)
+            .set_wet(1.0)
+            .set_dry(0.0);
+
+        // 2ms + 3ms
+        assert_eq!(
+            render(&mut node, &impulse(6), Some(0.003)),
+            vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn should_clamp_delay_time_to_max() {
+        let mut node = DelayNode::new(2, Duration::from_millis(100));
+        node.set_delay_time(Duration::from_secs(1));
+
+        assert_eq!(node.delay_time(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn should_accept_any_num_channels_for_delay_time_modulation_only() {
+        use crate::Node;
+
+        let node = DelayNode::new(2, Duration::from_secs(1));
+
+        assert!(!node.accepts_any_num_channels(DelayNode::SIGNAL_INPUT_INDEX));
+        assert!(node.accepts_any_num_channels(DelayNode::DELAY_TIME_INPUT_INDEX));
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, DelayNodeMessage, Node};
+
+        let mut node = DelayNode::new(1, Duration::from_secs(1));
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(DelayNodeMessage::SetFeedback { feedback: 0.25 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.feedback(), 0.25);
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{
+    BreakpointEnvelope, EnvelopeGenerator, EnvelopeTriggerMode, NumChannels, SampleRate,
+};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    async_channel::{Receiver, Sender},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Generates an ADSR (or any other breakpoint) envelope, which can be
+/// multiplied with an oscillator or used to control a gain.
+///
+/// The envelope can be started and stopped with messages, or with an optional
+/// gate connection: each channel's gate is on whenever that channel's input is
+/// above 0.0, and each channel's envelope follows its own gate.
+///
+/// Envelopes replaced by `EnvelopeNodeMessage::SetEnvelope` are sent back
+/// to be dropped on the main thread (see `replaced_envelopes`).
+///
+/// Input 0 - Gate (optional)
+///
+/// Output 0 - Envelope
+#[derive(Debug, Clone)]
+pub struct EnvelopeNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    /// One generator for every channel
+    generators: Vec<EnvelopeGenerator>,
+    /// Whether each channel's gate input was on during the previous frame
+    gate_inputs: Vec<bool>,
+    #[cfg(feature = "dac")]
+    replaced_envelopes_tx: Sender<BreakpointEnvelope>,
+    #[cfg(feature = "dac")]
+    replaced_envelopes_rx: Receiver<BreakpointEnvelope>,
+}
+
+impl EnvelopeNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    /// How many replaced envelopes can wait to be dropped on the main thread
+    #[cfg(feature = "dac")]
+    pub const REPLACED_ENVELOPES_CAPACITY: usize = 8;
+
+    pub fn new(num_channels: impl Into<NumChannels>, envelope: BreakpointEnvelope) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels, envelope)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        envelope: BreakpointEnvelope,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE, envelope)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        envelope: BreakpointEnvelope,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        #[cfg(feature = "dac")]
+        let (replaced_envelopes_tx, replaced_envelopes_rx) =
+            async_channel::bounded(Self::REPLACED_ENVELOPES_CAPACITY);
+        Self {
+            uid,
+            num_channels,
+            generators: vec![EnvelopeGenerator::new(sample_rate, envelope); *num_channels],
+            gate_inputs: vec![false; *num_channels],
+            #[cfg(feature = "dac")]
+            replaced_envelopes_tx,
+            #[cfg(feature = "dac")]
+            replaced_envelopes_rx,
+        }
+    }
+
+    pub fn envelope(&self) -> Option<&BreakpointEnvelope> {
+        self.generators.first().map(EnvelopeGenerator::envelope)
+    }
+
+    /// Changes take effect once each channel's current segment ends
+    pub fn set_envelope(&mut self, envelope: BreakpointEnvelope) -> &mut Self {
+        self.replace_envelope(envelope);
+        self
+    }
+
+    /// Receives the envelopes that were replaced by `EnvelopeNodeMessage::SetEnvelope`,
+    /// so that they can be dropped on the main thread rather than the audio thread.
+    ///
+    /// Once `REPLACED_ENVELOPES_CAPACITY` are waiting to be received,
+    /// any further replaced envelopes are dropped on the audio thread.
+    #[cfg(feature = "dac")]
+    pub fn replaced_envelopes(&self) -> Receiver<BreakpointEnvelope> {
+        self.replaced_envelopes_rx.clone()
+    }
+
+    /// Like `set_envelope`, but returns the replaced envelope
+    /// (every channel shares the same segments, so only one needs to be kept)
+    fn replace_envelope(&mut self, envelope: BreakpointEnvelope) -> Option<BreakpointEnvelope> {
+        self.generators
+            .iter_mut()
+            .map(|generator| generator.replace_envelope(envelope.clone()))
+            .reduce(|replaced_envelope, _| replaced_envelope)
+    }
+
+    pub fn trigger_mode(&self) -> EnvelopeTriggerMode {
+        self.generators
+            .first()
+            .map(EnvelopeGenerator::trigger_mode)
+            .unwrap_or_default()
+    }
+
+    pub fn set_trigger_mode(&mut self, trigger_mode: EnvelopeTriggerMode) -> &mut Self {
+        self.generators.iter_mut().for_each(|generator| {
+            generator.set_trigger_mode(trigger_mode);
+        });
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.generators
+            .first()
+            .map(EnvelopeGenerator::sample_rate)
+            .unwrap_or(SampleRate::from(Self::DEFAULT_SAMPLE_RATE))
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.generators.iter_mut().for_each(|generator| {
+            generator.set_sample_rate(sample_rate);
+        });
+        self
+    }
+
+    /// Turns the gate on for every channel
+    pub fn gate_on(&mut self) -> &mut Self {
+        self.generators.iter_mut().for_each(|generator| {
+            generator.gate_on();
+        });
+        self
+    }
+
+    /// Turns the gate off for every channel
+    pub fn gate_off(&mut self) -> &mut Self {
+        self.generators.iter_mut().for_each(|generator| {
+            generator.gate_off();
+        });
+        self
+    }
+
+    /// Plays the whole envelope once on every channel, without sustaining
+    pub fn trigger(&mut self) -> &mut Self {
+        self.generators.iter_mut().for_each(|generator| {
+            generator.trigger();
+        });
+        self
+    }
+
+    /// Whether any channel's envelope is still moving or sustaining
+    pub fn is_active(&self) -> bool {
+        self.generators.iter().any(EnvelopeGenerator::is_active)
+    }
+}
+
+impl Node for EnvelopeNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        if let Some(gate_input) = inputs.next() {
+            gate_input
+                .data()
+                .iter()
+                .zip(self.gate_inputs.iter_mut())
+                .zip(self.generators.iter_mut())
+                .for_each(|((gate, was_gate_on), generator)| {
+                    let is_gate_on = *gate > 0.0;
+                    if is_gate_on && !*was_gate_on {
+                        generator.gate_on();
+                    } else if !is_gate_on && *was_gate_on {
+                        generator.gate_off();
+                    }
+                    *was_gate_on = is_gate_on;
+                });
+        }
+
+        let mut output = outputs
+            .next()
+            .expect("EnvelopeNode should have one and only one output connection");
+
+        output
+            .data_mut()
+            .iter_mut()
+            .zip(self.generators.iter_mut())
+            .for_each(|(sample, generator)| *sample = generator.next_sample());
+    }
+
+    /// Envelopes are treated as an input to the graph, since they can
+    /// be controlled entirely with messages (without a gate connection)
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("EnvelopeNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<EnvelopeNodeMessage>()?;
+
+        match message {
+            EnvelopeNodeMessage::GateOn => {
+                self.gate_on();
+            }
+            EnvelopeNodeMessage::GateOff => {
+                self.gate_off();
+            }
+            EnvelopeNodeMessage::Trigger => {
+                self.trigger();
+            }
+            EnvelopeNodeMessage::SetEnvelope { envelope } => {
+                if let Some(replaced_envelope) = self.replace_envelope(envelope) {
+                    // if the channel is full, there's nowhere else to drop it
+                    let _ = self.replaced_envelopes_tx.try_send(replaced_envelope);
+                }
+            }
+            EnvelopeNodeMessage::SetTriggerMode { trigger_mode } => {
+                self.set_trigger_mode(trigger_mode);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum EnvelopeNodeMessage {
+    GateOn,
+    GateOff,
+    Trigger,
+    SetEnvelope { envelope: BreakpointEnvelope },
+    SetTriggerMode { trigger_mode: EnvelopeTriggerMode },
+}
+
+impl PartialEq for EnvelopeNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for EnvelopeNode {}
+
+impl PartialOrd for EnvelopeNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EnvelopeNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_envelope_node {
+    use std::time::Duration;
+
+    use resonix_core::BreakpointEnvelope;
+
+    use crate::{nodes::test_utils::process_frame, EnvelopeNode};
+
+    /// 2-sample attack, 2-sample decay to 0.5, and 2-sample release (at a sample rate of 1000)
+    fn envelope_node(num_channels: usize) -> EnvelopeNode {
+        let envelope = BreakpointEnvelope::adsr(
+            Duration::from_millis(2),
+            Duration::from_millis(2),
+            0.5,
+            Duration::from_millis(2),
+        );
+        EnvelopeNode::new_with_full_config(0, num_channels, 1000, envelope)
+    }
+
+    fn render(node: &mut EnvelopeNode, gate: Option<&[f32]>) -> Vec<f32> {
+        match gate {
+            Some(gate) => process_frame(node, &[(0, gate)]),
+            None => process_frame(node, &[]),
+        }
+    }
+
+    #[test]
+    fn should_follow_gate_messages() {
+        let mut node = envelope_node(1);
+        assert_eq!(render(&mut node, None), vec![0.0]);
+
+        node.gate_on();
+        assert_eq!(render(&mut node, None), vec![0.5]);
+        assert_eq!(render(&mut node, None), vec![1.0]);
+        (0..4).for_each(|_| {
+            render(&mut node, None);
+        });
+        assert_eq!(render(&mut node, None), vec![0.5]);
+
+        node.gate_off();
+        render(&mut node, None);
+        assert_eq!(render(&mut node, None), vec![0.0]);
+        assert!(!node.is_active());
+    }
+
+    #[test]
+    fn should_follow_gate_connection_per_channel() {
+        let mut node = envelope_node(2);
+
+        assert_eq!(render(&mut node, Some(&[1.0, 0.0])), vec![0.5, 0.0]);
+        assert_eq!(render(&mut node, Some(&[1.0, 1.0])), vec![1.0, 0.5]);
+
+        // gate is still on: the first channel decays towards the sustain level
+        assert!(render(&mut node, Some(&[1.0, 1.0]))[0] < 1.0);
+        assert!(node.is_active());
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, EnvelopeNodeMessage, Node};
+
+        let mut node = envelope_node(1);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(EnvelopeNodeMessage::Trigger),
+        })
+        .unwrap();
+
+        assert_eq!(render(&mut node, None), vec![0.5]);
+
+        let previous_envelope = node.envelope().unwrap().clone();
+        let replaced_envelopes = node.replaced_envelopes();
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(EnvelopeNodeMessage::SetEnvelope {
+                envelope: BreakpointEnvelope::default(),
+            }),
+        })
+        .unwrap();
+
+        // the replaced envelope is sent back instead of being dropped in the audio thread
+        assert!(std::ptr::eq(
+            replaced_envelopes.try_recv().unwrap().segments(),
+            previous_envelope.segments()
+        ));
+    }
+}
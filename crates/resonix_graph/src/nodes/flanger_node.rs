@@ -0,0 +1,322 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{Flanger, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Sweeps comb filter notches up and down the incoming signal by mixing it with
+/// a copy of itself that is delayed by a very short, slowly moving amount.
+///
+/// Works with any number of channels: each channel's LFO is offset by
+/// `channel_phase_offset`, which makes the flanger sound wide.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Dry signal mixed with delayed signal
+#[derive(Debug, Clone)]
+pub struct FlangerNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    flanger: Flanger,
+}
+
+impl FlangerNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            flanger: Flanger::new(num_channels, sample_rate),
+        }
+    }
+
+    /// Frequency of the LFO (in Hz)
+    pub fn rate(&self) -> f32 {
+        self.flanger.rate()
+    }
+
+    pub fn set_rate(&mut self, rate: f32) -> &mut Self {
+        self.flanger.set_rate(rate);
+        self
+    }
+
+    /// How much of `Flanger::SWEEP` the LFO moves the delay through (from 0.0 to 1.0)
+    pub fn depth(&self) -> f32 {
+        self.flanger.depth()
+    }
+
+    pub fn set_depth(&mut self, depth: f32) -> &mut Self {
+        self.flanger.set_depth(depth);
+        self
+    }
+
+    pub fn feedback(&self) -> f32 {
+        self.flanger.feedback()
+    }
+
+    /// Clamped between -0.95 and 0.95
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.flanger.set_feedback(feedback);
+        self
+    }
+
+    /// Balance between the original signal (0.0) and the delayed signal (1.0)
+    pub fn mix(&self) -> f32 {
+        self.flanger.mix()
+    }
+
+    pub fn set_mix(&mut self, mix: f32) -> &mut Self {
+        self.flanger.set_mix(mix);
+        self
+    }
+
+    /// Shortest delay, which the LFO sweeps upwards from
+    pub fn delay(&self) -> Duration {
+        self.flanger.delay()
+    }
+
+    /// Clamped to `Flanger::MAX_DELAY`
+    pub fn set_delay(&mut self, delay: Duration) -> &mut Self {
+        self.flanger.set_delay(delay);
+        self
+    }
+
+    /// How far (in turns) each channel's LFO is ahead of the previous channel's LFO
+    pub fn channel_phase_offset(&self) -> f32 {
+        self.flanger.channel_phase_offset()
+    }
+
+    pub fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) -> &mut Self {
+        self.flanger.set_channel_phase_offset(channel_phase_offset);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.flanger.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.flanger.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Silences the delay and restarts the LFO
+    pub fn clear(&mut self) -> &mut Self {
+        self.flanger.clear();
+        self
+    }
+}
+
+impl Node for FlangerNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("FlangerNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.flanger.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("FlangerNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<FlangerNodeMessage>()?;
+
+        match message {
+            FlangerNodeMessage::SetRate { rate } => {
+                self.set_rate(rate);
+            }
+            FlangerNodeMessage::SetDepth { depth } => {
+                self.set_depth(depth);
+            }
+            FlangerNodeMessage::SetFeedback { feedback } => {
+                self.set_feedback(feedback);
+            }
+            FlangerNodeMessage::SetMix { mix } => {
+                self.set_mix(mix);
+            }
+            FlangerNodeMessage::SetDelay { delay } => {
+                self.set_delay(delay);
+            }
+            FlangerNodeMessage::SetChannelPhaseOffset {
+                channel_phase_offset,
+            } => {
+                self.set_channel_phase_offset(channel_phase_offset);
+            }
+            FlangerNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum FlangerNodeMessage {
+    SetRate { rate: f32 },
+
+    SetDepth { depth: f32 },
+
+    SetFeedback { feedback: f32 },
+
+    SetMix { mix: f32 },
+
+    SetDelay { delay: Duration },
+
+    SetChannelPhaseOffset { channel_phase_offset: f32 },
+
+    Clear,
+}
+
+impl PartialEq for FlangerNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for FlangerNode {}
+
+impl PartialOrd for FlangerNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FlangerNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_flanger_node {
+    use std::time::Duration;
+
+    use crate::{nodes::test_utils::process_frame, FlangerNode};
+
+    fn render(node: &mut FlangerNode, frames: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        frames
+            .iter()
+            .map(|frame| process_frame(node, &[(0, frame)]))
+            .collect()
+    }
+
+    #[test]
+    fn should_feed_back_delayed_signal() {
+        let mut node = FlangerNode::new_with_full_config(0, 1, 1000);
+        node.set_depth(0.0)
+            .set_mix(1.0)
+            .set_feedback(-0.5)
+            .set_delay(Duration::from_millis(1));
+
+        let mut frames = vec![vec![0.0]; 4];
+        frames[0] = vec![1.0];
+        assert_eq!(
+            render(&mut node, &frames),
+            vec![vec![0.0], vec![1.0], vec![-0.5], vec![0.25]]
+        );
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, FlangerNodeMessage, Node};
+
+        let mut node = FlangerNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(FlangerNodeMessage::SetFeedback { feedback: -0.25 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.feedback(), -0.25);
+    }
+}
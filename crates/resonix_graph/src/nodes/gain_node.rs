@@ -0,0 +1,376 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{Decibel, NumChannels, SampleRate, SmoothedValue};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Scales the amplitude of the incoming signal.
+///
+/// Gain changes are smoothed sample-by-sample to prevent clicks and "zipper noise",
+/// which makes this node a good place to control the master volume right before the `DACNode`.
+///
+/// Fading in and out (e.g. when pausing and playing) is handled separately from the gain,
+/// so fading back in always returns to the most recently set gain.
+///
+/// The gain can be modulated by connecting a signal to the gain input:
+/// the first channel of that input is added to the (linear) gain, so the
+/// modulation signal can have any number of channels (e.g. a mono LFO).
+///
+/// Input 0 - Signal
+/// Input 1 - Gain modulation, as a linear amplitude (optional)
+///
+/// Output 0 - Signal with gain applied
+#[derive(Debug, Clone)]
+pub struct GainNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    gain: SmoothedValue,
+    /// Amplitude of the current fade (from 0.0 to 1.0), applied on top of `gain`
+    fade: SmoothedValue,
+}
+
+impl GainNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+    pub const DEFAULT_GAIN: f32 = 1.0;
+    pub const DEFAULT_SMOOTHING_TIME: Duration = Duration::from_millis(20);
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const GAIN_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let sample_rate = sample_rate.into();
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+            gain: SmoothedValue::new(
+                sample_rate,
+                Self::DEFAULT_SMOOTHING_TIME,
+                Self::DEFAULT_GAIN,
+            ),
+            fade: SmoothedValue::new(sample_rate, Duration::ZERO, 1.0),
+        }
+    }
+
+    /// Linear gain (before modulation and fading)
+    pub fn gain(&self) -> f32 {
+        self.gain.target()
+    }
+
+    /// Sets the linear gain, where 1.0 leaves the signal unchanged
+    pub fn set_gain(&mut self, gain: f32) -> &mut Self {
+        self.gain.set_target(gain);
+        self
+    }
+
+    /// Gain (before modulation and fading) in decibels relative to full scale
+    pub fn gain_db(&self) -> f32 {
+        Decibel::calculate_full_scale(self.gain())
+    }
+
+    /// Sets the gain in decibels relative to full scale, where 0.0 dB leaves
+    /// the signal unchanged and `f32::NEG_INFINITY` silences it
+    pub fn set_gain_db(&mut self, decibels: f32) -> &mut Self {
+        self.set_gain(Decibel::calculate_amplitude_full_scale(decibels))
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.gain.smoothing_time()
+    }
+
+    /// How long it takes to move to a new gain
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.gain.set_smoothing_time(smoothing_time);
+        self
+    }
+
+    /// Fades from the current fade level up to the full gain over `duration`
+    pub fn fade_in(&mut self, duration: Duration) -> &mut Self {
+        self.fade.set_smoothing_time(duration).set_target(1.0);
+        self
+    }
+
+    /// Fades from the current fade level down to silence over `duration`
+    pub fn fade_out(&mut self, duration: Duration) -> &mut Self {
+        self.fade.set_smoothing_time(duration).set_target(0.0);
+        self
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_smoothing()
+    }
+
+    /// Whether a fade out has finished (and the output is silent)
+    pub fn is_faded_out(&self) -> bool {
+        !self.fade.is_smoothing() && self.fade.value() == 0.0
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.gain.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.gain.set_sample_rate(sample_rate);
+        self.fade.set_sample_rate(sample_rate);
+        self
+    }
+}
+
+impl Node for GainNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut gain_modulation = 0.0;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::GAIN_INPUT_INDEX => {
+                    gain_modulation = input.data().first().copied().unwrap_or_default();
+                }
+                _ => signal = Some(input),
+            }
+        }
+
+        let gain = (self.gain.next_value() + gain_modulation) * self.fade.next_value();
+
+        let mut output = outputs
+            .next()
+            .expect("GainNode should have one and only one output connection");
+
+        match signal {
+            Some(signal) => output
+                .data_mut()
+                .iter_mut()
+                .zip(signal.data().iter())
+                .for_each(|(output_sample, input_sample)| *output_sample = input_sample * gain),
+            None => output.data_mut().fill(0.0),
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    /// Modulation inputs only use their first channel
+    fn accepts_any_num_channels(&self, input_index: usize) -> bool {
+        input_index != Self::SIGNAL_INPUT_INDEX
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("GainNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<GainNodeMessage>()?;
+
+        match message {
+            GainNodeMessage::SetGain { gain } => {
+                self.set_gain(gain);
+            }
+            GainNodeMessage::SetGainDb { decibels } => {
+                self.set_gain_db(decibels);
+            }
+            GainNodeMessage::SetSmoothingTime { smoothing_time } => {
+                self.set_smoothing_time(smoothing_time);
+            }
+            GainNodeMessage::FadeIn { duration } => {
+                self.fade_in(duration);
+            }
+            GainNodeMessage::FadeOut { duration } => {
+                self.fade_out(duration);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum GainNodeMessage {
+    SetGain { gain: f32 },
+    SetGainDb { decibels: f32 },
+    SetSmoothingTime { smoothing_time: Duration },
+    FadeIn { duration: Duration },
+    FadeOut { duration: Duration },
+}
+
+impl PartialEq for GainNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for GainNode {}
+
+impl PartialOrd for GainNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GainNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_gain_node {
+    use std::time::Duration;
+
+    use crate::{nodes::test_utils::process_frame, GainNode};
+
+    fn render(node: &mut GainNode, num_frames: usize) -> Vec<f32> {
+        (0..num_frames)
+            .map(|_| process_frame(node, &[(0, &[1.0])])[0])
+            .collect()
+    }
+
+    #[test]
+    fn should_smooth_gain_changes() {
+        let mut node = GainNode::new_with_full_config(0, 1, 1000);
+        node.set_smoothing_time(Duration::from_millis(4))
+            .set_gain(0.0);
+
+        assert_eq!(render(&mut node, 5), vec![0.75, 0.5, 0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn should_set_gain_in_decibels() {
+        let mut node = GainNode::new_with_full_config(0, 1, 1000);
+        node.set_smoothing_time(Duration::ZERO).set_gain_db(-6.0206);
+
+        assert!((render(&mut node, 1)[0] - 0.5).abs() < 0.0001);
+        assert!((node.gain_db() - -6.0206).abs() < 0.0001);
+
+        node.set_gain_db(f32::NEG_INFINITY);
+        assert_eq!(render(&mut node, 1), vec![0.0]);
+    }
+
+    #[test]
+    fn should_fade_out_and_back_in_to_gain() {
+        let mut node = GainNode::new_with_full_config(0, 1, 1000);
+        node.set_smoothing_time(Duration::ZERO)
+            .set_gain(0.5)
+            .fade_out(Duration::from_millis(2));
+
+        assert!(node.is_fading());
+        assert_eq!(render(&mut node, 3), vec![0.25, 0.0, 0.0]);
+        assert!(node.is_faded_out());
+
+        node.fade_in(Duration::from_millis(2));
+        assert_eq!(render(&mut node, 3), vec![0.25, 0.5, 0.5]);
+        assert!(!node.is_faded_out());
+    }
+
+    #[test]
+    fn should_add_gain_modulation() {
+        let mut node = GainNode::new_with_full_config(0, 1, 1000);
+        node.set_smoothing_time(Duration::ZERO).set_gain(0.5);
+
+        let output = process_frame(
+            &mut node,
+            &[(0, &[2.0]), (GainNode::GAIN_INPUT_INDEX, &[0.25])],
+        );
+        assert_eq!(output[0], 1.5);
+    }
+
+    #[test]
+    fn should_accept_any_num_channels_for_gain_modulation_only() {
+        use crate::Node;
+
+        let node = GainNode::new(2);
+
+        assert!(!node.accepts_any_num_channels(GainNode::SIGNAL_INPUT_INDEX));
+        assert!(node.accepts_any_num_channels(GainNode::GAIN_INPUT_INDEX));
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, GainNodeMessage, Node};
+
+        let mut node = GainNode::new(1);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(GainNodeMessage::SetGain { gain: 0.25 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.gain(), 0.25);
+    }
+}
@@ -4,10 +4,17 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use resonix_core::{GranularSynthesizer, GranularSynthesizerAction, NumChannels};
+use resonix_core::{
+    GrainPositionMode, GranularSynthesizer, GranularSynthesizerAction, NumChannels, PanMode,
+    Percentage,
+};
 
 #[cfg(feature = "dac")]
-use {resonix_dac::DACConfig, std::sync::Arc};
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
 
 use crate::{Connection, Node, NodeType, NodeUid};
 
@@ -41,6 +48,10 @@ impl GranularSynthesizerNode {
             granular_synthesizer,
         }
     }
+
+    pub fn granular_synthesizer(&self) -> &GranularSynthesizer {
+        &self.granular_synthesizer
+    }
 }
 
 impl Node for GranularSynthesizerNode {
@@ -106,6 +117,51 @@ impl Node for GranularSynthesizerNode {
         self.granular_synthesizer
             .set_sample_rate(dac_config.sample_rate());
     }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<GranularSynthesizerNodeMessage>()?;
+
+        match message {
+            GranularSynthesizerNodeMessage::SetPitchShift { semitones } => {
+                self.granular_synthesizer.set_pitch_shift(semitones);
+            }
+            GranularSynthesizerNodeMessage::SetPitchSpread { semitones } => {
+                self.granular_synthesizer.set_pitch_spread(semitones);
+            }
+            GranularSynthesizerNodeMessage::SetPositionMode { position_mode } => {
+                self.granular_synthesizer.set_position_mode(position_mode);
+            }
+            GranularSynthesizerNodeMessage::SetScanPosition { position } => {
+                self.granular_synthesizer.set_scan_position(position);
+            }
+            GranularSynthesizerNodeMessage::SetNumGrains { num_grains } => {
+                // clamped to `max_num_grains`, which are allocated up front
+                self.granular_synthesizer.set_num_grains(num_grains);
+            }
+            GranularSynthesizerNodeMessage::SetPanMode { pan_mode } => {
+                self.granular_synthesizer.set_pan_mode(pan_mode);
+            }
+            GranularSynthesizerNodeMessage::SetPanSpread { pan_spread } => {
+                self.granular_synthesizer.set_pan_spread(pan_spread);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum GranularSynthesizerNodeMessage {
+    SetPitchShift { semitones: f32 },
+    SetPitchSpread { semitones: f32 },
+    SetPositionMode { position_mode: GrainPositionMode },
+    SetScanPosition { position: Percentage },
+    SetNumGrains { num_grains: Option<usize> },
+    SetPanMode { pan_mode: PanMode },
+    SetPanSpread { pan_spread: f32 },
 }
 
 impl PartialEq for GranularSynthesizerNode {
@@ -173,4 +229,45 @@ mod test_constant_node {
             insta::assert_debug_snapshot!(output_buffer);
         }
     }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use resonix_core::GrainPositionMode;
+
+        use crate::{messages::UpdateNodeMessage, GranularSynthesizerNodeMessage};
+
+        let mut node = GranularSynthesizerNode::new(GranularSynthesizer::new());
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(GranularSynthesizerNodeMessage::SetPitchShift { semitones: 7.0 }),
+        })
+        .unwrap();
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(GranularSynthesizerNodeMessage::SetPositionMode {
+                position_mode: GrainPositionMode::Scan { speed: 0.5 },
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.granular_synthesizer().pitch_shift(), 7.0);
+        assert_eq!(
+            node.granular_synthesizer().position_mode(),
+            GrainPositionMode::Scan { speed: 0.5 }
+        );
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(GranularSynthesizerNodeMessage::SetNumGrains {
+                num_grains: Some(200),
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.granular_synthesizer().num_grains(), 200);
+        // grains are spread across the node's original channels
+        assert_eq!(*node.num_outgoing_channels(), 2);
+    }
 }
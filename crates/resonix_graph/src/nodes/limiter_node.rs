@@ -0,0 +1,319 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{Limiter, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Keeps every peak of the incoming signal at or below the threshold
+/// when no sidechain is connected.
+///
+/// Placing a `LimiterNode` right before the `DACNode` protects speakers from
+/// clipping when many sounds are mixed together (e.g. a granular synthesizer with many grains).
+///
+/// The signal is delayed by the lookahead time.
+///
+/// When a sidechain signal is connected, the sidechain's level (rather than the
+/// signal's own level) decides how much the signal is turned down, so the signal's
+/// peaks are no longer guaranteed to stay at or below the threshold.
+///
+/// Input 0 - Signal
+/// Input 1 - Sidechain (optional)
+///
+/// Output 0 - Limited signal
+#[derive(Debug, Clone)]
+pub struct LimiterNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    limiter: Limiter,
+}
+
+impl LimiterNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const SIDECHAIN_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            limiter: Limiter::new(num_channels, sample_rate),
+        }
+    }
+
+    /// Highest level (in dBFS) that the output can reach
+    pub fn threshold(&self) -> f32 {
+        self.limiter.threshold()
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) -> &mut Self {
+        self.limiter.set_threshold(threshold);
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.limiter.release()
+    }
+
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.limiter.set_release(release);
+        self
+    }
+
+    pub fn lookahead(&self) -> Duration {
+        self.limiter.lookahead()
+    }
+
+    /// Delays the signal (up to `Compressor::MAX_LOOKAHEAD`), so that gain reduction can begin before peaks arrive
+    pub fn set_lookahead(&mut self, lookahead: Duration) -> &mut Self {
+        self.limiter.set_lookahead(lookahead);
+        self
+    }
+
+    /// The current gain reduction in dB
+    pub fn gain_reduction(&self) -> f32 {
+        self.limiter.gain_reduction()
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.limiter.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.limiter.set_sample_rate(sample_rate);
+        self
+    }
+}
+
+impl Node for LimiterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut sidechain = None;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::SIDECHAIN_INPUT_INDEX => sidechain = Some(input),
+                _ => signal = Some(input),
+            }
+        }
+
+        let mut output = outputs
+            .next()
+            .expect("LimiterNode should have one and only one output connection");
+
+        match signal {
+            Some(signal) => output.data_mut().copy_from_slice(signal.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.limiter.process_frame(
+            output.data_mut(),
+            sidechain.as_ref().map(|sidechain| sidechain.data()),
+        );
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("LimiterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<LimiterNodeMessage>()?;
+
+        match message {
+            LimiterNodeMessage::SetThreshold { threshold } => {
+                self.set_threshold(threshold);
+            }
+            LimiterNodeMessage::SetRelease { release } => {
+                self.set_release(release);
+            }
+            LimiterNodeMessage::SetLookahead { lookahead } => {
+                self.set_lookahead(lookahead);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum LimiterNodeMessage {
+    SetThreshold { threshold: f32 },
+    SetRelease { release: Duration },
+    SetLookahead { lookahead: Duration },
+}
+
+impl PartialEq for LimiterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for LimiterNode {}
+
+impl PartialOrd for LimiterNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LimiterNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_limiter_node {
+    use std::{cell::RefCell, time::Duration};
+
+    use crate::{Connection, LimiterNode, Node};
+
+    #[test]
+    fn should_keep_peaks_below_threshold() {
+        let mut node = LimiterNode::new_with_full_config(0, 1, 1000);
+        node.set_threshold(-6.0206)
+            .set_lookahead(Duration::from_millis(2));
+
+        let output_connection = RefCell::new(Connection::new(1));
+        let output: Vec<f32> = [0.25, 2.0, -4.0, 0.25, 0.25, 0.25]
+            .into_iter()
+            .map(|sample| {
+                let input_connection =
+                    RefCell::new(Connection::from_test_data(0, 1, vec![sample], 0, 0));
+                {
+                    let inputs = [input_connection.borrow()];
+                    let outputs = [output_connection.borrow_mut()];
+                    node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+                }
+                let output = output_connection.borrow().data()[0];
+                output
+            })
+            .collect();
+
+        // the signal is delayed by the lookahead, and the gain reduction needed for
+        // the loudest peak is applied before it arrives, so it's turned down to the threshold
+        assert_eq!(output[..2], [0.0, 0.0]);
+        assert!(output.iter().all(|sample| sample.abs() <= 0.5 + 0.00001));
+        assert!((output[3] - 0.25).abs() < 0.00001);
+        assert!((output[4] - -0.5).abs() < 0.00001);
+    }
+
+    #[test]
+    fn should_limit_signal_with_sidechain() {
+        let mut node = LimiterNode::new_with_full_config(0, 1, 1000);
+        node.set_threshold(-20.0).set_lookahead(Duration::ZERO);
+
+        let signal_connection = RefCell::new(Connection::from_test_data(0, 1, vec![0.05], 0, 0));
+        let sidechain_connection = RefCell::new(Connection::from_test_data(
+            1,
+            1,
+            vec![1.0],
+            0,
+            LimiterNode::SIDECHAIN_INPUT_INDEX,
+        ));
+        let output_connection = RefCell::new(Connection::new(1));
+        {
+            let inputs = [signal_connection.borrow(), sidechain_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        // the quiet signal is turned down by as much as the sidechain is over the threshold
+        assert!((output_connection.borrow().data()[0] - 0.005).abs() < 0.00001);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, LimiterNodeMessage};
+
+        let mut node = LimiterNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(LimiterNodeMessage::SetThreshold { threshold: -3.0 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.threshold(), -3.0);
+    }
+}
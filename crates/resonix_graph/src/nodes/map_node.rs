@@ -0,0 +1,227 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Runs a closure on every frame of the incoming signal, which allows inserting
+/// custom math into an audio graph without writing a new `Node` implementation.
+///
+/// The closure receives the incoming frame (one sample per incoming channel)
+/// and writes into the outgoing frame (one sample per outgoing channel).
+///
+/// The closure runs on the audio thread, so it should avoid allocating, locking, or blocking.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Mapped signal
+#[derive(Clone)]
+pub struct MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    uid: NodeUid,
+    num_incoming_channels: NumChannels,
+    num_outgoing_channels: NumChannels,
+    map: F,
+}
+
+impl<F> MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    /// Creates a `MapNode` with the same number of incoming and outgoing channels
+    pub fn new(num_channels: impl Into<NumChannels>, map: F) -> Self {
+        let num_channels = num_channels.into();
+        Self::new_with_channels(num_channels, num_channels, map)
+    }
+
+    /// Creates a `MapNode` that can change the number of channels of the signal
+    pub fn new_with_channels(
+        num_incoming_channels: impl Into<NumChannels>,
+        num_outgoing_channels: impl Into<NumChannels>,
+        map: F,
+    ) -> Self {
+        Self::new_with_uid(0, num_incoming_channels, num_outgoing_channels, map)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_incoming_channels: impl Into<NumChannels>,
+        num_outgoing_channels: impl Into<NumChannels>,
+        map: F,
+    ) -> Self {
+        Self {
+            uid,
+            num_incoming_channels: num_incoming_channels.into(),
+            num_outgoing_channels: num_outgoing_channels.into(),
+            map,
+        }
+    }
+}
+
+impl<F> Node for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("MapNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => (self.map)(input.data(), output.data_mut()),
+            None => output.data_mut().fill(0.0),
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_incoming_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("MapNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<F> Debug for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapNode")
+            .field("uid", &self.uid)
+            .field("num_incoming_channels", &self.num_incoming_channels)
+            .field("num_outgoing_channels", &self.num_outgoing_channels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> PartialEq for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl<F> Eq for MapNode<F> where F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static {}
+
+impl<F> PartialOrd for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F> Ord for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+impl<F> Hash for MapNode<F>
+where
+    F: Fn(&[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uid.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test_map_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, MapNode, Node};
+
+    #[test]
+    fn should_run_closure_on_each_frame() {
+        let exponent = 2;
+        let mut map_node = MapNode::new(2, move |input: &[f32], output: &mut [f32]| {
+            output
+                .iter_mut()
+                .zip(input)
+                .for_each(|(output, input)| *output = input.powi(exponent))
+        });
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![-0.5, 3.0], 0, 0));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            map_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.25, 9.0]);
+    }
+
+    #[test]
+    fn should_change_number_of_channels() {
+        let mut map_node = MapNode::new_with_channels(3, 1, |input: &[f32], output: &mut [f32]| {
+            output[0] = input.iter().sum()
+        });
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 3, vec![0.25, 0.5, 1.0], 0, 0));
+        let output_connection = RefCell::new(Connection::new(1));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            map_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![1.75]);
+    }
+}
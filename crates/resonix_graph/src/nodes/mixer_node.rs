@@ -0,0 +1,476 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{pan_balance_to_buffer, NumChannels, SampleRate, SmoothedValue};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MixerNodeError {
+    #[error("MixerNode has {num_inputs:?} inputs, but input {input_index:?} was requested")]
+    InputIndexOutOfRange {
+        input_index: usize,
+        num_inputs: usize,
+    },
+}
+
+/// The settings of a single input of a `MixerNode`
+#[derive(Debug, Clone)]
+struct MixerInput {
+    gain: f32,
+    muted: bool,
+    /// Gain after muting is applied
+    level: SmoothedValue,
+    /// Value of `level` for the current frame
+    current_level: f32,
+    balance: SmoothedValue,
+    /// The gain of each channel at `balance_gains_position`
+    balance_gains: Vec<f32>,
+    /// Balance that `balance_gains` were last calculated for (`None` if they must be recalculated)
+    balance_gains_position: Option<f32>,
+}
+
+impl MixerInput {
+    fn new(num_channels: NumChannels, sample_rate: SampleRate) -> Self {
+        Self {
+            gain: MixerNode::DEFAULT_GAIN,
+            muted: false,
+            level: SmoothedValue::new(
+                sample_rate,
+                MixerNode::DEFAULT_SMOOTHING_TIME,
+                MixerNode::DEFAULT_GAIN,
+            ),
+            current_level: MixerNode::DEFAULT_GAIN,
+            balance: SmoothedValue::new(
+                sample_rate,
+                MixerNode::DEFAULT_SMOOTHING_TIME,
+                MixerNode::DEFAULT_BALANCE,
+            ),
+            balance_gains: vec![1.0; *num_channels],
+            balance_gains_position: None,
+        }
+    }
+
+    fn update_level(&mut self) {
+        self.level
+            .set_target(if self.muted { 0.0 } else { self.gain });
+    }
+
+    /// Advances the smoothed level and balance by one frame
+    fn next_frame(&mut self) {
+        self.current_level = self.level.next_value();
+
+        // balance gains only need to be recalculated when the balance moves
+        let balance = self.balance.next_value();
+        if self.balance_gains_position != Some(balance) {
+            pan_balance_to_buffer(balance, &mut self.balance_gains);
+            self.balance_gains_position = Some(balance);
+        }
+    }
+}
+
+/// Sums any number of multichannel signals together into one multichannel signal.
+///
+/// Every input has its own gain, mute and balance, which are all smoothed to prevent clicks.
+/// Balance fades out the channels on the opposite side of an input without moving
+/// any of its channels (see `pan_balance`), rather than panning with equal power.
+/// To place a mono signal among many channels, use a `PannerNode` before the mixer instead.
+///
+/// Input 0..n - Signals to mix (all with the same number of channels)
+///
+/// Output 0 - Mixed signal
+#[derive(Debug, Clone)]
+pub struct MixerNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    inputs: Vec<MixerInput>,
+}
+
+impl MixerNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+    pub const DEFAULT_GAIN: f32 = 1.0;
+    pub const DEFAULT_BALANCE: f32 = 0.0;
+    pub const DEFAULT_SMOOTHING_TIME: Duration = Duration::from_millis(20);
+
+    pub fn new(num_inputs: usize, num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_inputs, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_inputs: usize,
+        num_channels: impl Into<NumChannels>,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_inputs, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_inputs: usize,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        let sample_rate = sample_rate.into();
+        Self {
+            uid,
+            num_channels,
+            inputs: (0..num_inputs)
+                .map(|_| MixerInput::new(num_channels, sample_rate))
+                .collect(),
+        }
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn input(&self, input_index: usize) -> Result<&MixerInput, MixerNodeError> {
+        self.inputs
+            .get(input_index)
+            .ok_or(MixerNodeError::InputIndexOutOfRange {
+                input_index,
+                num_inputs: self.inputs.len(),
+            })
+    }
+
+    fn input_mut(&mut self, input_index: usize) -> Result<&mut MixerInput, MixerNodeError> {
+        let num_inputs = self.inputs.len();
+        self.inputs
+            .get_mut(input_index)
+            .ok_or(MixerNodeError::InputIndexOutOfRange {
+                input_index,
+                num_inputs,
+            })
+    }
+
+    /// Linear gain of an input (whether or not it is muted)
+    pub fn gain(&self, input_index: usize) -> Result<f32, MixerNodeError> {
+        self.input(input_index).map(|input| input.gain)
+    }
+
+    pub fn set_gain(&mut self, input_index: usize, gain: f32) -> Result<&mut Self, MixerNodeError> {
+        let input = self.input_mut(input_index)?;
+        input.gain = gain;
+        input.update_level();
+        Ok(self)
+    }
+
+    pub fn muted(&self, input_index: usize) -> Result<bool, MixerNodeError> {
+        self.input(input_index).map(|input| input.muted)
+    }
+
+    /// Muting (and unmuting) is smoothed, and keeps the input's gain for when it is unmuted
+    pub fn set_muted(
+        &mut self,
+        input_index: usize,
+        muted: bool,
+    ) -> Result<&mut Self, MixerNodeError> {
+        let input = self.input_mut(input_index)?;
+        input.muted = muted;
+        input.update_level();
+        Ok(self)
+    }
+
+    pub fn balance(&self, input_index: usize) -> Result<f32, MixerNodeError> {
+        self.input(input_index).map(|input| input.balance.target())
+    }
+
+    /// Balance of an input, from -1.0 (towards the first channel) to 1.0 (towards the last channel)
+    pub fn set_balance(
+        &mut self,
+        input_index: usize,
+        balance: f32,
+    ) -> Result<&mut Self, MixerNodeError> {
+        self.input_mut(input_index)?
+            .balance
+            .set_target(balance.clamp(-1.0, 1.0));
+        Ok(self)
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.inputs
+            .first()
+            .map(|input| input.level.smoothing_time())
+            .unwrap_or(Self::DEFAULT_SMOOTHING_TIME)
+    }
+
+    /// How long it takes every input to move to a new gain, mute or balance
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.inputs.iter_mut().for_each(|input| {
+            input.level.set_smoothing_time(smoothing_time);
+            input.balance.set_smoothing_time(smoothing_time);
+        });
+        self
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.inputs.iter_mut().for_each(|input| {
+            input.level.set_sample_rate(sample_rate);
+            input.balance.set_sample_rate(sample_rate);
+        });
+        self
+    }
+}
+
+impl Node for MixerNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("MixerNode should have one and only one output connection");
+        output.data_mut().fill(0.0);
+
+        // every input advances once per frame, however many connections it has
+        self.inputs.iter_mut().for_each(MixerInput::next_frame);
+
+        for input in inputs {
+            let Some(mixer_input) = self.inputs.get(input.to_index()) else {
+                continue;
+            };
+
+            let level = mixer_input.current_level;
+            output
+                .data_mut()
+                .iter_mut()
+                .zip(input.data().iter())
+                .zip(mixer_input.balance_gains.iter())
+                .for_each(|((output_sample, input_sample), balance_gain)| {
+                    *output_sample += input_sample * level * balance_gain;
+                });
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("MixerNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let uid = self.uid;
+        let message = update_node_message.try_into::<MixerNodeMessage>()?;
+
+        let result = match message {
+            MixerNodeMessage::SetGain { input_index, gain } => {
+                self.set_gain(input_index, gain).map(|_| ())
+            }
+            MixerNodeMessage::SetMuted { input_index, muted } => {
+                self.set_muted(input_index, muted).map(|_| ())
+            }
+            MixerNodeMessage::SetBalance {
+                input_index,
+                balance,
+            } => self.set_balance(input_index, balance).map(|_| ()),
+            MixerNodeMessage::SetSmoothingTime { smoothing_time } => {
+                self.set_smoothing_time(smoothing_time);
+                Ok(())
+            }
+        };
+
+        result.map_err(|_| UpdateNodeError::InvalidData { uid })
+    }
+}
+
+pub enum MixerNodeMessage {
+    SetGain { input_index: usize, gain: f32 },
+    SetMuted { input_index: usize, muted: bool },
+    SetBalance { input_index: usize, balance: f32 },
+    SetSmoothingTime { smoothing_time: Duration },
+}
+
+impl PartialEq for MixerNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for MixerNode {}
+
+impl PartialOrd for MixerNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MixerNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_mixer_node {
+    use std::time::Duration;
+
+    use crate::{nodes::test_utils::process_frame, MixerNode, MixerNodeError};
+
+    fn render(node: &mut MixerNode, input_data: &[[f32; 2]]) -> Vec<f32> {
+        let inputs: Vec<_> = input_data
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| (i, frame.as_slice()))
+            .collect();
+        process_frame(node, &inputs)
+    }
+
+    #[test]
+    fn should_sum_inputs_with_gain() {
+        let mut node = MixerNode::new_with_full_config(0, 3, 2, 1000);
+        node.set_smoothing_time(Duration::ZERO)
+            .set_gain(0, 0.5)
+            .unwrap()
+            .set_gain(2, 2.0)
+            .unwrap();
+
+        let output = render(&mut node, &[[1.0, -1.0], [0.25, 0.25], [0.5, 0.0]]);
+        assert_eq!(output, vec![1.75, -0.25]);
+    }
+
+    #[test]
+    fn should_smoothly_mute_and_unmute() {
+        let mut node = MixerNode::new_with_full_config(0, 2, 2, 1000);
+        node.set_smoothing_time(Duration::from_millis(2))
+            .set_gain(1, 0.5)
+            .unwrap()
+            .set_muted(1, true)
+            .unwrap();
+
+        let inputs = [[1.0, 1.0], [1.0, 1.0]];
+        assert_eq!(render(&mut node, &inputs), vec![1.5, 1.5]);
+        assert_eq!(render(&mut node, &inputs), vec![1.0, 1.0]);
+
+        node.set_muted(1, false).unwrap();
+        assert_eq!(render(&mut node, &inputs), vec![1.25, 1.25]);
+        assert_eq!(render(&mut node, &inputs), vec![1.5, 1.5]);
+        assert_eq!(node.gain(1), Ok(0.5));
+    }
+
+    #[test]
+    fn should_balance_inputs() {
+        let mut node = MixerNode::new_with_full_config(0, 2, 2, 1000);
+        node.set_smoothing_time(Duration::ZERO)
+            .set_balance(0, -1.0)
+            .unwrap()
+            .set_balance(1, 1.0)
+            .unwrap();
+
+        let output = render(&mut node, &[[1.0, 1.0], [0.0, 0.5]]);
+        assert_eq!(output[0], 1.0);
+        assert!((output[1] - 0.5).abs() < 0.000001);
+    }
+
+    #[test]
+    fn should_advance_smoothing_once_per_frame() {
+        let mut node = MixerNode::new_with_full_config(0, 2, 2, 1000);
+        node.set_smoothing_time(Duration::from_millis(2))
+            .set_gain(0, 0.0)
+            .unwrap()
+            .set_gain(1, 0.0)
+            .unwrap();
+
+        // input 0 has two connections, input 1 is not connected at all
+        let inputs: [(usize, &[f32]); 2] = [(0, &[1.0, 1.0]), (0, &[1.0, 1.0])];
+        assert_eq!(process_frame(&mut node, &inputs), vec![1.0, 1.0]);
+        assert_eq!(process_frame(&mut node, &inputs), vec![0.0, 0.0]);
+
+        // the unconnected input kept smoothing as well
+        assert_eq!(render(&mut node, &[[0.0, 0.0], [1.0, 1.0]]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn should_return_error_for_invalid_input_index() {
+        let mut node = MixerNode::new(2, 2);
+
+        assert_eq!(
+            node.set_gain(2, 1.0).unwrap_err(),
+            MixerNodeError::InputIndexOutOfRange {
+                input_index: 2,
+                num_inputs: 2
+            }
+        );
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, MixerNodeMessage, Node};
+
+        let mut node = MixerNode::new(2, 2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(MixerNodeMessage::SetMuted {
+                input_index: 1,
+                muted: true,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.muted(1), Ok(true));
+    }
+}
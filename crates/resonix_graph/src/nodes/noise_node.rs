@@ -0,0 +1,252 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{Noise, NoiseColor, NoiseSeed, NumChannels};
+
+#[cfg(feature = "dac")]
+use crate::messages::{UpdateNodeError, UpdateNodeMessage};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Determines whether each output channel of a `NoiseNode` gets its own noise
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum NoiseChannelMode {
+    /// Every channel gets its own, unrelated noise (e.g. for a wide stereo image)
+    #[default]
+    Independent,
+    /// Every channel gets exactly the same noise
+    Correlated,
+}
+
+/// Outputs white, pink, or brown noise.
+///
+/// Noise nodes created with `new_with_seed` always produce the
+/// same output for the same seed, which is useful for snapshot testing.
+///
+/// Output 0 - Noise
+#[derive(Debug, Clone)]
+pub struct NoiseNode {
+    uid: NodeUid,
+    num_outgoing_channels: NumChannels,
+    channel_mode: NoiseChannelMode,
+    /// One generator for every output channel (only the first is used when correlated)
+    generators: Vec<Noise>,
+}
+
+impl NoiseNode {
+    /// Creates a noise node with a random seed
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, color: NoiseColor) -> Self {
+        Self::new_with_uid(0, num_outgoing_channels, Noise::new(color))
+    }
+
+    /// Creates a noise node that always produces the same output for the same seed
+    pub fn new_with_seed(
+        num_outgoing_channels: impl Into<NumChannels>,
+        color: NoiseColor,
+        seed: NoiseSeed,
+    ) -> Self {
+        Self::new_with_uid(0, num_outgoing_channels, Noise::from_seed(color, seed))
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        mut noise: Noise,
+    ) -> Self {
+        let num_outgoing_channels = num_outgoing_channels.into();
+        // each channel's generator is seeded from the same initial generator
+        let generators = (0..(*num_outgoing_channels).max(1))
+            .map(|_| noise.split())
+            .collect();
+
+        Self {
+            uid,
+            num_outgoing_channels,
+            channel_mode: NoiseChannelMode::default(),
+            generators,
+        }
+    }
+
+    pub fn color(&self) -> NoiseColor {
+        self.generators[0].color()
+    }
+
+    pub fn set_color(&mut self, color: NoiseColor) -> &mut Self {
+        self.generators.iter_mut().for_each(|generator| {
+            generator.set_color(color);
+        });
+        self
+    }
+
+    pub fn channel_mode(&self) -> NoiseChannelMode {
+        self.channel_mode
+    }
+
+    pub fn set_channel_mode(&mut self, channel_mode: NoiseChannelMode) -> &mut Self {
+        self.channel_mode = channel_mode;
+        self
+    }
+}
+
+impl Node for NoiseNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("NoiseNode should have one and only one output connection");
+
+        match self.channel_mode {
+            NoiseChannelMode::Independent => {
+                output
+                    .data_mut()
+                    .iter_mut()
+                    .zip(self.generators.iter_mut())
+                    .for_each(|(sample, generator)| *sample = generator.next_sample());
+            }
+            NoiseChannelMode::Correlated => {
+                let sample = self.generators[0].next_sample();
+                output.update_data(|frame| frame.fill(sample));
+            }
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("NoiseNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<NoiseNodeMessage>()?;
+
+        match message {
+            NoiseNodeMessage::SetColor { color } => {
+                self.set_color(color);
+            }
+            NoiseNodeMessage::SetChannelMode { channel_mode } => {
+                self.set_channel_mode(channel_mode);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum NoiseNodeMessage {
+    SetColor { color: NoiseColor },
+    SetChannelMode { channel_mode: NoiseChannelMode },
+}
+
+impl PartialEq for NoiseNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for NoiseNode {}
+
+impl PartialOrd for NoiseNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NoiseNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_noise_node {
+    use resonix_core::NoiseColor;
+
+    use crate::{nodes::test_utils::process_frame, NoiseChannelMode, NoiseNode};
+
+    fn render(node: &mut NoiseNode, num_frames: usize) -> Vec<Vec<f32>> {
+        (0..num_frames).map(|_| process_frame(node, &[])).collect()
+    }
+
+    #[test]
+    fn should_produce_seeded_noise() {
+        let mut node = NoiseNode::new_with_seed(2, NoiseColor::Pink, [0; 32]);
+        insta::assert_debug_snapshot!(render(&mut node, 16));
+    }
+
+    #[test]
+    fn should_output_independent_or_correlated_channels() {
+        let mut node = NoiseNode::new_with_seed(3, NoiseColor::White, [0; 32]);
+
+        render(&mut node, 10).iter().for_each(|frame| {
+            assert_ne!(frame[0], frame[1]);
+            assert_ne!(frame[1], frame[2]);
+        });
+
+        node.set_channel_mode(NoiseChannelMode::Correlated);
+        render(&mut node, 10).iter().for_each(|frame| {
+            assert_eq!(frame, &vec![frame[0]; 3]);
+        });
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, Node, NoiseNodeMessage};
+
+        let mut node = NoiseNode::new(1, NoiseColor::White);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(NoiseNodeMessage::SetColor {
+                color: NoiseColor::Brown,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.color(), NoiseColor::Brown);
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{NumChannels, PanMode, SampleRate, SmoothedValue};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Places a mono signal at a position among any number of output channels.
+///
+/// With `PanMode::EqualPower`, the position ranges from -1.0 (first channel)
+/// to 1.0 (last channel), which is standard left-to-right panning for stereo output.
+///
+/// With `PanMode::Ring`, the position is an azimuth (in turns) around a ring of
+/// evenly spaced speakers, where 0.0 is the first channel's speaker.
+///
+/// Changes to the position are smoothed, and the position can be automated
+/// by connecting a signal to the position input: the signal is added to the position.
+///
+/// Input 0 - Mono signal
+/// Input 1 - Position modulation (optional)
+///
+/// Output 0 - Panned multichannel signal
+#[derive(Debug, Clone)]
+pub struct PannerNode {
+    uid: NodeUid,
+    num_outgoing_channels: NumChannels,
+    mode: PanMode,
+    position: SmoothedValue,
+    /// The gain of each output channel at `gains_position`
+    gains: Vec<f32>,
+    /// Position that `gains` were last calculated for (`None` if they must be recalculated)
+    gains_position: Option<f32>,
+}
+
+impl PannerNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+    pub const DEFAULT_POSITION: f32 = 0.0;
+    pub const DEFAULT_SMOOTHING_TIME: Duration = Duration::from_millis(20);
+
+    pub const SIGNAL_INPUT_INDEX: usize = 0;
+    pub const POSITION_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, mode: PanMode) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_outgoing_channels, mode)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        mode: PanMode,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_outgoing_channels, Self::DEFAULT_SAMPLE_RATE, mode)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        mode: PanMode,
+    ) -> Self {
+        let num_outgoing_channels = num_outgoing_channels.into();
+        Self {
+            uid,
+            num_outgoing_channels,
+            mode,
+            position: SmoothedValue::new(
+                sample_rate,
+                Self::DEFAULT_SMOOTHING_TIME,
+                Self::DEFAULT_POSITION,
+            ),
+            gains: vec![0.0; *num_outgoing_channels],
+            gains_position: None,
+        }
+    }
+
+    pub fn mode(&self) -> PanMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: PanMode) -> &mut Self {
+        self.mode = mode;
+        self.gains_position = None;
+        self
+    }
+
+    /// Position (before modulation)
+    pub fn position(&self) -> f32 {
+        self.position.target()
+    }
+
+    /// See `PanMode` for how the position is interpreted
+    pub fn set_position(&mut self, position: f32) -> &mut Self {
+        self.position.set_target(position);
+        self
+    }
+
+    pub fn smoothing_time(&self) -> Duration {
+        self.position.smoothing_time()
+    }
+
+    /// How long it takes to move to a new position
+    pub fn set_smoothing_time(&mut self, smoothing_time: Duration) -> &mut Self {
+        self.position.set_smoothing_time(smoothing_time);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.position.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.position.set_sample_rate(sample_rate);
+        self
+    }
+}
+
+impl Node for PannerNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut signal = None;
+        let mut position_modulation = 0.0;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::POSITION_INPUT_INDEX => {
+                    position_modulation = input.data().first().copied().unwrap_or_default();
+                }
+                _ => signal = Some(input),
+            }
+        }
+
+        // gains only need to be recalculated when the position moves
+        let position = self.position.next_value() + position_modulation;
+        if self.gains_position != Some(position) {
+            self.mode.as_panner_to_buffer()(position, &mut self.gains);
+            self.gains_position = Some(position);
+        }
+
+        let sample = signal
+            .and_then(|signal| signal.data().first().copied())
+            .unwrap_or_default();
+
+        let mut output = outputs
+            .next()
+            .expect("PannerNode should have one and only one output connection");
+
+        output
+            .data_mut()
+            .iter_mut()
+            .zip(self.gains.iter())
+            .for_each(|(output_sample, gain)| *output_sample = sample * gain);
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(1)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("PannerNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<PannerNodeMessage>()?;
+
+        match message {
+            PannerNodeMessage::SetMode { mode } => {
+                self.set_mode(mode);
+            }
+            PannerNodeMessage::SetPosition { position } => {
+                self.set_position(position);
+            }
+            PannerNodeMessage::SetSmoothingTime { smoothing_time } => {
+                self.set_smoothing_time(smoothing_time);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum PannerNodeMessage {
+    SetMode { mode: PanMode },
+    SetPosition { position: f32 },
+    SetSmoothingTime { smoothing_time: Duration },
+}
+
+impl PartialEq for PannerNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for PannerNode {}
+
+impl PartialOrd for PannerNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PannerNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_panner_node {
+    use std::{f32::consts::FRAC_1_SQRT_2, time::Duration};
+
+    use resonix_core::PanMode;
+
+    use crate::{nodes::test_utils::process_frame, PannerNode};
+
+    fn render(node: &mut PannerNode, position_modulation: Option<f32>) -> Vec<f32> {
+        match position_modulation {
+            Some(position_modulation) => process_frame(
+                node,
+                &[
+                    (0, &[1.0]),
+                    (PannerNode::POSITION_INPUT_INDEX, &[position_modulation]),
+                ],
+            ),
+            None => process_frame(node, &[(0, &[1.0])]),
+        }
+    }
+
+    #[test]
+    fn should_pan_stereo_with_equal_power() {
+        let mut node = PannerNode::new_with_full_config(0, 2, 1000, PanMode::EqualPower);
+
+        let output = render(&mut node, None);
+        assert!((output[0] - FRAC_1_SQRT_2).abs() < 0.000001);
+        assert!((output[1] - FRAC_1_SQRT_2).abs() < 0.000001);
+
+        node.set_smoothing_time(Duration::ZERO).set_position(-1.0);
+        assert_eq!(render(&mut node, None), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn should_smooth_position_changes() {
+        let mut node = PannerNode::new_with_full_config(0, 2, 1000, PanMode::EqualPower);
+        node.set_smoothing_time(Duration::from_millis(2))
+            .set_position(-1.0);
+
+        let first = render(&mut node, None);
+        assert!(first[0] > FRAC_1_SQRT_2 && first[0] < 1.0);
+        assert_eq!(render(&mut node, None), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn should_pan_around_ring_with_position_modulation() {
+        let mut node = PannerNode::new_with_full_config(0, 4, 1000, PanMode::Ring);
+
+        assert_eq!(render(&mut node, None), vec![1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(render(&mut node, Some(0.75)), vec![0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, Node, PannerNodeMessage};
+
+        let mut node = PannerNode::new(2, PanMode::EqualPower);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(PannerNodeMessage::SetPosition { position: 0.5 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.position(), 0.5);
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{EnvelopeFollower, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, MeterHandle, Node, NodeType, NodeUid};
+
+/// Meters the peak amplitude of each incoming channel.
+///
+/// The signal is passed through unaltered, so this node can be inserted
+/// anywhere in the audio graph. Readings are smoothed with attack / release
+/// ballistics and can be polled from the main thread via the `MeterHandle`
+/// returned from `PeakMeterNode::handle`.
+///
+/// Input 0 - Signal to meter
+///
+/// Output 0 - Unaltered input signal
+#[derive(Debug, Clone)]
+pub struct PeakMeterNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    followers: Vec<EnvelopeFollower>,
+    handle: MeterHandle,
+}
+
+impl PeakMeterNode {
+    pub const DEFAULT_ATTACK: Duration = Duration::ZERO;
+    pub const DEFAULT_RELEASE: Duration = Duration::from_millis(300);
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(
+            uid,
+            num_channels,
+            Self::DEFAULT_SAMPLE_RATE,
+            Self::DEFAULT_ATTACK,
+            Self::DEFAULT_RELEASE,
+        )
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        attack: Duration,
+        release: Duration,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        let follower = EnvelopeFollower::new(sample_rate, attack, release);
+        Self {
+            uid,
+            num_channels,
+            followers: vec![follower; *num_channels],
+            handle: MeterHandle::new(num_channels),
+        }
+    }
+
+    /// Returns a handle that can be used to read meter levels from the main thread
+    pub fn handle(&self) -> MeterHandle {
+        self.handle.clone()
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.followers
+            .first()
+            .map(EnvelopeFollower::attack)
+            .unwrap_or(Self::DEFAULT_ATTACK)
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_attack(attack);
+        });
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.followers
+            .first()
+            .map(EnvelopeFollower::release)
+            .unwrap_or(Self::DEFAULT_RELEASE)
+    }
+
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_release(release);
+        });
+        self
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_sample_rate(sample_rate);
+        });
+        self
+    }
+
+    /// Drops all current readings back to 0.0
+    pub fn reset(&mut self) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.reset();
+        });
+        self.handle
+            .publish(self.followers.iter().map(EnvelopeFollower::value));
+        self
+    }
+}
+
+impl Node for PeakMeterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let input = inputs
+            .next()
+            .expect("PeakMeterNode should have one and only one input connection");
+        let input_data = input.data();
+
+        self.handle.publish(
+            self.followers
+                .iter_mut()
+                .zip(input_data.iter())
+                .map(|(follower, sample)| follower.next_sample(sample.abs())),
+        );
+
+        // it's possible for a meter to be the last node in a chain,
+        // so an outgoing connection is not required
+        if let Some(mut output) = outputs.next() {
+            output.update_data(|frame| frame.copy_from_slice(input_data));
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("PeakMeterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<PeakMeterNodeMessage>()?;
+
+        match message {
+            PeakMeterNodeMessage::SetAttack { attack } => {
+                self.set_attack(attack);
+            }
+            PeakMeterNodeMessage::SetRelease { release } => {
+                self.set_release(release);
+            }
+            PeakMeterNodeMessage::Reset => {
+                self.reset();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum PeakMeterNodeMessage {
+    SetAttack { attack: Duration },
+    SetRelease { release: Duration },
+    Reset,
+}
+
+impl PartialEq for PeakMeterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for PeakMeterNode {}
+
+impl PartialOrd for PeakMeterNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PeakMeterNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_peak_meter_node {
+    use std::{cell::RefCell, time::Duration};
+
+    use crate::{Connection, Node, PeakMeterNode};
+
+    #[test]
+    fn should_pass_audio_through_and_publish_peak_levels() {
+        let mut meter_node =
+            PeakMeterNode::new_with_full_config(0, 2, 1000, Duration::ZERO, Duration::ZERO);
+        let handle = meter_node.handle();
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![-0.5, 0.25], 0, 0));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            meter_node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![-0.5, 0.25]);
+        assert_eq!(handle.levels(), vec![0.5, 0.25]);
+    }
+
+    #[test]
+    fn should_hold_peak_during_release() {
+        let mut meter_node = PeakMeterNode::new_with_full_config(
+            0,
+            1,
+            1000,
+            Duration::ZERO,
+            Duration::from_millis(100),
+        );
+        let handle = meter_node.handle();
+
+        let loud_connection = RefCell::new(Connection::from_test_data(0, 1, vec![1.0], 0, 0));
+        let silent_connection = RefCell::new(Connection::from_test_data(0, 1, vec![0.0], 0, 0));
+
+        {
+            let inputs = [loud_connection.borrow()];
+            meter_node.process(&mut inputs.into_iter(), &mut [].into_iter());
+        }
+
+        assert_eq!(handle.level(0), Some(1.0));
+
+        {
+            let inputs = [silent_connection.borrow()];
+            meter_node.process(&mut inputs.into_iter(), &mut [].into_iter());
+        }
+
+        let level = handle.level(0).unwrap();
+        assert!(level > 0.9 && level < 1.0);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, PeakMeterNodeMessage};
+
+        let mut meter_node = PeakMeterNode::new(1);
+
+        meter_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(PeakMeterNodeMessage::SetRelease {
+                    release: Duration::from_secs(1),
+                }),
+            })
+            .unwrap();
+
+        assert_eq!(meter_node.release(), Duration::from_secs(1));
+    }
+}
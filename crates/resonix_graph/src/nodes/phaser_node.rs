@@ -0,0 +1,358 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{NumChannels, Phaser, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Sweeps notches up and down the incoming signal by mixing it with a copy
+/// of itself that has passed through a chain of swept all-pass filters.
+///
+/// Works with any number of channels: each channel's LFO is offset by
+/// `channel_phase_offset`, which makes the phaser sound wide.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Dry signal mixed with phase-shifted signal
+#[derive(Debug, Clone)]
+pub struct PhaserNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    phaser: Phaser,
+}
+
+impl PhaserNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            phaser: Phaser::new(num_channels, sample_rate),
+        }
+    }
+
+    /// Frequency of the LFO (in Hz)
+    pub fn rate(&self) -> f32 {
+        self.phaser.rate()
+    }
+
+    pub fn set_rate(&mut self, rate: f32) -> &mut Self {
+        self.phaser.set_rate(rate);
+        self
+    }
+
+    /// How much of the range between `min_frequency` and `max_frequency`
+    /// the LFO sweeps through (from 0.0 to 1.0)
+    pub fn depth(&self) -> f32 {
+        self.phaser.depth()
+    }
+
+    pub fn set_depth(&mut self, depth: f32) -> &mut Self {
+        self.phaser.set_depth(depth);
+        self
+    }
+
+    pub fn feedback(&self) -> f32 {
+        self.phaser.feedback()
+    }
+
+    /// Clamped between -0.95 and 0.95
+    pub fn set_feedback(&mut self, feedback: f32) -> &mut Self {
+        self.phaser.set_feedback(feedback);
+        self
+    }
+
+    /// Balance between the original signal (0.0) and the phase-shifted signal (1.0)
+    pub fn mix(&self) -> f32 {
+        self.phaser.mix()
+    }
+
+    pub fn set_mix(&mut self, mix: f32) -> &mut Self {
+        self.phaser.set_mix(mix);
+        self
+    }
+
+    /// Lowest break frequency (in Hz) of the all-pass filters
+    pub fn min_frequency(&self) -> f32 {
+        self.phaser.min_frequency()
+    }
+
+    pub fn set_min_frequency(&mut self, min_frequency: f32) -> &mut Self {
+        self.phaser.set_min_frequency(min_frequency);
+        self
+    }
+
+    /// Highest break frequency (in Hz) of the all-pass filters
+    pub fn max_frequency(&self) -> f32 {
+        self.phaser.max_frequency()
+    }
+
+    pub fn set_max_frequency(&mut self, max_frequency: f32) -> &mut Self {
+        self.phaser.set_max_frequency(max_frequency);
+        self
+    }
+
+    /// Number of all-pass filters (every 2 stages add one notch)
+    pub fn num_stages(&self) -> usize {
+        self.phaser.num_stages()
+    }
+
+    /// Clamped between 1 and `Phaser::MAX_STAGES`
+    pub fn set_num_stages(&mut self, num_stages: usize) -> &mut Self {
+        self.phaser.set_num_stages(num_stages);
+        self
+    }
+
+    /// How far (in turns) each channel's LFO is ahead of the previous channel's LFO
+    pub fn channel_phase_offset(&self) -> f32 {
+        self.phaser.channel_phase_offset()
+    }
+
+    pub fn set_channel_phase_offset(&mut self, channel_phase_offset: f32) -> &mut Self {
+        self.phaser.set_channel_phase_offset(channel_phase_offset);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.phaser.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.phaser.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Silences the all-pass filters and restarts the LFO
+    pub fn clear(&mut self) -> &mut Self {
+        self.phaser.clear();
+        self
+    }
+}
+
+impl Node for PhaserNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("PhaserNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.phaser.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("PhaserNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<PhaserNodeMessage>()?;
+
+        match message {
+            PhaserNodeMessage::SetRate { rate } => {
+                self.set_rate(rate);
+            }
+            PhaserNodeMessage::SetDepth { depth } => {
+                self.set_depth(depth);
+            }
+            PhaserNodeMessage::SetFeedback { feedback } => {
+                self.set_feedback(feedback);
+            }
+            PhaserNodeMessage::SetMix { mix } => {
+                self.set_mix(mix);
+            }
+            PhaserNodeMessage::SetMinFrequency { min_frequency } => {
+                self.set_min_frequency(min_frequency);
+            }
+            PhaserNodeMessage::SetMaxFrequency { max_frequency } => {
+                self.set_max_frequency(max_frequency);
+            }
+            PhaserNodeMessage::SetNumStages { num_stages } => {
+                self.set_num_stages(num_stages);
+            }
+            PhaserNodeMessage::SetChannelPhaseOffset {
+                channel_phase_offset,
+            } => {
+                self.set_channel_phase_offset(channel_phase_offset);
+            }
+            PhaserNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum PhaserNodeMessage {
+    SetRate { rate: f32 },
+
+    SetDepth { depth: f32 },
+
+    SetFeedback { feedback: f32 },
+
+    SetMix { mix: f32 },
+
+    SetMinFrequency { min_frequency: f32 },
+
+    SetMaxFrequency { max_frequency: f32 },
+
+    SetNumStages { num_stages: usize },
+
+    SetChannelPhaseOffset { channel_phase_offset: f32 },
+
+    Clear,
+}
+
+impl PartialEq for PhaserNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for PhaserNode {}
+
+impl PartialOrd for PhaserNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PhaserNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_phaser_node {
+    use crate::{nodes::test_utils::process_frame, PhaserNode};
+
+    fn render(node: &mut PhaserNode, frames: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        frames
+            .iter()
+            .map(|frame| process_frame(node, &[(0, frame)]))
+            .collect()
+    }
+
+    #[test]
+    fn should_pass_dry_signal() {
+        let mut node = PhaserNode::new_with_full_config(0, 2, 44100);
+        node.set_mix(0.0);
+
+        let frames = vec![vec![0.5, -0.5], vec![0.25, 1.0]];
+        assert_eq!(render(&mut node, &frames), frames);
+    }
+
+    #[test]
+    fn should_phase_channels_differently() {
+        let mut node = PhaserNode::new_with_full_config(0, 2, 44100);
+        node.set_rate(10.0).set_channel_phase_offset(0.5);
+
+        let frames: Vec<_> = (0..2000)
+            .map(|i| vec![(i as f32 * 0.05).sin(); 2])
+            .collect();
+        let response = render(&mut node, &frames);
+
+        assert!(response
+            .iter()
+            .any(|frame| (frame[0] - frame[1]).abs() > 0.1));
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, Node, PhaserNodeMessage};
+
+        let mut node = PhaserNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(PhaserNodeMessage::SetNumStages { num_stages: 8 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.num_stages(), 8);
+    }
+}
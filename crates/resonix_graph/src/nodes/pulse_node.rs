@@ -0,0 +1,256 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{NumChannels, OscillatorInterface, Pulse, SampleRate, SineInterface};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Outputs a band-limited pulse wave with an adjustable pulse width (see `resonix_core::Pulse`).
+///
+/// The default pulse width of 0.5 produces a square wave.
+///
+/// Output 0 - Pulse wave, duplicated across all channels
+#[derive(Debug, Clone)]
+pub struct PulseNode {
+    uid: NodeUid,
+    pulse: Pulse,
+    num_outgoing_channels: NumChannels,
+}
+
+impl PulseNode {
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, frequency: impl Into<f32>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_outgoing_channels, frequency)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_outgoing_channels, 0, frequency)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self {
+            uid,
+            num_outgoing_channels: num_outgoing_channels.into(),
+            pulse: Pulse::new_with_config(sample_rate, frequency),
+        }
+    }
+
+    pub fn pulse_width(&self) -> f32 {
+        self.pulse.pulse_width()
+    }
+
+    /// Portion of each cycle (from 0.0 to 1.0) spent at 1.0 rather than -1.0
+    pub fn set_pulse_width(&mut self, pulse_width: f32) -> &mut Self {
+        self.pulse.set_pulse_width(pulse_width);
+        self
+    }
+}
+
+impl SineInterface for PulseNode {
+    fn next_sample(&mut self) -> f32 {
+        self.pulse.next_sample()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.pulse.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.pulse.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.pulse.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.pulse.frequency()
+    }
+}
+
+impl OscillatorInterface for PulseNode {
+    fn phase(&self) -> f32 {
+        self.pulse.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.pulse.set_phase(phase);
+        self
+    }
+}
+
+impl Node for PulseNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let next_sample = self.next_sample();
+
+        outputs.into_iter().for_each(|mut output| {
+            output.update_data(|buffer| buffer.fill(next_sample));
+        });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("PulseNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.pulse.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<PulseNodeMessage>()?;
+
+        match message {
+            PulseNodeMessage::SetFrequency { new_frequency } => {
+                self.set_frequency(new_frequency);
+            }
+            PulseNodeMessage::ResetPhase => {
+                self.reset_phase();
+            }
+            PulseNodeMessage::SetPulseWidth { pulse_width } => {
+                self.set_pulse_width(pulse_width);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum PulseNodeMessage {
+    SetFrequency { new_frequency: f32 },
+    ResetPhase,
+    SetPulseWidth { pulse_width: f32 },
+}
+
+impl PartialEq for PulseNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for PulseNode {}
+
+impl PartialOrd for PulseNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PulseNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_pulse_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, PulseNode};
+
+    #[test]
+    fn should_output_pulse_wave_data() {
+        let mut pulse_node = PulseNode::new_with_full_config(0, 1, 100, 1.0);
+        pulse_node.set_pulse_width(0.1);
+        let output_connection = RefCell::new(Connection::default());
+
+        let mut samples = Vec::new();
+        for _ in 0..100 {
+            {
+                let outputs = [output_connection.borrow_mut()];
+                pulse_node.process(&mut [].into_iter(), &mut outputs.into_iter());
+            }
+            samples.push(output_connection.borrow().data()[0]);
+        }
+
+        assert_eq!(samples[5], 1.0);
+        assert_eq!(samples[50], -1.0);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, PulseNodeMessage};
+
+        let mut pulse_node = PulseNode::new_with_full_config(0, 1, 4, 1.0);
+
+        pulse_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(PulseNodeMessage::SetPulseWidth { pulse_width: 0.25 }),
+            })
+            .unwrap();
+
+        assert_eq!(pulse_node.pulse_width(), 0.25);
+    }
+}
@@ -1,18 +1,108 @@
 use std::{
     any::Any,
     cell::{Ref, RefMut},
+    sync::{
+        atomic::{fence, AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
-use resonix_core::NumChannels;
+use resonix_core::{AtomicF32, NumChannels};
+
+#[cfg(feature = "dac")]
+use crate::messages::{UpdateNodeError, UpdateNodeMessage};
 
 use crate::{Connection, Node, NodeType, NodeUid};
 
+/// Determines how a `RecordNode` stores incoming audio
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RecordMode {
+    /// Grows to fit all incoming audio, which is stored on the node itself (see `RecordNode::data`).
+    ///
+    /// Note: this allocates on the audio thread, so it's best suited for offline processing.
+    #[default]
+    Unbounded,
+    /// Pre-allocates space for `num_frames` frames, and stops recording once full.
+    ///
+    /// Recorded audio can be read from the main thread via a `RecordHandle`.
+    Bounded { num_frames: usize },
+    /// Pre-allocates space for `num_frames` frames, and keeps only
+    /// the most recent `num_frames` frames, overwriting the oldest audio.
+    ///
+    /// Recorded audio can be read from the main thread via a `RecordHandle`.
+    Ring { num_frames: usize },
+}
+
+impl RecordMode {
+    fn capacity(&self) -> usize {
+        match self {
+            RecordMode::Unbounded => 0,
+            RecordMode::Bounded { num_frames } | RecordMode::Ring { num_frames } => *num_frames,
+        }
+    }
+}
+
+/// Audio shared between the `RecordNode` (writer) and any `RecordHandle`s (readers).
+///
+/// Samples are stored interleaved, in a ring of `capacity` frames.
+#[derive(Debug)]
+struct RecordBuffer {
+    num_channels: usize,
+    capacity: usize,
+    samples: Box<[AtomicF32]>,
+    /// Total number of frames written since the last clear
+    frames_written: AtomicUsize,
+    /// Total number of frames the writer has started writing since the last clear
+    /// (one more than `frames_written` while a frame is being written)
+    frames_started: AtomicUsize,
+    /// Incremented every time the buffer is cleared
+    generation: AtomicUsize,
+    is_recording: AtomicBool,
+}
+
+impl RecordBuffer {
+    fn new(num_channels: NumChannels, capacity: usize) -> Self {
+        Self {
+            num_channels: *num_channels,
+            capacity,
+            samples: (0..(*num_channels * capacity))
+                .map(|_| AtomicF32::default())
+                .collect(),
+            frames_written: AtomicUsize::new(0),
+            frames_started: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+            is_recording: AtomicBool::new(true),
+        }
+    }
+
+    fn read_frame(&self, frame: usize, buffer: &mut [f32]) {
+        let start = (frame % self.capacity) * self.num_channels;
+        buffer
+            .iter_mut()
+            .zip(self.samples[start..(start + self.num_channels)].iter())
+            .for_each(|(sample, atomic)| *sample = atomic.load(Ordering::Relaxed));
+    }
+}
+
+/// Records incoming audio, either onto the node itself
+/// or into a pre-allocated buffer that can be read from the main
+/// thread with a `RecordHandle` (see `RecordMode`).
+///
+/// Recording begins as soon as the node starts processing audio,
+/// and can be paused, resumed, and cleared with `RecordNodeMessage`s.
+///
 /// Stores data as interleaved buffer of samples
-#[derive(Debug, Default, Clone)]
+///
+/// Input 0 - Signal to record
+#[derive(Debug, Clone)]
 pub struct RecordNode {
     data: Vec<f32>,
     num_incoming_channels: NumChannels,
     uid: NodeUid,
+    mode: RecordMode,
+    is_recording: bool,
+    buffer: Arc<RecordBuffer>,
+    frames_written: usize,
 }
 
 impl RecordNode {
@@ -20,20 +110,117 @@ impl RecordNode {
         Self::new_with_uid(0, num_incoming_channels)
     }
 
+    pub fn new_with_mode(num_incoming_channels: impl Into<NumChannels>, mode: RecordMode) -> Self {
+        Self::new_with_full_config(0, num_incoming_channels, mode)
+    }
+
     pub(crate) fn new_with_uid(
         uid: NodeUid,
         num_incoming_channels: impl Into<NumChannels>,
     ) -> Self {
+        Self::new_with_full_config(uid, num_incoming_channels, RecordMode::default())
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_incoming_channels: impl Into<NumChannels>,
+        mode: RecordMode,
+    ) -> Self {
+        let num_incoming_channels = num_incoming_channels.into();
         Self {
             uid,
-            num_incoming_channels: num_incoming_channels.into(),
-            ..Default::default()
+            data: Vec::new(),
+            num_incoming_channels,
+            mode,
+            is_recording: true,
+            buffer: Arc::new(RecordBuffer::new(num_incoming_channels, mode.capacity())),
+            frames_written: 0,
         }
     }
 
+    /// Audio recorded in `RecordMode::Unbounded` mode
     pub fn data(&self) -> &[f32] {
         &self.data
     }
+
+    pub fn mode(&self) -> RecordMode {
+        self.mode
+    }
+
+    /// Returns a handle that can be used to read recorded audio from the main thread
+    /// while in `RecordMode::Bounded` or `RecordMode::Ring` modes.
+    pub fn handle(&self) -> RecordHandle {
+        RecordHandle::new(Arc::clone(&self.buffer))
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.is_recording
+    }
+
+    /// Resumes recording. In `RecordMode::Bounded` mode, this has
+    /// no effect once the buffer is full (until it is cleared).
+    pub fn start(&mut self) -> &mut Self {
+        let is_full = matches!(self.mode, RecordMode::Bounded { num_frames } if self.frames_written >= num_frames);
+        self.set_is_recording(!is_full)
+    }
+
+    /// Pauses recording, keeping any previously recorded audio
+    pub fn stop(&mut self) -> &mut Self {
+        self.set_is_recording(false)
+    }
+
+    /// Discards all previously recorded audio, without
+    /// changing whether or not the node is currently recording.
+    ///
+    /// Does not deallocate any memory.
+    pub fn clear(&mut self) -> &mut Self {
+        self.data.clear();
+        self.frames_written = 0;
+        self.buffer.frames_written.store(0, Ordering::Release);
+        self.buffer.frames_started.store(0, Ordering::Release);
+        self.buffer.generation.fetch_add(1, Ordering::AcqRel);
+        self
+    }
+
+    fn set_is_recording(&mut self, is_recording: bool) -> &mut Self {
+        self.is_recording = is_recording;
+        self.buffer
+            .is_recording
+            .store(is_recording, Ordering::Release);
+        self
+    }
+
+    fn write_frame(&mut self, frame: &[f32]) {
+        let capacity = self.buffer.capacity;
+        if capacity == 0 {
+            return;
+        }
+
+        // readers that see any part of this frame must also see that it was started,
+        // since it overwrites the oldest frame in the ring (see `RecordHandle::copy_frames`)
+        self.buffer
+            .frames_started
+            .store(self.frames_written + 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        let start = (self.frames_written % capacity) * self.buffer.num_channels;
+        self.buffer.samples[start..(start + self.buffer.num_channels)]
+            .iter()
+            .zip(frame.iter())
+            .for_each(|(atomic, sample)| atomic.store(*sample, Ordering::Relaxed));
+
+        self.frames_written += 1;
+        self.buffer
+            .frames_written
+            .store(self.frames_written, Ordering::Release);
+    }
+}
+
+impl Default for RecordNode {
+    /// A node with no channels, which records in `RecordMode::Unbounded` mode
+    fn default() -> Self {
+        Self::new(0)
+    }
 }
 
 impl Node for RecordNode {
@@ -55,7 +242,20 @@ impl Node for RecordNode {
             assert_eq!(input_num_channels, self_num_channels, "Number of channels in the input connection to a RecordNode does not match number of channels that RecordNode was expecting. Expected {self_num_channels} but found {input_num_channels}");
         }
 
-        self.data.extend_from_slice(input_data);
+        if !self.is_recording {
+            return;
+        }
+
+        match self.mode {
+            RecordMode::Unbounded => self.data.extend_from_slice(input_data),
+            RecordMode::Bounded { num_frames } => {
+                self.write_frame(input_data);
+                if self.frames_written >= num_frames {
+                    self.stop();
+                }
+            }
+            RecordMode::Ring { .. } => self.write_frame(input_data),
+        }
     }
 
     fn node_type(&self) -> NodeType {
@@ -96,6 +296,34 @@ impl Node for RecordNode {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<RecordNodeMessage>()?;
+
+        match message {
+            RecordNodeMessage::Start => {
+                self.start();
+            }
+            RecordNodeMessage::Stop => {
+                self.stop();
+            }
+            RecordNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum RecordNodeMessage {
+    Start,
+    Stop,
+    Clear,
 }
 
 impl PartialEq for RecordNode {
@@ -118,12 +346,204 @@ impl Ord for RecordNode {
     }
 }
 
+/// The `RecordHandle` reads audio recorded by a `RecordNode` from the main thread,
+/// even after that node has been sent to the audio thread.
+///
+/// Audio can either be fetched all at once (`fetch`), or streamed
+/// in chunks as it's recorded (`read_new`), in which case only audio that
+/// hasn't already been read by this handle is returned.
+///
+/// Only audio recorded in `RecordMode::Bounded` or `RecordMode::Ring` modes
+/// is available to the `RecordHandle`.
+///
+/// All data is returned as interleaved buffers of samples.
+#[derive(Debug, Clone)]
+pub struct RecordHandle {
+    buffer: Arc<RecordBuffer>,
+    generation: usize,
+    frames_read: usize,
+    frames_dropped: usize,
+}
+
+impl RecordHandle {
+    fn new(buffer: Arc<RecordBuffer>) -> Self {
+        let generation = buffer.generation.load(Ordering::Acquire);
+        Self {
+            buffer,
+            generation,
+            frames_read: 0,
+            frames_dropped: 0,
+        }
+    }
+
+    pub fn num_channels(&self) -> NumChannels {
+        NumChannels::from(self.buffer.num_channels)
+    }
+
+    /// The maximum number of frames that can be stored at once
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.buffer.is_recording.load(Ordering::Acquire)
+    }
+
+    /// Total number of frames recorded since the recording was last cleared
+    /// (in `RecordMode::Ring` mode, this can be greater than the `capacity`)
+    pub fn num_frames_recorded(&self) -> usize {
+        self.buffer.frames_written.load(Ordering::Acquire)
+    }
+
+    /// Number of frames that are currently stored and can be fetched
+    pub fn num_frames_available(&self) -> usize {
+        self.num_frames_recorded().min(self.buffer.capacity)
+    }
+
+    /// Number of frames that were overwritten before they could be read by `read_new`
+    pub fn num_frames_dropped(&self) -> usize {
+        self.frames_dropped
+    }
+
+    /// Returns all currently stored audio, from oldest to newest
+    pub fn fetch(&self) -> Vec<f32> {
+        let mut data = vec![0.0; self.num_frames_available() * self.buffer.num_channels];
+        let num_frames = self.fetch_into(&mut data);
+        data.truncate(num_frames * self.buffer.num_channels);
+        data
+    }
+
+    /// Copies as many of the currently stored frames as will fit into `buffer`,
+    /// from oldest to newest. Returns the number of frames copied.
+    pub fn fetch_into(&self, buffer: &mut [f32]) -> usize {
+        let frames_written = self.num_frames_recorded();
+        let first_frame = frames_written.saturating_sub(self.buffer.capacity);
+        let (_, num_frames) = self.copy_frames(first_frame, frames_written, buffer);
+        num_frames
+    }
+
+    /// Returns any audio that has been recorded since the last call to `read_new`
+    pub fn read_new(&mut self) -> Vec<f32> {
+        self.sync_generation();
+        let num_frames = self
+            .num_frames_recorded()
+            .saturating_sub(self.frames_read)
+            .min(self.buffer.capacity);
+        let mut data = vec![0.0; num_frames * self.buffer.num_channels];
+        let num_frames = self.read_new_into(&mut data);
+        data.truncate(num_frames * self.buffer.num_channels);
+        data
+    }
+
+    /// Copies as much audio recorded since the last read as will fit into `buffer`.
+    /// Returns the number of frames copied.
+    ///
+    /// In `RecordMode::Ring` mode, this should be called at least once every
+    /// `capacity` frames, or the oldest unread audio will be dropped.
+    pub fn read_new_into(&mut self, buffer: &mut [f32]) -> usize {
+        self.sync_generation();
+
+        let frames_written = self.num_frames_recorded();
+        let oldest_available_frame = frames_written.saturating_sub(self.buffer.capacity);
+        if self.frames_read < oldest_available_frame {
+            self.frames_dropped += oldest_available_frame - self.frames_read;
+            self.frames_read = oldest_available_frame;
+        }
+
+        let (first_frame, num_frames) = self.copy_frames(self.frames_read, frames_written, buffer);
+        if first_frame > self.frames_read {
+            self.frames_dropped += first_frame - self.frames_read;
+        }
+        self.frames_read = first_frame + num_frames;
+        num_frames
+    }
+
+    fn sync_generation(&mut self) {
+        let generation = self.buffer.generation.load(Ordering::Acquire);
+        if generation != self.generation {
+            self.generation = generation;
+            self.frames_read = 0;
+        }
+    }
+
+    /// Copies frames `first_frame..end_frame` into the start of `buffer`.
+    ///
+    /// In `RecordMode::Ring` mode, the `RecordNode` can overwrite the oldest frames
+    /// while they are being copied, so any frames that may have been overwritten are
+    /// dropped from the front of `buffer` (and nothing is returned if the recording was
+    /// cleared in the meantime). Returns the first frame and the number of frames copied.
+    fn copy_frames(
+        &self,
+        first_frame: usize,
+        end_frame: usize,
+        buffer: &mut [f32],
+    ) -> (usize, usize) {
+        let num_channels = self.buffer.num_channels;
+        if num_channels == 0 || self.buffer.capacity == 0 {
+            return (first_frame, 0);
+        }
+
+        let generation = self.buffer.generation.load(Ordering::Acquire);
+        let num_frames = end_frame
+            .saturating_sub(first_frame)
+            .min(buffer.len() / num_channels);
+
+        buffer
+            .chunks_exact_mut(num_channels)
+            .take(num_frames)
+            .enumerate()
+            .for_each(|(i, frame)| self.buffer.read_frame(first_frame + i, frame));
+
+        // pairs with the fence in `RecordNode::write_frame`, so that any frame
+        // read above that was being overwritten is counted in `frames_started`
+        fence(Ordering::Acquire);
+        if self.buffer.generation.load(Ordering::Relaxed) != generation {
+            return (first_frame, 0);
+        }
+
+        let oldest_intact_frame = self
+            .buffer
+            .frames_started
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.buffer.capacity);
+        let num_frames_overwritten = oldest_intact_frame
+            .saturating_sub(first_frame)
+            .min(num_frames);
+        if num_frames_overwritten > 0 {
+            buffer.copy_within(
+                (num_frames_overwritten * num_channels)..(num_frames * num_channels),
+                0,
+            );
+        }
+
+        (
+            first_frame + num_frames_overwritten,
+            num_frames - num_frames_overwritten,
+        )
+    }
+}
+
 #[cfg(test)]
 mod test_record_node {
 
     use std::cell::RefCell;
 
-    use crate::{Connection, Node, RecordNode};
+    use crate::{Connection, Node, RecordMode, RecordNode};
+
+    fn record_frames(record_node: &mut RecordNode, frames: &[Vec<f32>]) {
+        for frame in frames {
+            let input_connection = RefCell::new(Connection::from_test_data(
+                0,
+                frame.len(),
+                frame.clone(),
+                0,
+                0,
+            ));
+            let inputs = [input_connection.borrow()];
+            let outputs = [];
+            record_node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+    }
 
     #[test]
     fn should_record_incoming_node_data() {
@@ -162,4 +582,150 @@ mod test_record_node {
         assert_eq!(record_node.data().len(), 5);
         assert_eq!(*record_node.data(), input_connection_data);
     }
+
+    #[test]
+    fn should_not_record_while_stopped() {
+        let mut record_node = RecordNode::new(1);
+
+        record_frames(&mut record_node, &[vec![1.0]]);
+        record_node.stop();
+        record_frames(&mut record_node, &[vec![2.0]]);
+        record_node.start();
+        record_frames(&mut record_node, &[vec![3.0]]);
+
+        assert_eq!(record_node.data(), &[1.0, 3.0]);
+
+        record_node.clear();
+        assert!(record_node.data().is_empty());
+        assert!(record_node.is_recording());
+    }
+
+    #[test]
+    fn should_stop_recording_when_bounded_buffer_is_full() {
+        let mut record_node = RecordNode::new_with_mode(2, RecordMode::Bounded { num_frames: 2 });
+        let handle = record_node.handle();
+
+        record_frames(
+            &mut record_node,
+            &[vec![1.0, -1.0], vec![2.0, -2.0], vec![3.0, -3.0]],
+        );
+
+        assert!(!handle.is_recording());
+        assert_eq!(handle.fetch(), vec![1.0, -1.0, 2.0, -2.0]);
+
+        // can't restart until cleared
+        record_node.start();
+        assert!(!record_node.is_recording());
+
+        record_node.clear().start();
+        record_frames(&mut record_node, &[vec![4.0, -4.0]]);
+        assert_eq!(handle.fetch(), vec![4.0, -4.0]);
+    }
+
+    #[test]
+    fn should_keep_most_recent_frames_in_ring_mode() {
+        let mut record_node = RecordNode::new_with_mode(1, RecordMode::Ring { num_frames: 3 });
+        let handle = record_node.handle();
+
+        let frames: Vec<Vec<f32>> = (0..5).map(|i| vec![i as f32]).collect();
+        record_frames(&mut record_node, &frames);
+
+        assert!(handle.is_recording());
+        assert_eq!(handle.num_frames_recorded(), 5);
+        assert_eq!(handle.fetch(), vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn should_stream_new_audio_in_chunks() {
+        let mut record_node = RecordNode::new_with_mode(1, RecordMode::Ring { num_frames: 4 });
+        let mut handle = record_node.handle();
+
+        record_frames(&mut record_node, &[vec![1.0], vec![2.0]]);
+        assert_eq!(handle.read_new(), vec![1.0, 2.0]);
+        assert!(handle.read_new().is_empty());
+
+        record_frames(&mut record_node, &[vec![3.0]]);
+        let mut buffer = [0.0; 4];
+        assert_eq!(handle.read_new_into(&mut buffer), 1);
+        assert_eq!(buffer[0], 3.0);
+
+        // reader falls behind: oldest frames are dropped
+        let frames: Vec<Vec<f32>> = (4..10).map(|i| vec![i as f32]).collect();
+        record_frames(&mut record_node, &frames);
+        assert_eq!(handle.read_new(), vec![6.0, 7.0, 8.0, 9.0]);
+        assert_eq!(handle.num_frames_dropped(), 2);
+
+        // clearing restarts the stream
+        record_node.clear();
+        record_frames(&mut record_node, &[vec![10.0]]);
+        assert_eq!(handle.read_new(), vec![10.0]);
+    }
+
+    #[test]
+    fn should_not_return_overwritten_frames_while_recording_concurrently() {
+        const NUM_FRAMES: usize = 200_000;
+
+        let mut record_node = RecordNode::new_with_mode(2, RecordMode::Ring { num_frames: 4 });
+        let mut stream_handle = record_node.handle();
+        let fetch_handle = record_node.handle();
+
+        // every frame holds its own index in each channel, so torn
+        // or out-of-order frames can be detected by the reader
+        let writer = std::thread::spawn(move || {
+            let frames: Vec<Vec<f32>> = (0..NUM_FRAMES).map(|i| vec![i as f32; 2]).collect();
+            record_frames(&mut record_node, &frames);
+        });
+
+        let assert_consecutive_frames = |data: &[f32]| {
+            data.chunks_exact(2).enumerate().for_each(|(i, frame)| {
+                assert_eq!(frame[0], frame[1]);
+                assert_eq!(frame[0], data[0] + i as f32);
+            });
+        };
+
+        let mut buffer = [0.0; 8];
+        let mut next_frame = 0.0;
+        while stream_handle.num_frames_recorded() < NUM_FRAMES {
+            let num_frames = stream_handle.read_new_into(&mut buffer);
+            let data = &buffer[..(num_frames * 2)];
+            assert_consecutive_frames(data);
+            if let Some(first_frame) = data.first() {
+                assert!(*first_frame >= next_frame);
+                next_frame = data[data.len() - 1] + 1.0;
+            }
+
+            let num_frames = fetch_handle.fetch_into(&mut buffer);
+            assert_consecutive_frames(&buffer[..(num_frames * 2)]);
+        }
+
+        writer.join().unwrap();
+    }
+
+    #[test]
+    fn should_default_to_empty_unbounded_recording() {
+        let record_node = RecordNode::default();
+
+        assert_eq!(record_node.uid(), 0);
+        assert_eq!(*record_node.num_incoming_channels(), 0);
+        assert_eq!(record_node.mode(), RecordMode::Unbounded);
+        assert!(record_node.is_recording());
+        assert!(record_node.data().is_empty());
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, RecordNodeMessage};
+
+        let mut record_node = RecordNode::new(1);
+
+        record_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(RecordNodeMessage::Stop),
+            })
+            .unwrap();
+
+        assert!(!record_node.is_recording());
+    }
 }
@@ -0,0 +1,532 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use resonix_core::{NumChannels, ResampleQuality, Resampler, SampleQueue, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResamplerNodeError {
+    #[error("ResamplerNode's queue was sized for input at up to {max_input_sample_rate:?}, but {input_sample_rate:?} was requested")]
+    InputSampleRateTooHigh {
+        input_sample_rate: SampleRate,
+        max_input_sample_rate: SampleRate,
+    },
+}
+
+/// Counters shared between the `ResamplerNode` and any `ResamplerHandle`s
+#[derive(Debug, Default)]
+struct ResamplerState {
+    frames_underrun: AtomicUsize,
+}
+
+/// Plays a stream of audio recorded at a different sample rate than the audio context
+/// (e.g. audio from a network stream or from a separate input device),
+/// converting it to the context's sample rate on the fly.
+///
+/// Interleaved audio is pushed into the node from any thread via a `ResamplerHandle`.
+/// If the node runs out of queued audio, it outputs silence until more arrives
+/// (see `ResamplerHandle::frames_underrun`).
+///
+/// The queue and resampling kernels are sized up front for a maximum input sample rate
+/// (see `new_with_max_input_sample_rate`) and a minimum audio context sample rate
+/// (see `MIN_SAMPLE_RATE`), so that changing the sample rates or quality from the
+/// audio thread never allocates.
+///
+/// Output 0 - Resampled audio
+#[derive(Debug, Clone)]
+pub struct ResamplerNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    max_input_sample_rate: SampleRate,
+    resamplers: Vec<Resampler>,
+    queue: SampleQueue,
+    /// Scratch space for moving one frame at a time out of the queue
+    frame: Vec<f32>,
+    state: Arc<ResamplerState>,
+}
+
+impl ResamplerNode {
+    /// How much audio (at the max input sample rate) can be queued up before pushes start failing
+    pub const QUEUE_DURATION: Duration = Duration::from_secs(1);
+
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    /// Lowest audio context sample rate that the resampling kernels are sized for.
+    /// Lower sample rates still work, but may allocate when they are set.
+    pub const MIN_SAMPLE_RATE: u32 = 8000;
+
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        input_sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> Self {
+        let input_sample_rate = input_sample_rate.into();
+        Self::new_with_max_input_sample_rate(
+            num_channels,
+            input_sample_rate,
+            input_sample_rate,
+            quality,
+        )
+    }
+
+    /// Leaves room for the input sample rate to be raised later on, up to `max_input_sample_rate`
+    pub fn new_with_max_input_sample_rate(
+        num_channels: impl Into<NumChannels>,
+        input_sample_rate: impl Into<SampleRate>,
+        max_input_sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> Self {
+        Self::new_with_uid(
+            0,
+            num_channels,
+            input_sample_rate,
+            max_input_sample_rate,
+            quality,
+        )
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        input_sample_rate: impl Into<SampleRate>,
+        max_input_sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> Self {
+        Self::new_with_full_config(
+            uid,
+            num_channels,
+            input_sample_rate,
+            max_input_sample_rate,
+            Self::DEFAULT_SAMPLE_RATE,
+            quality,
+        )
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        input_sample_rate: impl Into<SampleRate>,
+        max_input_sample_rate: impl Into<SampleRate>,
+        sample_rate: impl Into<SampleRate>,
+        quality: ResampleQuality,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        let input_sample_rate = input_sample_rate.into();
+        let max_input_sample_rate = max_input_sample_rate.into().max(input_sample_rate);
+        let sample_rate = sample_rate.into();
+
+        let queue_capacity = (Self::QUEUE_DURATION.as_secs_f32()
+            * max_input_sample_rate.get() as f32)
+            .ceil() as usize
+            * *num_channels;
+
+        let mut node = Self {
+            uid,
+            num_channels,
+            max_input_sample_rate,
+            resamplers: vec![
+                Resampler::new(input_sample_rate, sample_rate, quality);
+                *num_channels
+            ],
+            queue: SampleQueue::new(queue_capacity.max(*num_channels)),
+            frame: vec![0.0; *num_channels],
+            state: Arc::new(ResamplerState::default()),
+        };
+        node.reserve_latency();
+        node
+    }
+
+    /// Returns a handle that can be used to push audio into the node from the main thread
+    pub fn handle(&self) -> ResamplerHandle {
+        ResamplerHandle {
+            num_channels: self.num_channels,
+            queue: self.queue.clone(),
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Sample rate of the audio being pushed into the node
+    pub fn input_sample_rate(&self) -> SampleRate {
+        self.resamplers
+            .first()
+            .map(Resampler::input_sample_rate)
+            .unwrap_or_default()
+    }
+
+    /// Highest input sample rate that the node's queue was sized for
+    pub fn max_input_sample_rate(&self) -> SampleRate {
+        self.max_input_sample_rate
+    }
+
+    /// Fails if the new input sample rate is higher than `max_input_sample_rate`,
+    /// since `QUEUE_DURATION` of audio would no longer fit in the queue
+    pub fn set_input_sample_rate(
+        &mut self,
+        input_sample_rate: impl Into<SampleRate>,
+    ) -> Result<&mut Self, ResamplerNodeError> {
+        let input_sample_rate = input_sample_rate.into();
+        if input_sample_rate > self.max_input_sample_rate {
+            return Err(ResamplerNodeError::InputSampleRateTooHigh {
+                input_sample_rate,
+                max_input_sample_rate: self.max_input_sample_rate,
+            });
+        }
+
+        self.resamplers.iter_mut().for_each(|resampler| {
+            resampler.set_input_sample_rate(input_sample_rate);
+        });
+        Ok(self)
+    }
+
+    /// Sample rate of the audio context
+    pub fn sample_rate(&self) -> SampleRate {
+        self.resamplers
+            .first()
+            .map(Resampler::output_sample_rate)
+            .unwrap_or_default()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.resamplers.iter_mut().for_each(|resampler| {
+            resampler.set_output_sample_rate(sample_rate);
+        });
+        self
+    }
+
+    pub fn quality(&self) -> ResampleQuality {
+        self.resamplers
+            .first()
+            .map(Resampler::quality)
+            .unwrap_or_default()
+    }
+
+    pub fn set_quality(&mut self, quality: ResampleQuality) -> &mut Self {
+        self.resamplers.iter_mut().for_each(|resampler| {
+            resampler.set_quality(quality);
+        });
+        self
+    }
+
+    /// Makes room in every resampler for the widest kernel that any allowed input
+    /// sample rate, audio context sample rate, and quality could need,
+    /// so that changing them doesn't allocate
+    fn reserve_latency(&mut self) {
+        let min_sample_rate = self
+            .sample_rate()
+            .min(SampleRate::from(Self::MIN_SAMPLE_RATE));
+        let latency = Resampler::latency_for(
+            self.max_input_sample_rate,
+            min_sample_rate,
+            ResampleQuality::Best,
+        );
+        self.resamplers
+            .iter_mut()
+            .for_each(|resampler| resampler.reserve_latency(latency));
+    }
+
+    /// Feeds queued frames into the resamplers until every channel can produce its next sample.
+    ///
+    /// Returns `false` if the queue ran dry first.
+    fn fill_resamplers(&mut self) -> bool {
+        // every channel receives the same number of input samples, so they all need input at once
+        while self.resamplers.first().is_some_and(Resampler::needs_input) {
+            if self.queue.len() < self.frame.len() {
+                return false;
+            }
+            self.queue.pop_into(&mut self.frame);
+            self.resamplers
+                .iter_mut()
+                .zip(self.frame.iter())
+                .for_each(|(resampler, sample)| resampler.push_sample(*sample));
+        }
+        true
+    }
+}
+
+impl Node for ResamplerNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ResamplerNode should have one and only one output connection");
+
+        if !self.fill_resamplers() {
+            self.state.frames_underrun.fetch_add(1, Ordering::Relaxed);
+            output.update_data(|frame| frame.fill(0.0));
+            return;
+        }
+
+        output
+            .data_mut()
+            .iter_mut()
+            .zip(self.resamplers.iter_mut())
+            .for_each(|(sample, resampler)| {
+                *sample = resampler.next_sample().unwrap_or_default();
+            });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ResamplerNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let uid = self.uid;
+        let message = update_node_message.try_into::<ResamplerNodeMessage>()?;
+
+        let result = match message {
+            ResamplerNodeMessage::SetInputSampleRate { input_sample_rate } => {
+                self.set_input_sample_rate(input_sample_rate).map(|_| ())
+            }
+            ResamplerNodeMessage::SetQuality { quality } => {
+                self.set_quality(quality);
+                Ok(())
+            }
+        };
+
+        result.map_err(|_| UpdateNodeError::InvalidData { uid })
+    }
+}
+
+pub enum ResamplerNodeMessage {
+    SetInputSampleRate { input_sample_rate: SampleRate },
+    SetQuality { quality: ResampleQuality },
+}
+
+impl PartialEq for ResamplerNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ResamplerNode {}
+
+impl PartialOrd for ResamplerNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ResamplerNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+/// The `ResamplerHandle` allows pushing audio into a `ResamplerNode` from the main thread,
+/// even after that node has been sent to the audio thread.
+///
+/// Only one thread should push audio at a time. This struct can be safely and cheaply cloned
+#[derive(Debug, Clone)]
+pub struct ResamplerHandle {
+    num_channels: NumChannels,
+    queue: SampleQueue,
+    state: Arc<ResamplerState>,
+}
+
+impl ResamplerHandle {
+    /// Queues interleaved audio (i.e. `[L, R, L, R, ...]`) at the node's input sample rate.
+    ///
+    /// Only whole frames are queued. Returns the number of frames that fit in the queue.
+    pub fn push_interleaved(&self, samples: &[f32]) -> usize {
+        let num_channels = (*self.num_channels).max(1);
+        let num_frames = (samples.len() / num_channels).min(self.free_frames());
+        self.queue.push_slice(&samples[..num_frames * num_channels]);
+        num_frames
+    }
+
+    /// Number of frames waiting to be resampled
+    pub fn frames_queued(&self) -> usize {
+        self.queue.len() / (*self.num_channels).max(1)
+    }
+
+    /// Number of frames that can be pushed before the queue is full
+    pub fn free_frames(&self) -> usize {
+        self.queue.free_len() / (*self.num_channels).max(1)
+    }
+
+    /// Number of output frames that were silent because no queued audio was available
+    pub fn frames_underrun(&self) -> usize {
+        self.state.frames_underrun.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod test_resampler_node {
+    use resonix_core::{resample_samples, ResampleQuality, SampleRate};
+
+    use crate::{nodes::test_utils::process_frame, ResamplerNode, ResamplerNodeError};
+
+    fn render(node: &mut ResamplerNode, num_frames: usize) -> Vec<Vec<f32>> {
+        (0..num_frames).map(|_| process_frame(node, &[])).collect()
+    }
+
+    #[test]
+    fn should_output_silence_and_count_underruns_without_input() {
+        let mut node = ResamplerNode::new(2, 22050, ResampleQuality::High);
+        let handle = node.handle();
+
+        assert_eq!(render(&mut node, 3), vec![vec![0.0, 0.0]; 3]);
+        assert_eq!(handle.frames_underrun(), 3);
+    }
+
+    #[test]
+    fn should_resample_pushed_audio_to_context_sample_rate() {
+        let mut node = ResamplerNode::new_with_full_config(0, 2, 4, 4, 8, ResampleQuality::Fast);
+        let handle = node.handle();
+
+        assert_eq!(
+            handle.push_interleaved(&[0.0, 0.0, 1.0, -1.0, 2.0, -2.0, 3.0]),
+            3
+        );
+        assert_eq!(handle.frames_queued(), 3);
+
+        // twice as many output frames as input frames, linearly interpolated
+        assert_eq!(
+            render(&mut node, 4),
+            vec![
+                vec![0.0, 0.0],
+                vec![0.5, -0.5],
+                vec![1.0, -1.0],
+                vec![1.5, -1.5]
+            ]
+        );
+        assert_eq!(handle.frames_underrun(), 0);
+    }
+
+    #[test]
+    fn should_match_offline_resampling() {
+        let input: Vec<f32> = (0..2000).map(|i| (i as f32 * 0.01).sin()).collect();
+        let expected = resample_samples(&input, 48000, 44100, ResampleQuality::High);
+
+        let mut node =
+            ResamplerNode::new_with_full_config(0, 1, 48000, 48000, 44100, ResampleQuality::High);
+        node.handle().push_interleaved(&input);
+
+        let output = render(&mut node, 1000);
+        output
+            .iter()
+            .zip(expected.iter())
+            .for_each(|(frame, expected)| assert!((frame[0] - expected).abs() < 0.0001));
+    }
+
+    #[test]
+    fn should_reject_input_sample_rates_the_queue_cannot_hold() {
+        let mut node =
+            ResamplerNode::new_with_max_input_sample_rate(2, 44100, 96000, ResampleQuality::High);
+        let handle = node.handle();
+        assert_eq!(handle.free_frames(), 96000);
+
+        assert!(node.set_input_sample_rate(96000).is_ok());
+        assert_eq!(
+            node.set_input_sample_rate(192000).unwrap_err(),
+            ResamplerNodeError::InputSampleRateTooHigh {
+                input_sample_rate: SampleRate::from(192000),
+                max_input_sample_rate: SampleRate::from(96000),
+            }
+        );
+        assert_eq!(node.input_sample_rate(), SampleRate::from(96000));
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, Node, ResamplerNodeMessage};
+
+        let mut node = ResamplerNode::new(1, 44100, ResampleQuality::High);
+
+        assert!(node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(ResamplerNodeMessage::SetInputSampleRate {
+                    input_sample_rate: SampleRate::from(48000),
+                }),
+            })
+            .is_err());
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ResamplerNodeMessage::SetInputSampleRate {
+                input_sample_rate: SampleRate::from(22050),
+            }),
+        })
+        .unwrap();
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ResamplerNodeMessage::SetQuality {
+                quality: ResampleQuality::Fast,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.input_sample_rate(), SampleRate::from(22050));
+        assert_eq!(node.quality(), ResampleQuality::Fast);
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{NumChannels, Reverb, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Adds algorithmic (Freeverb-style) reverb to the incoming signal.
+///
+/// Works with any number of channels: each channel of reverb is slightly
+/// different from the others, which makes the reverb sound wide.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Dry signal mixed with reverb
+#[derive(Debug, Clone)]
+pub struct ReverbNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    reverb: Reverb,
+}
+
+impl ReverbNode {
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(uid, num_channels, Self::DEFAULT_SAMPLE_RATE)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            reverb: Reverb::new(num_channels, sample_rate),
+        }
+    }
+
+    pub fn room_size(&self) -> f32 {
+        self.reverb.room_size()
+    }
+
+    /// Larger rooms (from 0.0 to 1.0) ring out for longer
+    pub fn set_room_size(&mut self, room_size: f32) -> &mut Self {
+        self.reverb.set_room_size(room_size);
+        self
+    }
+
+    pub fn damping(&self) -> f32 {
+        self.reverb.damping()
+    }
+
+    /// Higher damping (from 0.0 to 1.0) makes high frequencies die out faster
+    pub fn set_damping(&mut self, damping: f32) -> &mut Self {
+        self.reverb.set_damping(damping);
+        self
+    }
+
+    pub fn width(&self) -> f32 {
+        self.reverb.width()
+    }
+
+    /// Stereo width of the reverb (from 0.0 to 1.0)
+    pub fn set_width(&mut self, width: f32) -> &mut Self {
+        self.reverb.set_width(width);
+        self
+    }
+
+    pub fn wet(&self) -> f32 {
+        self.reverb.wet()
+    }
+
+    /// Gain of the reverb in the output
+    pub fn set_wet(&mut self, wet: f32) -> &mut Self {
+        self.reverb.set_wet(wet);
+        self
+    }
+
+    pub fn dry(&self) -> f32 {
+        self.reverb.dry()
+    }
+
+    /// Gain of the original signal in the output
+    pub fn set_dry(&mut self, dry: f32) -> &mut Self {
+        self.reverb.set_dry(dry);
+        self
+    }
+
+    pub fn pre_delay(&self) -> Duration {
+        self.reverb.pre_delay()
+    }
+
+    /// Time before the reverb starts (up to `Reverb::MAX_PRE_DELAY`)
+    pub fn set_pre_delay(&mut self, pre_delay: Duration) -> &mut Self {
+        self.reverb.set_pre_delay(pre_delay);
+        self
+    }
+
+    pub fn sample_rate(&self) -> SampleRate {
+        self.reverb.sample_rate()
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.reverb.set_sample_rate(sample_rate);
+        self
+    }
+
+    /// Silences the reverb's tail
+    pub fn clear(&mut self) -> &mut Self {
+        self.reverb.clear();
+        self
+    }
+}
+
+impl Node for ReverbNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ReverbNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.reverb.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ReverbNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<ReverbNodeMessage>()?;
+
+        match message {
+            ReverbNodeMessage::SetRoomSize { room_size } => {
+                self.set_room_size(room_size);
+            }
+            ReverbNodeMessage::SetDamping { damping } => {
+                self.set_damping(damping);
+            }
+            ReverbNodeMessage::SetWidth { width } => {
+                self.set_width(width);
+            }
+            ReverbNodeMessage::SetWet { wet } => {
+                self.set_wet(wet);
+            }
+            ReverbNodeMessage::SetDry { dry } => {
+                self.set_dry(dry);
+            }
+            ReverbNodeMessage::SetPreDelay { pre_delay } => {
+                self.set_pre_delay(pre_delay);
+            }
+            ReverbNodeMessage::Clear => {
+                self.clear();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum ReverbNodeMessage {
+    SetRoomSize { room_size: f32 },
+    SetDamping { damping: f32 },
+    SetWidth { width: f32 },
+    SetWet { wet: f32 },
+    SetDry { dry: f32 },
+    SetPreDelay { pre_delay: Duration },
+    Clear,
+}
+
+impl PartialEq for ReverbNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ReverbNode {}
+
+impl PartialOrd for ReverbNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReverbNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_reverb_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, ReverbNode};
+
+    fn impulse_response(node: &mut ReverbNode, num_frames: usize) -> Vec<Vec<f32>> {
+        let num_channels = *node.num_outgoing_channels();
+        let output_connection = RefCell::new(Connection::new(num_channels));
+        (0..num_frames)
+            .map(|i| {
+                let sample = if i == 0 { 1.0 } else { 0.0 };
+                let input_connection = RefCell::new(Connection::from_test_data(
+                    0,
+                    num_channels,
+                    vec![sample; num_channels],
+                    0,
+                    0,
+                ));
+                {
+                    let inputs = [input_connection.borrow()];
+                    let outputs = [output_connection.borrow_mut()];
+                    node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+                }
+                let output = output_connection.borrow().data().to_vec();
+                output
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_produce_deterministic_stereo_reverb() {
+        let mut node = ReverbNode::new_with_full_config(0, 2, 44100);
+        node.set_room_size(0.8).set_damping(0.3).set_dry(0.0);
+
+        let response = impulse_response(&mut node, 6000);
+        // the first echoes of both channels, followed by every 250th frame of the tail
+        let snapshot: Vec<_> = response[1116..1124]
+            .iter()
+            .chain(response[1124..].iter().step_by(250))
+            .collect();
+
+        insta::assert_debug_snapshot!(snapshot);
+    }
+
+    #[test]
+    fn should_pass_dry_signal() {
+        let mut node = ReverbNode::new_with_full_config(0, 1, 44100);
+        node.set_wet(0.0).set_dry(0.5);
+
+        assert_eq!(impulse_response(&mut node, 2), vec![vec![0.5], vec![0.0]]);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, ReverbNodeMessage};
+
+        let mut node = ReverbNode::new(2);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(ReverbNodeMessage::SetRoomSize { room_size: 0.9 }),
+        })
+        .unwrap();
+
+        assert_eq!(node.room_size(), 0.9);
+    }
+}
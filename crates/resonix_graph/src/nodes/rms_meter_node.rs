@@ -0,0 +1,327 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    time::Duration,
+};
+
+use resonix_core::{EnvelopeFollower, NumChannels, SampleRate};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, MeterHandle, Node, NodeType, NodeUid};
+
+/// Meters the RMS (root mean square) amplitude of each incoming channel,
+/// which more closely matches perceived loudness than the peak amplitude.
+///
+/// The mean square of each channel is averaged over the attack / release
+/// times, so these act as the integration window of the meter.
+///
+/// The signal is passed through unaltered, so this node can be inserted
+/// anywhere in the audio graph. Readings are smoothed with attack / release
+/// ballistics and can be polled from the main thread via the `MeterHandle`
+/// returned from `RmsMeterNode::handle`.
+///
+/// Input 0 - Signal to meter
+///
+/// Output 0 - Unaltered input signal
+#[derive(Debug, Clone)]
+pub struct RmsMeterNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    followers: Vec<EnvelopeFollower>,
+    handle: MeterHandle,
+}
+
+impl RmsMeterNode {
+    pub const DEFAULT_ATTACK: Duration = Duration::from_millis(300);
+    pub const DEFAULT_RELEASE: Duration = Duration::from_millis(300);
+    pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_full_config(
+            uid,
+            num_channels,
+            Self::DEFAULT_SAMPLE_RATE,
+            Self::DEFAULT_ATTACK,
+            Self::DEFAULT_RELEASE,
+        )
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        attack: Duration,
+        release: Duration,
+    ) -> Self {
+        let num_channels = num_channels.into();
+        let follower = EnvelopeFollower::new(sample_rate, attack, release);
+        Self {
+            uid,
+            num_channels,
+            followers: vec![follower; *num_channels],
+            handle: MeterHandle::new(num_channels),
+        }
+    }
+
+    /// Returns a handle that can be used to read meter levels from the main thread
+    pub fn handle(&self) -> MeterHandle {
+        self.handle.clone()
+    }
+
+    pub fn attack(&self) -> Duration {
+        self.followers
+            .first()
+            .map(EnvelopeFollower::attack)
+            .unwrap_or(Self::DEFAULT_ATTACK)
+    }
+
+    pub fn set_attack(&mut self, attack: Duration) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_attack(attack);
+        });
+        self
+    }
+
+    pub fn release(&self) -> Duration {
+        self.followers
+            .first()
+            .map(EnvelopeFollower::release)
+            .unwrap_or(Self::DEFAULT_RELEASE)
+    }
+
+    pub fn set_release(&mut self, release: Duration) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_release(release);
+        });
+        self
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        let sample_rate = sample_rate.into();
+        self.followers.iter_mut().for_each(|follower| {
+            follower.set_sample_rate(sample_rate);
+        });
+        self
+    }
+
+    /// Drops all current readings back to 0.0
+    pub fn reset(&mut self) -> &mut Self {
+        self.followers.iter_mut().for_each(|follower| {
+            follower.reset();
+        });
+        self.handle
+            .publish(self.followers.iter().map(|follower| follower.value().sqrt()));
+        self
+    }
+}
+
+impl Node for RmsMeterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let input = inputs
+            .next()
+            .expect("RmsMeterNode should have one and only one input connection");
+        let input_data = input.data();
+
+        self.handle.publish(
+            self.followers
+                .iter_mut()
+                .zip(input_data.iter())
+                .map(|(follower, sample)| follower.next_sample(sample * sample).sqrt()),
+        );
+
+        // it's possible for a meter to be the last node in a chain,
+        // so an outgoing connection is not required
+        if let Some(mut output) = outputs.next() {
+            output.update_data(|frame| frame.copy_from_slice(input_data));
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("RmsMeterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<RmsMeterNodeMessage>()?;
+
+        match message {
+            RmsMeterNodeMessage::SetAttack { attack } => {
+                self.set_attack(attack);
+            }
+            RmsMeterNodeMessage::SetRelease { release } => {
+                self.set_release(release);
+            }
+            RmsMeterNodeMessage::Reset => {
+                self.reset();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum RmsMeterNodeMessage {
+    SetAttack { attack: Duration },
+    SetRelease { release: Duration },
+    Reset,
+}
+
+impl PartialEq for RmsMeterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for RmsMeterNode {}
+
+impl PartialOrd for RmsMeterNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RmsMeterNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_rms_meter_node {
+    use std::{cell::RefCell, time::Duration};
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{Connection, Node, RmsMeterNode};
+
+    #[test]
+    fn should_pass_audio_through_and_publish_rms_levels() {
+        let mut meter_node =
+            RmsMeterNode::new_with_full_config(0, 2, 1000, Duration::ZERO, Duration::ZERO);
+        let handle = meter_node.handle();
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![-0.5, 0.25], 0, 0));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            meter_node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![-0.5, 0.25]);
+        assert_eq!(handle.levels(), vec![0.5, 0.25]);
+    }
+
+    #[test]
+    fn should_settle_on_rms_of_sine_wave() {
+        const SAMPLE_RATE: u32 = 1000;
+        let mut meter_node = RmsMeterNode::new_with_full_config(
+            0,
+            1,
+            SAMPLE_RATE,
+            Duration::from_millis(200),
+            Duration::from_millis(200),
+        );
+        let handle = meter_node.handle();
+
+        for i in 0..(SAMPLE_RATE * 5) {
+            let sample = (i as f32 / 10.0 * std::f32::consts::TAU).sin();
+            let input_connection =
+                RefCell::new(Connection::from_test_data(0, 1, vec![sample], 0, 0));
+            let inputs = [input_connection.borrow()];
+            meter_node.process(&mut inputs.into_iter(), &mut [].into_iter());
+        }
+
+        assert_difference_is_within_tolerance(
+            handle.level(0).unwrap(),
+            std::f32::consts::FRAC_1_SQRT_2,
+            0.01,
+        );
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, RmsMeterNodeMessage};
+
+        let mut meter_node = RmsMeterNode::new(1);
+
+        meter_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(RmsMeterNodeMessage::SetRelease {
+                    release: Duration::from_secs(1),
+                }),
+            })
+            .unwrap();
+
+        assert_eq!(meter_node.release(), Duration::from_secs(1));
+    }
+}
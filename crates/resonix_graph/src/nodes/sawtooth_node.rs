@@ -0,0 +1,250 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{NumChannels, OscillatorInterface, SampleRate, Sawtooth, SineInterface};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Outputs a band-limited sawtooth wave (see `resonix_core::Sawtooth`)
+///
+/// Output 0 - Sawtooth wave, duplicated across all channels
+#[derive(Debug, Clone)]
+pub struct SawtoothNode {
+    uid: NodeUid,
+    sawtooth: Sawtooth,
+    num_outgoing_channels: NumChannels,
+}
+
+impl SawtoothNode {
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, frequency: impl Into<f32>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_outgoing_channels, frequency)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_outgoing_channels, 0, frequency)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self {
+            uid,
+            num_outgoing_channels: num_outgoing_channels.into(),
+            sawtooth: Sawtooth::new_with_config(sample_rate, frequency),
+        }
+    }
+}
+
+impl SineInterface for SawtoothNode {
+    fn next_sample(&mut self) -> f32 {
+        self.sawtooth.next_sample()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.sawtooth.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.sawtooth.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.sawtooth.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.sawtooth.frequency()
+    }
+}
+
+impl OscillatorInterface for SawtoothNode {
+    fn phase(&self) -> f32 {
+        self.sawtooth.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.sawtooth.set_phase(phase);
+        self
+    }
+}
+
+impl Node for SawtoothNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let next_sample = self.next_sample();
+
+        outputs.into_iter().for_each(|mut output| {
+            output.update_data(|buffer| buffer.fill(next_sample));
+        });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("SawtoothNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.sawtooth.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<SawtoothNodeMessage>()?;
+
+        match message {
+            SawtoothNodeMessage::SetFrequency { new_frequency } => {
+                self.set_frequency(new_frequency);
+            }
+            SawtoothNodeMessage::ResetPhase => {
+                self.reset_phase();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum SawtoothNodeMessage {
+    SetFrequency { new_frequency: f32 },
+    ResetPhase,
+}
+
+impl PartialEq for SawtoothNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for SawtoothNode {}
+
+impl PartialOrd for SawtoothNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SawtoothNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_sawtooth_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, SawtoothNode};
+
+    #[test]
+    fn should_output_sawtooth_wave_data() {
+        let mut sawtooth_node = SawtoothNode::new_with_full_config(0, 2, 8, 1.0);
+        let output_connection = RefCell::new(Connection::from_test_data(1, 2, vec![0.0; 2], 0, 0));
+
+        let mut samples = Vec::new();
+        for _ in 0..3 {
+            {
+                let outputs = [output_connection.borrow_mut()];
+                sawtooth_node.process(&mut [].into_iter(), &mut outputs.into_iter());
+            }
+            samples.push(output_connection.borrow().data().to_vec());
+        }
+
+        assert_eq!(samples, vec![vec![0.0; 2], vec![0.25; 2], vec![0.5; 2]]);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use resonix_core::{OscillatorInterface, SineInterface};
+
+        use crate::{messages::UpdateNodeMessage, SawtoothNodeMessage};
+
+        let mut sawtooth_node = SawtoothNode::new_with_full_config(0, 1, 4, 1.0);
+        sawtooth_node.next_sample();
+
+        sawtooth_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(SawtoothNodeMessage::SetFrequency {
+                    new_frequency: 440.0,
+                }),
+            })
+            .unwrap();
+        sawtooth_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(SawtoothNodeMessage::ResetPhase),
+            })
+            .unwrap();
+
+        assert_eq!(sawtooth_node.frequency(), 440.0);
+        assert_eq!(sawtooth_node.phase(), 0.0);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+#[cfg(feature = "dac")]
+use crate::messages::{UpdateNodeError, UpdateNodeMessage};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Multiplies every sample of the incoming signal by `scale` and then adds `offset`.
+///
+/// Useful for mapping one range of values into another, such as
+/// mapping an oscillator's -1.0 to 1.0 output into a range of frequencies.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Scaled and offset signal
+#[derive(Debug, Clone)]
+pub struct ScaleOffsetNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    scale: f32,
+    offset: f32,
+}
+
+impl ScaleOffsetNode {
+    pub fn new(num_channels: impl Into<NumChannels>, scale: f32, offset: f32) -> Self {
+        Self::new_with_uid(0, num_channels, scale, offset)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        scale: f32,
+        offset: f32,
+    ) -> Self {
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+            scale,
+            offset,
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: f32) -> &mut Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: f32) -> &mut Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl Node for ScaleOffsetNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("ScaleOffsetNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        output
+            .data_mut()
+            .iter_mut()
+            .for_each(|sample| *sample = *sample * self.scale + self.offset);
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ScaleOffsetNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<ScaleOffsetNodeMessage>()?;
+
+        match message {
+            ScaleOffsetNodeMessage::SetScale { scale } => {
+                self.set_scale(scale);
+            }
+            ScaleOffsetNodeMessage::SetOffset { offset } => {
+                self.set_offset(offset);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum ScaleOffsetNodeMessage {
+    SetScale { scale: f32 },
+    SetOffset { offset: f32 },
+}
+
+impl PartialEq for ScaleOffsetNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for ScaleOffsetNode {}
+
+impl PartialOrd for ScaleOffsetNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScaleOffsetNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_scale_offset_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, ScaleOffsetNode};
+
+    #[test]
+    fn should_scale_and_offset_signal() {
+        // maps -1.0..1.0 to 200.0..600.0
+        let mut scale_offset_node = ScaleOffsetNode::new(3, 200.0, 400.0);
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 3, vec![-1.0, 0.0, 1.0], 0, 0));
+        let output_connection = RefCell::new(Connection::new(3));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            scale_offset_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(
+            output_connection.borrow().data(),
+            &vec![200.0, 400.0, 600.0]
+        );
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, ScaleOffsetNodeMessage};
+
+        let mut scale_offset_node = ScaleOffsetNode::new(1, 1.0, 0.0);
+
+        scale_offset_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(ScaleOffsetNodeMessage::SetOffset { offset: 0.5 }),
+            })
+            .unwrap();
+
+        assert_eq!(scale_offset_node.offset(), 0.5);
+    }
+}
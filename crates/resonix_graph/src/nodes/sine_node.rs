@@ -4,7 +4,7 @@ use std::{
 };
 
 use petgraph::prelude::EdgeIndex;
-use resonix_core::{NumChannels, SampleRate, Sine, SineInterface};
+use resonix_core::{NumChannels, OscillatorInterface, SampleRate, Sine, SineInterface};
 
 #[cfg(feature = "dac")]
 use {resonix_dac::DACConfig, std::sync::Arc};
@@ -75,6 +75,17 @@ impl SineInterface for SineNode {
     }
 }
 
+impl OscillatorInterface for SineNode {
+    fn phase(&self) -> f32 {
+        self.sine.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.sine.set_phase(phase);
+        self
+    }
+}
+
 impl Node for SineNode {
     #[inline]
     fn process(
@@ -149,6 +160,9 @@ impl Node for SineNode {
             SineNodeMessage::SetFrequency { new_frequency } => {
                 self.set_frequency(new_frequency);
             }
+            SineNodeMessage::ResetPhase => {
+                self.reset_phase();
+            }
         }
 
         Ok(())
@@ -157,6 +171,7 @@ impl Node for SineNode {
 
 pub enum SineNodeMessage {
     SetFrequency { new_frequency: f32 },
+    ResetPhase,
 }
 
 impl PartialEq for SineNode {
@@ -0,0 +1,277 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SplitterNodeError {
+    #[error("SplitterNode requires at least one channel group")]
+    NoChannelGroups,
+    #[error("Channel group {group_index:?} is empty, but every channel group must contain at least one channel")]
+    EmptyChannelGroup { group_index: usize },
+    #[error("Channel group {group_index:?} has {num_channels:?} channels, but every channel group must have the same number of channels as the first group ({expected_num_channels:?})")]
+    MismatchedChannelGroupSize {
+        group_index: usize,
+        num_channels: usize,
+        expected_num_channels: usize,
+    },
+    #[error("Channel group {group_index:?} contains channel {channel:?}, but the incoming signal only has {num_incoming_channels:?} channels")]
+    ChannelOutOfRange {
+        group_index: usize,
+        channel: usize,
+        num_incoming_channels: usize,
+    },
+}
+
+/// Takes one connection with many channels and splits it into many
+/// narrower connections (the inverse of `MulticoreNode`).
+///
+/// By default, every channel gets its own single-channel output, so that
+/// output N (i.e. connections made with a `from_index` of N) carries channel N.
+///
+/// Alternatively, each output can carry an arbitrary group of channels (see
+/// `new_with_channel_groups`). Every group must have the same number of channels,
+/// since all outgoing connections of a node share the same number of channels.
+///
+/// Input 0 - Multichannel signal
+///
+/// Output 0..n - Channel (or group of channels) n
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct SplitterNode {
+    uid: NodeUid,
+    num_incoming_channels: NumChannels,
+    /// The incoming channels that are copied into each output (in order)
+    channel_groups: Vec<Vec<usize>>,
+}
+
+impl SplitterNode {
+    /// Splits every incoming channel into its own single-channel output
+    pub fn new(num_incoming_channels: impl Into<NumChannels>) -> Self {
+        let num_incoming_channels = num_incoming_channels.into();
+        Self {
+            uid: 0,
+            num_incoming_channels,
+            channel_groups: (0..*num_incoming_channels)
+                .map(|channel| vec![channel])
+                .collect(),
+        }
+    }
+
+    /// Copies each group of incoming channels into its own output,
+    /// where output N carries `channel_groups[N]`.
+    ///
+    /// Channels can be reordered, repeated, or left out entirely.
+    pub fn new_with_channel_groups(
+        num_incoming_channels: impl Into<NumChannels>,
+        channel_groups: Vec<Vec<usize>>,
+    ) -> Result<Self, SplitterNodeError> {
+        let num_incoming_channels = num_incoming_channels.into();
+        let expected_num_channels = channel_groups
+            .first()
+            .ok_or(SplitterNodeError::NoChannelGroups)?
+            .len();
+
+        for (group_index, channel_group) in channel_groups.iter().enumerate() {
+            if channel_group.is_empty() {
+                return Err(SplitterNodeError::EmptyChannelGroup { group_index });
+            }
+
+            if channel_group.len() != expected_num_channels {
+                return Err(SplitterNodeError::MismatchedChannelGroupSize {
+                    group_index,
+                    num_channels: channel_group.len(),
+                    expected_num_channels,
+                });
+            }
+
+            if let Some(&channel) = channel_group
+                .iter()
+                .find(|&&channel| channel >= *num_incoming_channels)
+            {
+                return Err(SplitterNodeError::ChannelOutOfRange {
+                    group_index,
+                    channel,
+                    num_incoming_channels: *num_incoming_channels,
+                });
+            }
+        }
+
+        Ok(Self {
+            uid: 0,
+            num_incoming_channels,
+            channel_groups,
+        })
+    }
+
+    /// The incoming channels that are copied into each output
+    pub fn channel_groups(&self) -> &[Vec<usize>] {
+        &self.channel_groups
+    }
+}
+
+impl Node for SplitterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let input = inputs.next();
+
+        for mut output in outputs {
+            let Some(channel_group) = self.channel_groups.get(output.from_index()) else {
+                continue;
+            };
+
+            match &input {
+                Some(input) => output
+                    .data_mut()
+                    .iter_mut()
+                    .zip(channel_group.iter())
+                    .for_each(|(output_sample, &channel)| {
+                        *output_sample = input.data()[channel];
+                    }),
+                None => output.data_mut().fill(0.0),
+            }
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        self.channel_groups.len()
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_incoming_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        NumChannels::from(self.channel_groups.first().map_or(0, Vec::len))
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("SplitterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_splitter_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, SplitterNode, SplitterNodeError};
+
+    #[test]
+    fn should_split_every_channel_into_its_own_output() {
+        let mut splitter_node = SplitterNode::new(3);
+
+        let input_connection =
+            RefCell::new(Connection::from_test_data(0, 3, vec![0.1, 0.2, 0.3], 0, 0));
+        let output_connections: Vec<_> = [2, 0, 1]
+            .into_iter()
+            .map(|from_index| {
+                RefCell::new(Connection::from_test_data(
+                    from_index as u32 + 1,
+                    1,
+                    vec![0.0],
+                    from_index,
+                    0,
+                ))
+            })
+            .collect();
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = output_connections.iter().map(|c| c.borrow_mut());
+            splitter_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        let output_data: Vec<_> = output_connections
+            .iter()
+            .map(|c| c.borrow().data().to_vec())
+            .collect();
+        assert_eq!(output_data, vec![vec![0.3], vec![0.1], vec![0.2]]);
+        assert_eq!(splitter_node.num_output_connections(), 3);
+        assert_eq!(*splitter_node.num_outgoing_channels(), 1);
+    }
+
+    #[test]
+    fn should_split_channel_groups() {
+        let mut splitter_node =
+            SplitterNode::new_with_channel_groups(4, vec![vec![3, 0], vec![1, 1]]).unwrap();
+
+        let input_connection = RefCell::new(Connection::from_test_data(
+            0,
+            4,
+            vec![0.1, 0.2, 0.3, 0.4],
+            0,
+            0,
+        ));
+        let first_output_connection =
+            RefCell::new(Connection::from_test_data(1, 2, vec![0.0; 2], 0, 0));
+        let second_output_connection =
+            RefCell::new(Connection::from_test_data(2, 2, vec![0.0; 2], 1, 0));
+
+        {
+            let inputs = [input_connection.borrow()];
+            let outputs = [
+                first_output_connection.borrow_mut(),
+                second_output_connection.borrow_mut(),
+            ];
+            splitter_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(first_output_connection.borrow().data(), &vec![0.4, 0.1]);
+        assert_eq!(second_output_connection.borrow().data(), &vec![0.2, 0.2]);
+    }
+
+    #[test]
+    fn should_reject_invalid_channel_groups() {
+        assert_eq!(
+            SplitterNode::new_with_channel_groups(4, vec![]).unwrap_err(),
+            SplitterNodeError::NoChannelGroups
+        );
+        assert_eq!(
+            SplitterNode::new_with_channel_groups(4, vec![vec![0, 1], vec![2]]).unwrap_err(),
+            SplitterNodeError::MismatchedChannelGroupSize {
+                group_index: 1,
+                num_channels: 1,
+                expected_num_channels: 2
+            }
+        );
+        assert_eq!(
+            SplitterNode::new_with_channel_groups(4, vec![vec![0], vec![4]]).unwrap_err(),
+            SplitterNodeError::ChannelOutOfRange {
+                group_index: 1,
+                channel: 4,
+                num_incoming_channels: 4
+            }
+        );
+    }
+}
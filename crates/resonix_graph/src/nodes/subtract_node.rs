@@ -0,0 +1,133 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Takes two signals and subtracts the second signal from the first
+///
+/// Input 0 - Signal to subtract from
+/// Input 1 - Signal to subtract
+///
+/// Output 0 - Difference of the signals
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct SubtractNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+}
+
+impl SubtractNode {
+    pub const MINUEND_INPUT_INDEX: usize = 0;
+    pub const SUBTRAHEND_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>) -> Self {
+        Self::new_with_uid(0, num_channels)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>) -> Self {
+        Self {
+            uid,
+            num_channels: num_channels.into(),
+        }
+    }
+}
+
+impl Node for SubtractNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("SubtractNode should have one and only one output connection");
+
+        output.data_mut().fill(0.0);
+        for input in inputs {
+            // the input order is not guaranteed, so check which side of the subtraction this is
+            let sign = match input.to_index() {
+                Self::SUBTRAHEND_INPUT_INDEX => -1.0,
+                _ => 1.0,
+            };
+            output
+                .data_mut()
+                .iter_mut()
+                .zip(input.data().iter())
+                .for_each(|(output_sample, input_sample)| *output_sample += sign * input_sample);
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("SubtractNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test_subtract_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, SubtractNode};
+
+    #[test]
+    fn should_subtract_2nd_input_from_1st_input() {
+        let mut subtract_node = SubtractNode::new(2);
+
+        let left_input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![0.5, 1.0], 0, 0));
+        let right_input_connection =
+            RefCell::new(Connection::from_test_data(1, 2, vec![0.25, -2.0], 0, 1));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        // inputs are given out of order
+        {
+            let inputs = [
+                right_input_connection.borrow(),
+                left_input_connection.borrow(),
+            ];
+            let outputs = [output_connection.borrow_mut()];
+            subtract_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.25, 3.0]);
+    }
+}
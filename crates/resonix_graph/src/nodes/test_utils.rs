@@ -0,0 +1,31 @@
+use std::cell::RefCell;
+
+use crate::{Connection, Node};
+
+/// Runs `node` for a single frame and returns the frame written to its output.
+///
+/// Each `(input_index, frame)` in `inputs` is connected to that input of the node,
+/// so leaving an input out of `inputs` leaves it disconnected.
+pub(crate) fn process_frame(node: &mut impl Node, inputs: &[(usize, &[f32])]) -> Vec<f32> {
+    let input_connections: Vec<_> = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, (input_index, frame))| {
+            RefCell::new(Connection::from_test_data(
+                i as u32,
+                frame.len(),
+                frame.to_vec(),
+                0,
+                *input_index,
+            ))
+        })
+        .collect();
+    let output_connection = RefCell::new(Connection::new(node.num_outgoing_channels()));
+    {
+        let inputs = input_connections.iter().map(RefCell::borrow);
+        let outputs = [output_connection.borrow_mut()];
+        node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+    }
+    let output = output_connection.borrow().data().to_vec();
+    output
+}
@@ -0,0 +1,225 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{NumChannels, OscillatorInterface, SampleRate, SineInterface, Triangle};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+    std::sync::Arc,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Outputs a band-limited triangle wave (see `resonix_core::Triangle`)
+///
+/// Output 0 - Triangle wave, duplicated across all channels
+#[derive(Debug, Clone)]
+pub struct TriangleNode {
+    uid: NodeUid,
+    triangle: Triangle,
+    num_outgoing_channels: NumChannels,
+}
+
+impl TriangleNode {
+    pub fn new(num_outgoing_channels: impl Into<NumChannels>, frequency: impl Into<f32>) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_outgoing_channels, frequency)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_outgoing_channels, 0, frequency)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        frequency: impl Into<f32>,
+    ) -> Self {
+        Self {
+            uid,
+            num_outgoing_channels: num_outgoing_channels.into(),
+            triangle: Triangle::new_with_config(sample_rate, frequency),
+        }
+    }
+}
+
+impl SineInterface for TriangleNode {
+    fn next_sample(&mut self) -> f32 {
+        self.triangle.next_sample()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.triangle.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.triangle.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.triangle.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.triangle.frequency()
+    }
+}
+
+impl OscillatorInterface for TriangleNode {
+    fn phase(&self) -> f32 {
+        self.triangle.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.triangle.set_phase(phase);
+        self
+    }
+}
+
+impl Node for TriangleNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let next_sample = self.next_sample();
+
+        outputs.into_iter().for_each(|mut output| {
+            output.update_data(|buffer| buffer.fill(next_sample));
+        });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("TriangleNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.triangle.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<TriangleNodeMessage>()?;
+
+        match message {
+            TriangleNodeMessage::SetFrequency { new_frequency } => {
+                self.set_frequency(new_frequency);
+            }
+            TriangleNodeMessage::ResetPhase => {
+                self.reset_phase();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum TriangleNodeMessage {
+    SetFrequency { new_frequency: f32 },
+    ResetPhase,
+}
+
+impl PartialEq for TriangleNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for TriangleNode {}
+
+impl PartialOrd for TriangleNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TriangleNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_triangle_node {
+    use std::cell::RefCell;
+
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{Connection, Node, TriangleNode};
+
+    #[test]
+    fn should_output_triangle_wave_data() {
+        let mut triangle_node = TriangleNode::new_with_full_config(0, 1, 16, 1.0);
+        let output_connection = RefCell::new(Connection::default());
+
+        let mut samples = Vec::new();
+        for _ in 0..3 {
+            {
+                let outputs = [output_connection.borrow_mut()];
+                triangle_node.process(&mut [].into_iter(), &mut outputs.into_iter());
+            }
+            samples.push(output_connection.borrow().data()[0]);
+        }
+
+        assert_difference_is_within_tolerance(samples[0], 0.0, 0.0001);
+        assert_difference_is_within_tolerance(samples[1], 0.25, 0.0001);
+        assert_difference_is_within_tolerance(samples[2], 0.5, 0.0001);
+    }
+}
@@ -0,0 +1,500 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    fs::File,
+    io::BufWriter,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, Weak,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use hound::{SampleFormat, WavSpec, WavWriter};
+use resonix_core::{NumChannels, SampleQueue, SampleRate};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+#[derive(thiserror::Error, Debug)]
+pub enum WavWriterError {
+    #[error("Error occurred while writing WAV file. Original error: {0:?}")]
+    Hound(#[from] hound::Error),
+    #[error("WAV writer thread could not be spawned. Original error: {0:?}")]
+    Spawn(#[from] std::io::Error),
+    #[error("WAV writer thread panicked before the file could be finalized")]
+    WriterThreadPanicked,
+    #[error("WAV writer has already been stopped")]
+    AlreadyStopped,
+}
+
+/// The format of each sample written to the WAV file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WavSampleFormat {
+    Int16,
+    Int24,
+    #[default]
+    Float32,
+}
+
+impl WavSampleFormat {
+    fn spec(&self, num_channels: NumChannels, sample_rate: SampleRate) -> WavSpec {
+        let (bits_per_sample, sample_format) = match self {
+            WavSampleFormat::Int16 => (16, SampleFormat::Int),
+            WavSampleFormat::Int24 => (24, SampleFormat::Int),
+            WavSampleFormat::Float32 => (32, SampleFormat::Float),
+        };
+
+        WavSpec {
+            channels: *num_channels as u16,
+            sample_rate: sample_rate.get(),
+            bits_per_sample,
+            sample_format,
+        }
+    }
+}
+
+/// State shared between the `WavWriterNode`, the background writer thread,
+/// and any `WavWriterHandle`s.
+#[derive(Debug)]
+struct WavWriterState {
+    stop_requested: AtomicBool,
+    frames_dropped: AtomicUsize,
+    frames_written: AtomicUsize,
+    writer_thread: Mutex<Option<JoinHandle<Result<(), WavWriterError>>>>,
+}
+
+/// The pushing end of the `SampleQueue`.
+///
+/// `SampleQueue` only supports one thread pushing at a time, but every `Node`
+/// must be `Clone`, so copies of a `WavWriterNode` share one `QueueProducer`
+/// and claim it before pushing. A copy that can't claim it drops its frame.
+#[derive(Debug)]
+struct QueueProducer {
+    queue: SampleQueue,
+    is_claimed: AtomicBool,
+}
+
+impl QueueProducer {
+    /// Pushes all of `samples` if they fit in the queue.
+    ///
+    /// Returns `false` if they didn't fit, or if another copy of the node was pushing.
+    fn try_push_frame(&self, samples: &[f32]) -> bool {
+        if self.is_claimed.swap(true, Ordering::Acquire) {
+            return false;
+        }
+
+        // only push whole frames, so that channels never get out of alignment
+        let did_push = self.queue.free_len() >= samples.len();
+        if did_push {
+            self.queue.push_slice(samples);
+        }
+
+        self.is_claimed.store(false, Ordering::Release);
+        did_push
+    }
+}
+
+/// Streams incoming audio to a WAV file on disk.
+///
+/// Frames are pushed into a lock-free queue on the audio thread and
+/// written to disk by a background thread, so the audio thread never
+/// blocks on file IO. If the background thread falls behind and the queue
+/// fills up, incoming frames are dropped (see `WavWriterHandle::frames_dropped`).
+///
+/// The WAV header is finalized once `WavWriterHandle::stop` is called
+/// (or once every copy of the node has been dropped).
+///
+/// Copies of the node write to the same file, but only one copy
+/// can push a frame at a time (any others drop their frames).
+///
+/// Input 0 - Signal to write
+#[derive(Debug, Clone)]
+pub struct WavWriterNode {
+    uid: NodeUid,
+    num_channels: NumChannels,
+    producer: Arc<QueueProducer>,
+    state: Arc<WavWriterState>,
+    /// Lets the writer thread know when every copy of the node has been dropped
+    _alive: Arc<()>,
+}
+
+impl WavWriterNode {
+    /// How much audio can be queued up before frames start being dropped
+    pub const QUEUE_DURATION: Duration = Duration::from_secs(1);
+
+    /// How long the background thread waits between checks for more audio
+    const WRITER_THREAD_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+    /// Creates the WAV file at `path` and starts the background writer thread.
+    ///
+    /// `sample_rate` should match the sample rate of the audio being written
+    /// (e.g. the sample rate of the `AudioContext`).
+    pub fn new(
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        sample_format: WavSampleFormat,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, WavWriterError> {
+        Self::new_with_uid(0, num_channels, sample_rate, sample_format, path)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        sample_format: WavSampleFormat,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, WavWriterError> {
+        let num_channels = num_channels.into();
+        let sample_rate = sample_rate.into();
+        let writer = WavWriter::create(path, sample_format.spec(num_channels, sample_rate))?;
+
+        let queue_capacity = (Self::QUEUE_DURATION.as_secs_f32() * sample_rate.get() as f32).ceil()
+            as usize
+            * *num_channels;
+        let queue = SampleQueue::new(queue_capacity.max(*num_channels));
+        let state = Arc::new(WavWriterState {
+            stop_requested: AtomicBool::new(false),
+            frames_dropped: AtomicUsize::new(0),
+            frames_written: AtomicUsize::new(0),
+            writer_thread: Mutex::new(None),
+        });
+        let alive = Arc::new(());
+
+        let writer_thread = {
+            let queue = queue.clone();
+            let state = Arc::clone(&state);
+            let alive = Arc::downgrade(&alive);
+            std::thread::Builder::new()
+                .name(String::from("resonix_wav_writer"))
+                .spawn(move || {
+                    Self::run_writer_thread(
+                        writer,
+                        sample_format,
+                        num_channels,
+                        queue,
+                        state,
+                        alive,
+                    )
+                })?
+        };
+        *state.writer_thread.lock().unwrap() = Some(writer_thread);
+
+        Ok(Self {
+            uid,
+            num_channels,
+            producer: Arc::new(QueueProducer {
+                queue,
+                is_claimed: AtomicBool::new(false),
+            }),
+            state,
+            _alive: alive,
+        })
+    }
+
+    /// Returns a handle that can be used to stop writing and finalize the file
+    pub fn handle(&self) -> WavWriterHandle {
+        WavWriterHandle {
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    fn run_writer_thread(
+        mut writer: WavWriter<BufWriter<File>>,
+        sample_format: WavSampleFormat,
+        num_channels: NumChannels,
+        queue: SampleQueue,
+        state: Arc<WavWriterState>,
+        alive: Weak<()>,
+    ) -> Result<(), WavWriterError> {
+        let mut buffer = vec![0.0; queue.capacity()];
+
+        loop {
+            // check whether to stop *before* draining, so that no
+            // frames pushed before the stop request are missed
+            let should_stop =
+                state.stop_requested.load(Ordering::Acquire) || alive.strong_count() == 0;
+
+            let num_samples = queue.pop_into(&mut buffer);
+            for sample in &buffer[..num_samples] {
+                Self::write_sample(&mut writer, sample_format, *sample)?;
+            }
+            state
+                .frames_written
+                .fetch_add(num_samples / (*num_channels).max(1), Ordering::AcqRel);
+
+            if num_samples == 0 {
+                if should_stop {
+                    break;
+                }
+                std::thread::sleep(Self::WRITER_THREAD_POLL_INTERVAL);
+            }
+        }
+
+        writer.finalize()?;
+
+        Ok(())
+    }
+
+    fn write_sample(
+        writer: &mut WavWriter<BufWriter<File>>,
+        sample_format: WavSampleFormat,
+        sample: f32,
+    ) -> Result<(), hound::Error> {
+        const I24_MAX: f32 = 8_388_607.0;
+
+        match sample_format {
+            WavSampleFormat::Int16 => {
+                writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            }
+            WavSampleFormat::Int24 => {
+                writer.write_sample((sample.clamp(-1.0, 1.0) * I24_MAX) as i32)
+            }
+            WavSampleFormat::Float32 => writer.write_sample(sample),
+        }
+    }
+}
+
+impl Node for WavWriterNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        _: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let input = inputs
+            .next()
+            .expect("WavWriterNode should have one and only one input connection");
+        let input_data = input.data();
+
+        if self.state.stop_requested.load(Ordering::Relaxed) {
+            return;
+        }
+
+        if !self.producer.try_push_frame(input_data) {
+            self.state.frames_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Output
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        0
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("WavWriterNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl PartialEq for WavWriterNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for WavWriterNode {}
+
+impl PartialOrd for WavWriterNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WavWriterNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+/// The `WavWriterHandle` allows stopping a `WavWriterNode` from the main thread,
+/// even after that node has been sent to the audio thread.
+///
+/// This struct can be safely and cheaply cloned
+#[derive(Debug, Clone)]
+pub struct WavWriterHandle {
+    state: Arc<WavWriterState>,
+}
+
+impl WavWriterHandle {
+    /// Stops writing new audio, waits for any queued audio to be
+    /// written to disk, and finalizes the WAV file's header.
+    ///
+    /// Returns an error if writing failed at any point,
+    /// or if the writer has already been stopped.
+    pub fn stop(&self) -> Result<(), WavWriterError> {
+        self.state.stop_requested.store(true, Ordering::Release);
+
+        let writer_thread = self
+            .state
+            .writer_thread
+            .lock()
+            .map_err(|_| WavWriterError::WriterThreadPanicked)?
+            .take()
+            .ok_or(WavWriterError::AlreadyStopped)?;
+
+        writer_thread
+            .join()
+            .map_err(|_| WavWriterError::WriterThreadPanicked)?
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state.stop_requested.load(Ordering::Acquire)
+    }
+
+    /// Number of frames that have been written to disk so far
+    pub fn frames_written(&self) -> usize {
+        self.state.frames_written.load(Ordering::Acquire)
+    }
+
+    /// Number of frames that were dropped because the writer thread fell behind
+    /// (or because another copy of the node was pushing at the same time)
+    pub fn frames_dropped(&self) -> usize {
+        self.state.frames_dropped.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod test_wav_writer_node {
+    use std::{cell::RefCell, path::PathBuf, sync::atomic::Ordering};
+
+    use crate::{Connection, Node, WavSampleFormat, WavWriterNode};
+
+    fn temp_wav_path() -> PathBuf {
+        std::env::temp_dir().join(format!("resonix_test_{}.wav", uuid::Uuid::new_v4()))
+    }
+
+    fn write_frames(node: &mut WavWriterNode, frames: &[Vec<f32>]) {
+        for frame in frames {
+            let input_connection = RefCell::new(Connection::from_test_data(
+                0,
+                frame.len(),
+                frame.clone(),
+                0,
+                0,
+            ));
+            let inputs = [input_connection.borrow()];
+            let outputs = [];
+            node.process(&mut inputs.into_iter(), &mut outputs.into_iter());
+        }
+    }
+
+    #[test]
+    fn should_write_float_wav_file() {
+        let path = temp_wav_path();
+        let mut node = WavWriterNode::new(2, 44100, WavSampleFormat::Float32, &path).unwrap();
+        let handle = node.handle();
+
+        write_frames(&mut node, &[vec![0.5, -0.5], vec![0.25, -0.25]]);
+        handle.stop().unwrap();
+
+        // frames received after stopping are ignored
+        write_frames(&mut node, &[vec![1.0, 1.0]]);
+        assert_eq!(handle.frames_written(), 2);
+        assert_eq!(handle.frames_dropped(), 0);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.sample_rate, 44100);
+        assert_eq!(spec.bits_per_sample, 32);
+
+        let samples: Vec<f32> = reader.samples::<f32>().map(Result::unwrap).collect();
+        assert_eq!(samples, vec![0.5, -0.5, 0.25, -0.25]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_write_integer_wav_files() {
+        for (sample_format, bits_per_sample, max) in [
+            (WavSampleFormat::Int16, 16, i16::MAX as i32),
+            (WavSampleFormat::Int24, 24, 8_388_607),
+        ] {
+            let path = temp_wav_path();
+            let mut node = WavWriterNode::new(3, 48000, sample_format, &path).unwrap();
+
+            write_frames(&mut node, &[vec![1.0, 0.0, -1.0], vec![2.0, 0.5, -2.0]]);
+            node.handle().stop().unwrap();
+
+            let mut reader = hound::WavReader::open(&path).unwrap();
+            assert_eq!(reader.spec().channels, 3);
+            assert_eq!(reader.spec().bits_per_sample, bits_per_sample);
+
+            let samples: Vec<i32> = reader.samples::<i32>().map(Result::unwrap).collect();
+            // out-of-range samples are clipped
+            assert_eq!(samples, vec![max, 0, -max, max, max / 2, -max]);
+
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn should_drop_frames_while_another_copy_is_pushing() {
+        let path = temp_wav_path();
+        let mut node = WavWriterNode::new(1, 44100, WavSampleFormat::Float32, &path).unwrap();
+        let mut copy = node.clone();
+        let handle = node.handle();
+
+        write_frames(&mut node, &[vec![0.5]]);
+        // simulate the original node being in the middle of a push
+        node.producer.is_claimed.store(true, Ordering::Release);
+        write_frames(&mut copy, &[vec![0.25]]);
+        node.producer.is_claimed.store(false, Ordering::Release);
+        write_frames(&mut copy, &[vec![-0.5]]);
+        handle.stop().unwrap();
+
+        assert_eq!(handle.frames_written(), 2);
+        assert_eq!(handle.frames_dropped(), 1);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        let samples: Vec<f32> = reader.samples::<f32>().map(Result::unwrap).collect();
+        assert_eq!(samples, vec![0.5, -0.5]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_error_when_stopped_twice() {
+        let path = temp_wav_path();
+        let node = WavWriterNode::new(1, 44100, WavSampleFormat::Int16, &path).unwrap();
+        let handle = node.handle();
+
+        assert!(handle.stop().is_ok());
+        assert!(handle.stop().is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
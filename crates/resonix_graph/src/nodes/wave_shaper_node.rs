@@ -0,0 +1,241 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+};
+
+use resonix_core::{NumChannels, Oversampling, ShapingCurve, WaveShaper};
+
+#[cfg(feature = "dac")]
+use crate::messages::{UpdateNodeError, UpdateNodeMessage};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Distorts the incoming signal by passing it through a `ShapingCurve`
+/// (e.g. tanh saturation, hard clipping, foldback, bitcrushing, or a custom curve).
+///
+/// See `WaveShaper` for how drive, sample rate reduction, and oversampling are applied.
+///
+/// Input 0 - Signal
+///
+/// Output 0 - Shaped signal
+#[derive(Debug, Clone)]
+pub struct WaveShaperNode {
+    uid: NodeUid,
+    wave_shaper: WaveShaper,
+}
+
+impl WaveShaperNode {
+    pub fn new(num_channels: impl Into<NumChannels>, curve: ShapingCurve) -> Self {
+        Self::new_with_uid(0, num_channels, curve)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_channels: impl Into<NumChannels>,
+        curve: ShapingCurve,
+    ) -> Self {
+        Self {
+            uid,
+            wave_shaper: WaveShaper::new(num_channels, curve),
+        }
+    }
+
+    pub fn curve(&self) -> &ShapingCurve {
+        self.wave_shaper.curve()
+    }
+
+    pub fn set_curve(&mut self, curve: ShapingCurve) -> &mut Self {
+        self.wave_shaper.set_curve(curve);
+        self
+    }
+
+    /// Linear gain applied to the signal before it is shaped
+    pub fn drive(&self) -> f32 {
+        self.wave_shaper.drive()
+    }
+
+    pub fn set_drive(&mut self, drive: f32) -> &mut Self {
+        self.wave_shaper.set_drive(drive);
+        self
+    }
+
+    pub fn oversampling(&self) -> Oversampling {
+        self.wave_shaper.oversampling()
+    }
+
+    pub fn set_oversampling(&mut self, oversampling: Oversampling) -> &mut Self {
+        self.wave_shaper.set_oversampling(oversampling);
+        self
+    }
+
+    /// Number of samples that each held input sample lasts for (1 means no reduction)
+    pub fn sample_rate_reduction(&self) -> usize {
+        self.wave_shaper.sample_rate_reduction()
+    }
+
+    pub fn set_sample_rate_reduction(&mut self, sample_rate_reduction: usize) -> &mut Self {
+        self.wave_shaper
+            .set_sample_rate_reduction(sample_rate_reduction);
+        self
+    }
+}
+
+impl Node for WaveShaperNode {
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut output = outputs
+            .next()
+            .expect("WaveShaperNode should have one and only one output connection");
+
+        match inputs.next() {
+            Some(input) => output.data_mut().copy_from_slice(input.data()),
+            None => output.data_mut().fill(0.0),
+        }
+
+        self.wave_shaper.process_frame(output.data_mut());
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        1
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.wave_shaper.num_channels()
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.wave_shaper.num_channels()
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("WaveShaperNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<WaveShaperNodeMessage>()?;
+
+        match message {
+            WaveShaperNodeMessage::SetCurve { curve } => {
+                self.set_curve(curve);
+            }
+            WaveShaperNodeMessage::SetDrive { drive } => {
+                self.set_drive(drive);
+            }
+            WaveShaperNodeMessage::SetOversampling { oversampling } => {
+                self.set_oversampling(oversampling);
+            }
+            WaveShaperNodeMessage::SetSampleRateReduction {
+                sample_rate_reduction,
+            } => {
+                self.set_sample_rate_reduction(sample_rate_reduction);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum WaveShaperNodeMessage {
+    SetCurve { curve: ShapingCurve },
+    SetDrive { drive: f32 },
+    SetOversampling { oversampling: Oversampling },
+    SetSampleRateReduction { sample_rate_reduction: usize },
+}
+
+impl PartialEq for WaveShaperNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for WaveShaperNode {}
+
+impl PartialOrd for WaveShaperNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WaveShaperNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_wave_shaper_node {
+    use resonix_core::ShapingCurve;
+
+    use crate::{nodes::test_utils::process_frame, WaveShaperNode};
+
+    fn render(node: &mut WaveShaperNode, input: Vec<f32>) -> Vec<f32> {
+        process_frame(node, &[(0, &input)])
+    }
+
+    #[test]
+    fn should_shape_every_channel() {
+        let mut node = WaveShaperNode::new(3, ShapingCurve::Foldback);
+
+        assert_eq!(render(&mut node, vec![0.5, 1.5, -2.0]), vec![0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn should_apply_drive() {
+        let mut node = WaveShaperNode::new(2, ShapingCurve::HardClip);
+        node.set_drive(10.0);
+
+        assert_eq!(render(&mut node, vec![0.05, -0.2]), vec![0.5, -1.0]);
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use resonix_core::Oversampling;
+
+        use crate::{messages::UpdateNodeMessage, Node, WaveShaperNodeMessage};
+
+        let mut node = WaveShaperNode::new(1, ShapingCurve::SoftClip);
+
+        node.handle_update_node_message(UpdateNodeMessage {
+            node_uid: 0,
+            data: Box::new(WaveShaperNodeMessage::SetOversampling {
+                oversampling: Oversampling::X2,
+            }),
+        })
+        .unwrap();
+
+        assert_eq!(node.oversampling(), Oversampling::X2);
+    }
+}
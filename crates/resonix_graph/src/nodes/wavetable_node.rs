@@ -0,0 +1,291 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    sync::Arc,
+};
+
+use resonix_core::{
+    NumChannels, OscillatorInterface, SampleRate, SineInterface, Wavetable, WavetableOscillator,
+};
+
+#[cfg(feature = "dac")]
+use {
+    crate::messages::{UpdateNodeError, UpdateNodeMessage},
+    resonix_dac::DACConfig,
+};
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Plays back one or more single-cycle wavetables, morphing between them
+/// (see `resonix_core::WavetableOscillator`).
+///
+/// Output 0 - Wavetable audio, duplicated across all channels
+#[derive(Debug, Clone)]
+pub struct WavetableNode {
+    uid: NodeUid,
+    oscillator: WavetableOscillator,
+    num_outgoing_channels: NumChannels,
+}
+
+impl WavetableNode {
+    pub fn new(
+        num_outgoing_channels: impl Into<NumChannels>,
+        frequency: impl Into<f32>,
+        tables: Vec<Arc<Wavetable>>,
+    ) -> Self {
+        // sample_rate is automatically configured in the audio thread when "dac" feature is enabled
+        Self::new_with_uid(0, num_outgoing_channels, frequency, tables)
+    }
+
+    pub(crate) fn new_with_uid(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        frequency: impl Into<f32>,
+        tables: Vec<Arc<Wavetable>>,
+    ) -> Self {
+        Self::new_with_full_config(uid, num_outgoing_channels, 0, frequency, tables)
+    }
+
+    pub(crate) fn new_with_full_config(
+        uid: NodeUid,
+        num_outgoing_channels: impl Into<NumChannels>,
+        sample_rate: impl Into<SampleRate>,
+        frequency: impl Into<f32>,
+        tables: Vec<Arc<Wavetable>>,
+    ) -> Self {
+        Self {
+            uid,
+            num_outgoing_channels: num_outgoing_channels.into(),
+            oscillator: WavetableOscillator::new_with_config(sample_rate, frequency, tables),
+        }
+    }
+
+    pub fn tables(&self) -> &[Arc<Wavetable>] {
+        self.oscillator.tables()
+    }
+
+    pub fn set_tables(&mut self, tables: Vec<Arc<Wavetable>>) -> &mut Self {
+        self.oscillator.set_tables(tables);
+        self
+    }
+
+    pub fn morph(&self) -> f32 {
+        self.oscillator.morph()
+    }
+
+    /// Position (from 0.0 to 1.0) between the first and last tables
+    pub fn set_morph(&mut self, morph: f32) -> &mut Self {
+        self.oscillator.set_morph(morph);
+        self
+    }
+}
+
+impl SineInterface for WavetableNode {
+    fn next_sample(&mut self) -> f32 {
+        self.oscillator.next_sample()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: impl Into<SampleRate>) -> &mut Self {
+        self.oscillator.set_sample_rate(sample_rate);
+        self
+    }
+
+    fn set_frequency(&mut self, frequency: f32) -> &mut Self {
+        self.oscillator.set_frequency(frequency);
+        self
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.oscillator.sample_rate()
+    }
+
+    fn frequency(&self) -> f32 {
+        self.oscillator.frequency()
+    }
+}
+
+impl OscillatorInterface for WavetableNode {
+    fn phase(&self) -> f32 {
+        self.oscillator.phase()
+    }
+
+    fn set_phase(&mut self, phase: f32) -> &mut Self {
+        self.oscillator.set_phase(phase);
+        self
+    }
+}
+
+impl Node for WavetableNode {
+    #[inline]
+    fn process(
+        &mut self,
+        _inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let next_sample = self.next_sample();
+
+        outputs.into_iter().for_each(|mut output| {
+            output.update_data(|buffer| buffer.fill(next_sample));
+        });
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Input
+    }
+
+    fn num_input_connections(&self) -> usize {
+        0
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        NumChannels::from(0)
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_outgoing_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("WavetableNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[cfg(feature = "dac")]
+    fn requires_audio_updates(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "dac")]
+    fn update_from_dac_config(&mut self, dac_config: Arc<DACConfig>) {
+        self.oscillator.set_sample_rate(dac_config.sample_rate());
+    }
+
+    #[cfg(feature = "dac")]
+    fn handle_update_node_message(
+        &mut self,
+        update_node_message: UpdateNodeMessage,
+    ) -> Result<(), UpdateNodeError> {
+        let message = update_node_message.try_into::<WavetableNodeMessage>()?;
+
+        match message {
+            WavetableNodeMessage::SetFrequency { new_frequency } => {
+                self.set_frequency(new_frequency);
+            }
+            WavetableNodeMessage::ResetPhase => {
+                self.reset_phase();
+            }
+            WavetableNodeMessage::SetMorph { morph } => {
+                self.set_morph(morph);
+            }
+            WavetableNodeMessage::SetTables { tables } => {
+                self.set_tables(tables);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub enum WavetableNodeMessage {
+    SetFrequency { new_frequency: f32 },
+    ResetPhase,
+    SetMorph { morph: f32 },
+    SetTables { tables: Vec<Arc<Wavetable>> },
+}
+
+impl PartialEq for WavetableNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for WavetableNode {}
+
+impl PartialOrd for WavetableNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WavetableNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+#[cfg(test)]
+mod test_wavetable_node {
+    use std::{cell::RefCell, sync::Arc};
+
+    use resonix_core::Wavetable;
+    use resonix_test_utils::assert_difference_is_within_tolerance;
+
+    use crate::{Connection, Node, WavetableNode};
+
+    #[test]
+    fn should_output_wavetable_data() {
+        let mut wavetable_node =
+            WavetableNode::new_with_full_config(0, 2, 4, 1.0, vec![Arc::new(Wavetable::sine())]);
+        let output_connection = RefCell::new(Connection::from_test_data(1, 2, vec![0.0; 2], 0, 0));
+
+        let mut samples = Vec::new();
+        for _ in 0..3 {
+            {
+                let outputs = [output_connection.borrow_mut()];
+                wavetable_node.process(&mut [].into_iter(), &mut outputs.into_iter());
+            }
+            samples.push(output_connection.borrow().data().to_vec());
+        }
+
+        for (frame, expected) in samples.iter().zip([0.0, 1.0, 0.0]) {
+            frame.iter().for_each(|sample| {
+                assert_difference_is_within_tolerance(*sample, expected, 0.0001)
+            });
+        }
+    }
+
+    #[cfg(feature = "dac")]
+    #[test]
+    fn accepts_node_message_request() {
+        use crate::{messages::UpdateNodeMessage, WavetableNodeMessage};
+
+        let mut wavetable_node = WavetableNode::new_with_full_config(0, 1, 4, 1.0, Vec::new());
+
+        wavetable_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(WavetableNodeMessage::SetTables {
+                    tables: vec![Arc::new(Wavetable::sine()), Arc::new(Wavetable::sine())],
+                }),
+            })
+            .unwrap();
+        wavetable_node
+            .handle_update_node_message(UpdateNodeMessage {
+                node_uid: 0,
+                data: Box::new(WavetableNodeMessage::SetMorph { morph: 0.5 }),
+            })
+            .unwrap();
+
+        assert_eq!(wavetable_node.tables().len(), 2);
+        assert_eq!(wavetable_node.morph(), 0.5);
+    }
+}
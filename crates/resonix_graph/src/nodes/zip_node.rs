@@ -0,0 +1,234 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefMut},
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+};
+
+use resonix_core::NumChannels;
+
+use crate::{Connection, Node, NodeType, NodeUid};
+
+/// Runs a closure on every frame of two incoming signals, which allows combining signals
+/// with custom math without writing a new `Node` implementation.
+///
+/// The closure receives the frames of both incoming signals (one sample per channel)
+/// and writes into the outgoing frame. If either input is not connected,
+/// the closure receives silence in its place.
+///
+/// The closure runs on the audio thread, so it should avoid allocating, locking, or blocking.
+///
+/// Input 0 - Signal 1
+/// Input 1 - Signal 2
+///
+/// Output 0 - Combined signal
+#[derive(Clone)]
+pub struct ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    uid: NodeUid,
+    num_channels: NumChannels,
+    zip: F,
+    /// Passed to the closure in place of unconnected inputs
+    silence: Vec<f32>,
+}
+
+impl<F> ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    pub const FIRST_INPUT_INDEX: usize = 0;
+    pub const SECOND_INPUT_INDEX: usize = 1;
+
+    pub fn new(num_channels: impl Into<NumChannels>, zip: F) -> Self {
+        Self::new_with_uid(0, num_channels, zip)
+    }
+
+    pub(crate) fn new_with_uid(uid: NodeUid, num_channels: impl Into<NumChannels>, zip: F) -> Self {
+        let num_channels = num_channels.into();
+        Self {
+            uid,
+            num_channels,
+            zip,
+            silence: vec![0.0; *num_channels],
+        }
+    }
+}
+
+impl<F> Node for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    #[inline]
+    fn process(
+        &mut self,
+        inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+        outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+    ) {
+        let mut first = None;
+        let mut second = None;
+
+        for input in inputs {
+            match input.to_index() {
+                Self::SECOND_INPUT_INDEX => second = Some(input),
+                _ => first = Some(input),
+            }
+        }
+
+        let mut output = outputs
+            .next()
+            .expect("ZipNode should have one and only one output connection");
+
+        (self.zip)(
+            first.as_ref().map_or(&self.silence, |first| first.data()),
+            second
+                .as_ref()
+                .map_or(&self.silence, |second| second.data()),
+            output.data_mut(),
+        );
+    }
+
+    fn node_type(&self) -> NodeType {
+        NodeType::Effect
+    }
+
+    fn num_input_connections(&self) -> usize {
+        2
+    }
+
+    fn num_output_connections(&self) -> usize {
+        1
+    }
+
+    fn num_incoming_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn num_outgoing_channels(&self) -> NumChannels {
+        self.num_channels
+    }
+
+    fn uid(&self) -> NodeUid {
+        self.uid
+    }
+
+    fn set_uid(&mut self, uid: NodeUid) {
+        self.uid = uid;
+    }
+
+    fn name(&self) -> String {
+        String::from("ZipNode")
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<F> Debug for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipNode")
+            .field("uid", &self.uid)
+            .field("num_channels", &self.num_channels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> PartialEq for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl<F> Eq for ZipNode<F> where F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static {}
+
+impl<F> PartialOrd for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F> Ord for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.uid.cmp(&other.uid)
+    }
+}
+
+impl<F> Hash for ZipNode<F>
+where
+    F: Fn(&[f32], &[f32], &mut [f32]) + Send + Clone + 'static,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.uid.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test_zip_node {
+    use std::cell::RefCell;
+
+    use crate::{Connection, Node, ZipNode};
+
+    fn max(first: &[f32], second: &[f32], output: &mut [f32]) {
+        output
+            .iter_mut()
+            .zip(first.iter().zip(second))
+            .for_each(|(output, (first, second))| *output = first.max(*second))
+    }
+
+    #[test]
+    fn should_combine_inputs_with_closure() {
+        let mut zip_node = ZipNode::new(2, max);
+
+        let first_input_connection =
+            RefCell::new(Connection::from_test_data(0, 2, vec![0.5, -1.0], 0, 0));
+        let second_input_connection =
+            RefCell::new(Connection::from_test_data(1, 2, vec![0.25, 2.0], 0, 1));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [
+                first_input_connection.borrow(),
+                second_input_connection.borrow(),
+            ];
+            let outputs = [output_connection.borrow_mut()];
+            zip_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.5, 2.0]);
+    }
+
+    #[test]
+    fn should_use_silence_for_unconnected_input() {
+        let mut zip_node = ZipNode::new(2, max);
+
+        let second_input_connection =
+            RefCell::new(Connection::from_test_data(1, 2, vec![-0.25, 2.0], 0, 1));
+        let output_connection = RefCell::new(Connection::new(2));
+
+        {
+            let inputs = [second_input_connection.borrow()];
+            let outputs = [output_connection.borrow_mut()];
+            zip_node.process(&mut inputs.into_iter(), &mut outputs.into_iter())
+        }
+
+        assert_eq!(output_connection.borrow().data(), &vec![0.0, 2.0]);
+    }
+}
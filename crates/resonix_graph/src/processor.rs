@@ -250,7 +250,7 @@ impl Processor {
                 to_index,
             )?;
 
-            Self::check_num_channels_compatibility(&parent_node.borrow(), &child_node.borrow())?;
+            Self::check_input_num_channels(&parent_node.borrow(), &child_node.borrow(), to_index)?;
 
             if parent_node_uid == child_node_uid {
                 return Err(ConnectError::GraphCycleFound {
@@ -290,6 +290,8 @@ impl Processor {
         self.add_outgoing_connection_index(parent_uuid, edge_index);
         self.add_incoming_connection_index(child_uuid, edge_index);
 
+        self.reset_visit_order_cache();
+
         Ok(edge_index)
     }
 
@@ -331,8 +333,8 @@ impl Processor {
             || to_index >= child_node.num_input_connections()
         {
             return Err(ConnectError::IncorrectIndex {
-                expected_from_index: parent_node.num_input_connections() - 1,
-                expected_to_index: child_node.num_input_connections() - 1,
+                expected_from_index: parent_node.num_output_connections().saturating_sub(1),
+                expected_to_index: child_node.num_input_connections().saturating_sub(1),
                 from_index,
                 to_index,
                 parent_node_name: parent_node.name(),
@@ -374,15 +376,21 @@ impl Processor {
     }
 
     /// Regardless of the node type, the outgoing number of channels of a parent
-    /// should always match the incoming number of channels for the child node
+    /// should always match the incoming number of channels for the child node,
+    /// unless the child's input accepts any number of channels (e.g. modulation inputs)
     ///
     /// Note: given a single node, the number its incoming and outgoing connections
     /// do not necessarily need to match. For example, a Downmix node could take multichannel
     /// input and mix it down to single-channel output.
-    fn check_num_channels_compatibility(
+    fn check_input_num_channels(
         parent_node: &BoxedNode,
         child_node: &BoxedNode,
+        input_index: usize,
     ) -> Result<(), ConnectError> {
+        if child_node.accepts_any_num_channels(input_index) {
+            return Ok(());
+        }
+
         let parent_node_num_outgoing_channels = parent_node.num_outgoing_channels();
         let child_node_num_incoming_channels = child_node.num_incoming_channels();
         if parent_node_num_outgoing_channels != child_node_num_incoming_channels {
@@ -466,19 +474,12 @@ impl Processor {
         parent_node_uid: NodeUid,
         child_node_uid: NodeUid,
     ) -> Result<EdgeIndex, ConnectError> {
-        let result = self.connect_with_indexes(
+        self.connect_with_indexes(
             parent_node_uid,
             child_node_uid,
             Default::default(),
             Default::default(),
-        );
-
-        // reset visit order cache
-        if result.is_ok() {
-            self.reset_visit_order_cache();
-        }
-
-        result
+        )
     }
 
     /// Incrementing `uid` counter for objects added to the `AudioContext`
@@ -534,10 +535,97 @@ impl DerefMut for Processor {
 #[cfg(test)]
 mod test_processor {
 
+    use std::{
+        any::Any,
+        cell::{Ref, RefMut},
+    };
+
+    use resonix_core::NumChannels;
+
     use crate::{
-        messages::ConnectError, ConstantNode, DACNode, PassThroughNode, Processor, SineNode,
+        messages::ConnectError, Connection, ConstantNode, DACNode, Node, NodeType, NodeUid,
+        PassThroughNode, Processor, SineNode,
     };
 
+    /// Adds the first channel of its modulation input (input 1)
+    /// to every channel of its signal input (input 0)
+    #[derive(Debug, Default, Clone)]
+    struct ModulatedNode {
+        uid: NodeUid,
+        num_channels: NumChannels,
+    }
+
+    impl Node for ModulatedNode {
+        fn process(
+            &mut self,
+            inputs: &mut dyn Iterator<Item = Ref<Connection>>,
+            outputs: &mut dyn Iterator<Item = RefMut<Connection>>,
+        ) {
+            let mut signal = vec![0.0; *self.num_channels];
+            let mut modulation = 0.0;
+            for input in inputs {
+                if input.to_index() == 0 {
+                    signal.copy_from_slice(input.data());
+                } else {
+                    modulation = input.data()[0];
+                }
+            }
+
+            for mut output in outputs {
+                output.update_data(|frame| {
+                    frame
+                        .iter_mut()
+                        .zip(signal.iter())
+                        .for_each(|(output, signal)| *output = signal + modulation)
+                });
+            }
+        }
+
+        fn node_type(&self) -> NodeType {
+            NodeType::Effect
+        }
+
+        fn num_input_connections(&self) -> usize {
+            2
+        }
+
+        fn num_output_connections(&self) -> usize {
+            1
+        }
+
+        fn num_incoming_channels(&self) -> NumChannels {
+            self.num_channels
+        }
+
+        fn num_outgoing_channels(&self) -> NumChannels {
+            self.num_channels
+        }
+
+        fn accepts_any_num_channels(&self, input_index: usize) -> bool {
+            input_index == 1
+        }
+
+        fn uid(&self) -> NodeUid {
+            self.uid
+        }
+
+        fn set_uid(&mut self, uid: NodeUid) {
+            self.uid = uid;
+        }
+
+        fn name(&self) -> String {
+            String::from("ModulatedNode")
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
     #[test]
     fn rejects_connection_to_self() {
         let mut processor = Processor::default();
@@ -670,4 +758,100 @@ mod test_processor {
             assert_eq!(pass_through_to_dac_edge.borrow().data(), &vec![0.5]);
         }
     }
+
+    #[test]
+    fn connecting_with_indexes_should_route_data_to_the_given_input() {
+        let mut processor = Processor::default();
+        let signal_node = ConstantNode::new(2, 0.5);
+        let modulation_node = ConstantNode::new(1, 0.25);
+        let modulated_node = ModulatedNode {
+            num_channels: NumChannels::from(2),
+            ..Default::default()
+        };
+        let dac_node = DACNode::new(2);
+
+        let signal_node_uid = processor.add_node(signal_node).unwrap();
+        let modulation_node_uid = processor.add_node(modulation_node).unwrap();
+        let modulated_node_uid = processor.add_node(modulated_node).unwrap();
+        let dac_node_uid = processor.add_node(dac_node).unwrap();
+
+        processor
+            .connect_with_indexes(signal_node_uid, modulated_node_uid, 0, 0)
+            .unwrap();
+        let modulation_to_modulated_edge_index = processor
+            .connect_with_indexes(modulation_node_uid, modulated_node_uid, 0, 1)
+            .unwrap();
+        let modulated_to_dac_edge_index =
+            processor.connect(modulated_node_uid, dac_node_uid).unwrap();
+
+        {
+            let modulation_to_modulated_edge = processor
+                .graph
+                .edge_weight(modulation_to_modulated_edge_index)
+                .unwrap();
+            assert_eq!(modulation_to_modulated_edge.borrow().from_index(), 0);
+            assert_eq!(modulation_to_modulated_edge.borrow().to_index(), 1);
+        }
+
+        // the mono modulation signal is added to every channel of the signal
+        processor.run();
+
+        let modulated_to_dac_edge = processor
+            .graph
+            .edge_weight(modulated_to_dac_edge_index)
+            .unwrap();
+        assert_eq!(modulated_to_dac_edge.borrow().data(), &vec![0.75, 0.75]);
+    }
+
+    #[test]
+    fn rejects_incompatible_num_channels_unless_input_accepts_any() {
+        let mut processor = Processor::default();
+
+        let mono_node_uid = processor.add_node(ConstantNode::new(1, 1.0)).unwrap();
+        let modulated_node_uid = processor
+            .add_node(ModulatedNode {
+                num_channels: NumChannels::from(2),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let result = processor.connect_with_indexes(mono_node_uid, modulated_node_uid, 0, 0);
+        assert!(matches!(
+            result,
+            Err(ConnectError::IncompatibleNumChannels { .. })
+        ));
+
+        let result = processor.connect_with_indexes(mono_node_uid, modulated_node_uid, 0, 1);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_connection_indexes() {
+        let mut processor = Processor::default();
+
+        let constant_node_uid = processor.add_node(ConstantNode::new(1, 0.5)).unwrap();
+        let pass_through_node_uid = processor.add_node(PassThroughNode::new(1)).unwrap();
+
+        let result = processor.connect_with_indexes(constant_node_uid, pass_through_node_uid, 0, 1);
+        assert!(matches!(result, Err(ConnectError::IncorrectIndex { .. })));
+
+        let result = processor.connect_with_indexes(constant_node_uid, pass_through_node_uid, 1, 0);
+        assert!(matches!(result, Err(ConnectError::IncorrectIndex { .. })));
+    }
+
+    #[test]
+    fn connecting_with_indexes_should_reset_visit_order() {
+        let mut processor = Processor::default();
+
+        let constant_node_uid = processor.add_node(ConstantNode::new(1, 0.5)).unwrap();
+        let dac_node_uid = processor.add_node(DACNode::new(1)).unwrap();
+
+        processor.initialize_visit_order();
+        assert!(processor.visit_order.is_some());
+
+        processor
+            .connect_with_indexes(constant_node_uid, dac_node_uid, 0, 0)
+            .unwrap();
+        assert!(processor.visit_order.is_none());
+    }
 }
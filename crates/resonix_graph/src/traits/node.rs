@@ -42,6 +42,13 @@ where
 
     fn num_outgoing_channels(&self) -> NumChannels;
 
+    /// Whether the given input accepts connections with any number of channels,
+    /// rather than only `num_incoming_channels` (e.g. a modulation input that only
+    /// reads its first channel, so a mono signal can modulate a multichannel node)
+    fn accepts_any_num_channels(&self, _input_index: usize) -> bool {
+        false
+    }
+
     fn uid(&self) -> NodeUid;
 
     fn set_uid(&mut self, uid: NodeUid);
@@ -109,6 +116,10 @@ impl Node for BoxedNode {
         (**self).num_outgoing_channels()
     }
 
+    fn accepts_any_num_channels(&self, input_index: usize) -> bool {
+        (**self).accepts_any_num_channels(input_index)
+    }
+
     fn uid(&self) -> NodeUid {
         (**self).uid()
     }
@@ -1,20 +1,17 @@
 use resonix::{
-    granular_synthesizer::GranularSynthesizer, granular_synthesizer::GranularSynthesizerAction,
-    AudioContext, DACNode, DownmixNode, Downmixer, GranularSynthesizerNode,
+    decode_audio_with_extension, granular_synthesizer::GranularSynthesizer,
+    granular_synthesizer::GranularSynthesizerAction, resample_buffer, AudioContext, DACConfig,
+    DACNode, DownmixNode, Downmixer, GranularSynthesizerNode, ResampleQuality,
 };
-use rodio::{Decoder, Source};
 use std::{sync::Arc, time::Duration};
 
-/// Converts default mp3 file to raw audio sample data
-fn load_default_buffer() -> Arc<Vec<f32>> {
+/// Converts default mp3 file to raw audio sample data at the given sample rate
+fn load_default_buffer(sample_rate: u32) -> Arc<Vec<f32>> {
     // get audio file data as compile time
-    let audio_file_slice =
-        std::io::Cursor::new(include_bytes!("../../../assets/ecce_nova_3.mp3").as_ref());
-    let mp3_source = Decoder::new(audio_file_slice).unwrap();
-    let num_channels = mp3_source.channels() as usize;
-    let mp3_source_data: Vec<f32> =
-        granular_synthesizer_cli::utils::i16_array_to_f32(mp3_source.collect());
-    let left_channel_audio_data = mp3_source_data.into_iter().step_by(num_channels).collect();
+    let audio_file_bytes = include_bytes!("../../../assets/ecce_nova_3.mp3");
+    let audio_buffer = decode_audio_with_extension(audio_file_bytes, "mp3").unwrap();
+    let audio_buffer = resample_buffer(&audio_buffer, sample_rate, ResampleQuality::High);
+    let left_channel_audio_data = audio_buffer.into_channels().swap_remove(0);
 
     Arc::new(left_channel_audio_data)
 }
@@ -22,10 +19,11 @@ fn load_default_buffer() -> Arc<Vec<f32>> {
 #[tokio::main]
 pub async fn main() {
     let mut audio_context = AudioContext::new();
+    let dac_config = Arc::new(DACConfig::from_defaults().unwrap());
 
     let mut granular_synthesizer = GranularSynthesizer::new();
     granular_synthesizer
-        .set_buffer(load_default_buffer())
+        .set_buffer(load_default_buffer(dac_config.sample_rate()))
         .set_grain_len(Duration::from_millis(1000))
         .set_num_channels(50);
     let granular_synthesizer_node = GranularSynthesizerNode::new(granular_synthesizer);
@@ -43,7 +41,9 @@ pub async fn main() {
         .connect(downmix_node_handle, dac_node_handle)
         .unwrap();
 
-    let _audio_context = audio_context.into_audio_init().unwrap();
+    let _audio_context = audio_context
+        .into_audio_init_from_config(dac_config)
+        .unwrap();
 
     tokio::time::sleep(Duration::MAX).await;
 }
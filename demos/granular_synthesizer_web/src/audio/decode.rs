@@ -1,39 +1,62 @@
+use std::path::Path;
+
+use resonix::{
+    decode_audio, decode_audio_with_extension, resample_buffer, AudioBuffer, DACConfig,
+    ResampleQuality,
+};
 use thiserror::Error;
-use wasm_bindgen::JsCast;
-use web_sys::{AudioBuffer, AudioContext};
+use wasm_bindgen::JsValue;
 
 #[derive(Error, Debug)]
 pub enum DecodeBytesError {
     #[error("failed to decode audio data")]
-    DecodeFailure,
-    #[error("failed to convert promise to future")]
-    PromiseToFutureFailure,
-    #[error("failed to convert JavaScript type to expected Rust type")]
-    TypeConversionFailure,
+    DecodeFailure(#[from] resonix::DecodeError),
 }
 
-/// Decodes raw bytes into a JavaScript AudioBuffer, using the browser's built-in `decode_audio_data` functionality.
+/// Decodes the raw bytes of the audio file `file_name` into an AudioBuffer, resampled to `sample_rate`.
+///
+/// If `sample_rate` is 0 (i.e. audio has not been initialized yet), the audio
+/// is resampled to the sample rate that audio will be initialized with instead.
+///
+/// Decoding and resampling each run synchronously, but control is handed back to the
+/// browser before each of them, so that the UI can update (e.g. to show that audio is loading).
 pub async fn decode_bytes(
-    audio_context: &AudioContext,
     bytes: &[u8],
+    file_name: &str,
+    sample_rate: u32,
 ) -> Result<AudioBuffer, DecodeBytesError> {
-    // This action is "unsafe" because it's creating a JavaScript view into wasm linear memory.
-    // This is low risk as long as no allocations are made between this call and `decode_audio_data`.
-    let mp3_u_int8_array = unsafe { js_sys::Uint8Array::view(bytes) };
-
-    // this data must be copied, because decodeAudioData() claims the ArrayBuffer it receives
-    let mp3_u_int8_array = mp3_u_int8_array.slice(0, mp3_u_int8_array.length());
+    let sample_rate = if sample_rate == 0 {
+        DACConfig::from_defaults()
+            .map(|dac_config| dac_config.sample_rate())
+            .unwrap_or_default()
+    } else {
+        sample_rate
+    };
 
-    let decoded_audio_promise = audio_context
-        .decode_audio_data(&mp3_u_int8_array.buffer())
-        .map_err(|_| DecodeBytesError::DecodeFailure)?;
+    yield_to_browser().await;
+    let audio_buffer = match Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some(extension) => decode_audio_with_extension(bytes, extension)?,
+        None => decode_audio(bytes)?,
+    };
 
-    let audio_buffer: web_sys::AudioBuffer =
-        wasm_bindgen_futures::JsFuture::from(decoded_audio_promise)
-            .await
-            .map_err(|_| DecodeBytesError::PromiseToFutureFailure)?
-            .dyn_into()
-            .map_err(|_| DecodeBytesError::TypeConversionFailure)?;
+    yield_to_browser().await;
+    Ok(resample_buffer(
+        &audio_buffer,
+        sample_rate,
+        ResampleQuality::High,
+    ))
+}
 
-    Ok(audio_buffer)
+/// Lets the browser handle any pending events (such as re-rendering) before continuing
+async fn yield_to_browser() {
+    let timeout = js_sys::Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback(&resolve)
+            .unwrap();
+    });
+    let _: Result<JsValue, JsValue> = wasm_bindgen_futures::JsFuture::from(timeout).await;
 }
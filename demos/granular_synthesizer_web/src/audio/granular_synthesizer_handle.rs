@@ -1,7 +1,7 @@
 use resonix::granular_synthesizer::GranularSynthesizer;
 use resonix::granular_synthesizer::GranularSynthesizerAction;
 use resonix::Percentage;
-use resonix::{EnvelopeType, NumChannels};
+use resonix::{EnvelopeType, GrainPositionMode, NumChannels, PanMode};
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -125,6 +125,97 @@ impl GranularSynthesizerAction for GranularSynthesizerHandle {
             .unwrap()
             .grain_initialization_delay()
     }
+
+    fn pitch_shift(&self) -> f32 {
+        self.granular_synthesizer.lock().unwrap().pitch_shift()
+    }
+
+    fn set_pitch_shift(&mut self, semitones: f32) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_pitch_shift(semitones);
+
+        self
+    }
+
+    fn pitch_spread(&self) -> f32 {
+        self.granular_synthesizer.lock().unwrap().pitch_spread()
+    }
+
+    fn set_pitch_spread(&mut self, semitones: f32) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_pitch_spread(semitones);
+
+        self
+    }
+
+    fn position_mode(&self) -> GrainPositionMode {
+        self.granular_synthesizer.lock().unwrap().position_mode()
+    }
+
+    fn set_position_mode(&mut self, position_mode: GrainPositionMode) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_position_mode(position_mode);
+
+        self
+    }
+
+    fn scan_position(&self) -> Percentage {
+        self.granular_synthesizer.lock().unwrap().scan_position()
+    }
+
+    fn set_scan_position(&mut self, position: impl Into<Percentage>) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_scan_position(position);
+
+        self
+    }
+
+    fn num_grains(&self) -> usize {
+        self.granular_synthesizer.lock().unwrap().num_grains()
+    }
+
+    fn set_num_grains(&mut self, num_grains: impl Into<Option<usize>>) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_num_grains(num_grains);
+
+        self
+    }
+
+    fn pan_mode(&self) -> PanMode {
+        self.granular_synthesizer.lock().unwrap().pan_mode()
+    }
+
+    fn set_pan_mode(&mut self, pan_mode: PanMode) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_pan_mode(pan_mode);
+
+        self
+    }
+
+    fn pan_spread(&self) -> f32 {
+        self.granular_synthesizer.lock().unwrap().pan_spread()
+    }
+
+    fn set_pan_spread(&mut self, pan_spread: f32) -> &mut Self {
+        self.granular_synthesizer
+            .lock()
+            .unwrap()
+            .set_pan_spread(pan_spread);
+
+        self
+    }
 }
 
 impl Default for GranularSynthesizerHandle {
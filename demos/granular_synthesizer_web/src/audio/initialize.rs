@@ -18,11 +18,11 @@ use resonix::{
 use std::sync::Arc;
 use yew::UseReducerHandle;
 
-/// Converts default mp3 file to raw audio sample data
-async fn load_default_buffer(app_state_handle: UseReducerHandle<AppState>) -> Arc<Vec<f32>> {
-    let audio_context =
-        web_sys::AudioContext::new().expect("Browser should have AudioContext implemented");
-
+/// Converts default mp3 file to raw audio sample data at the given sample rate
+async fn load_default_buffer(
+    app_state_handle: UseReducerHandle<AppState>,
+    sample_rate: u32,
+) -> Arc<Vec<f32>> {
     // audio files are copied into static director for web (same directory as source wasm file)
     // fetch a default audio file at initialization time
     let mp3_file_bytes = Request::get(&format!("./{}", DEFAULT_AUDIO_FILE))
@@ -33,10 +33,10 @@ async fn load_default_buffer(app_state_handle: UseReducerHandle<AppState>) -> Ar
         .await
         .unwrap();
 
-    let audio_buffer = decode::decode_bytes(&audio_context, &mp3_file_bytes)
+    let audio_buffer = decode::decode_bytes(&mp3_file_bytes, DEFAULT_AUDIO_FILE, sample_rate)
         .await
         .unwrap();
-    let mp3_source_data = Arc::new(audio_buffer.get_channel_data(0).unwrap());
+    let mp3_source_data = Arc::new(audio_buffer.channel(0).unwrap_or_default().to_vec());
     app_state_handle.dispatch(AppAction::SetBuffer(Arc::clone(&mp3_source_data)));
 
     mp3_source_data
@@ -66,7 +66,7 @@ pub async fn initialize_audio(app_state_handle: UseReducerHandle<AppState>) -> D
 
     // only load if buffer hasn't been loaded
     if app_state_handle.buffer_handle.get_data().is_empty() {
-        load_default_buffer(app_state_handle.clone()).await;
+        load_default_buffer(app_state_handle.clone(), output_sample_rate).await;
     }
 
     let buffer_selection_handle = app_state_handle.buffer_selection_handle.clone();
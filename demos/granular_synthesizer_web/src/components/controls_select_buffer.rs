@@ -44,7 +44,8 @@ pub fn controls_select_buffer() -> Html {
                     .dyn_into::<HtmlSelectElement>()
                     .unwrap();
                 let selected_index = select_element.selected_index();
-                let request_url = format!("./{}", AUDIO_FILES[selected_index as usize]);
+                let file_name = AUDIO_FILES[selected_index as usize];
+                let request_url = format!("./{}", file_name);
 
                 // audio files are copied into static directory for web (same directory as the source wasm file)
                 let mp3_file_bytes = Request::get(&request_url)
@@ -55,15 +56,14 @@ pub fn controls_select_buffer() -> Html {
                     .await
                     .unwrap();
 
-                // @todo: initialize a single audio_context at the top level of the app
-                let audio_context = web_sys::AudioContext::new()
-                    .expect("Browser should have AudioContext implemented");
                 let audio_buffer_result =
-                    decode::decode_bytes(&audio_context, &mp3_file_bytes).await;
+                    decode::decode_bytes(&mp3_file_bytes, file_name, state_handle.sample_rate)
+                        .await;
 
                 match audio_buffer_result {
                     Ok(audio_buffer) => {
-                        let buffer_data = Arc::new(audio_buffer.get_channel_data(0).unwrap());
+                        let buffer_data =
+                            Arc::new(audio_buffer.channel(0).unwrap_or_default().to_vec());
 
                         state_handle.dispatch(AppAction::SetBuffer(buffer_data));
                     }
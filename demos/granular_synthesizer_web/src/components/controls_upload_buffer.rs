@@ -54,16 +54,17 @@ pub fn controls_upload_buffer() -> Html {
                         let file_array_buffer = Uint8Array::new(file_array_buffer.as_ref());
                         let file_bytes = file_array_buffer.to_vec();
 
-                        // @todo: initialize a single audio_context at the top level of the app
-                        let audio_context = web_sys::AudioContext::new()
-                            .expect("Browser should have AudioContext implemented");
-                        let audio_buffer_result =
-                            decode::decode_bytes(&audio_context, &file_bytes).await;
+                        let audio_buffer_result = decode::decode_bytes(
+                            &file_bytes,
+                            &file.name(),
+                            state_handle.sample_rate,
+                        )
+                        .await;
 
                         match audio_buffer_result {
                             Ok(audio_buffer) => {
                                 let buffer_data =
-                                    Arc::new(audio_buffer.get_channel_data(0).unwrap());
+                                    Arc::new(audio_buffer.channel(0).unwrap_or_default().to_vec());
 
                                 state_handle.dispatch(AppAction::SetBuffer(buffer_data));
                             }